@@ -0,0 +1,334 @@
+use crate::arabic_char::ArabicChar;
+use crate::arabic_digits::DigitSet;
+
+/*
+Terminals that implement the Unicode bidirectional algorithm lay out a line holding Arabic as a
+right-to-left paragraph: english words and western numbers in it keep their own order, but
+neutral characters between them and the Arabic (spaces, signs, brackets) may end up on either
+side, and a caret printed under a column of such a line rarely sits under the character it
+points at. Terminals that don't implement it show the characters in memory order.
+
+Output is made predictable by isolating each left-to-right run, so only the run as a whole is
+placed in the right-to-left text. Source excerpts are laid out here instead: the line is put in
+the order a right-to-left paragraph displays and printed under a left-to-right override, which
+every terminal shows as is, so the caret line below it can be counted in the same columns.
+
+The layout follows the bidirectional algorithm for a single paragraph without explicit
+embeddings: numbers after Arabic letters read as Arabic numbers, separators between digits join
+them, western numbers after english read as english, and neutrals take the direction of the
+text on both sides of them, or the paragraph's.
+ */
+
+const FIRST_STRONG_ISOLATE: char = '\u{2068}';
+const POP_DIRECTIONAL_ISOLATE: char = '\u{2069}';
+const LEFT_TO_RIGHT_OVERRIDE: char = '\u{202D}';
+const POP_DIRECTIONAL_FORMATTING: char = '\u{202C}';
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Class {
+    /// Left to right letters.
+    L,
+    /// Arabic letters, and the Arabic punctuation that reads as letters.
+    R,
+    /// Western digits.
+    EN,
+    /// Arabic-Indic digits, and western ones after Arabic letters.
+    AN,
+    /// `+` and `-`, which join western digits.
+    ES,
+    /// `.`, `,`, `:`, `/` and `،`, which join digits of either kind.
+    CS,
+    /// Everything else.
+    N,
+}
+
+fn class_of(c: char) -> Class {
+    if DigitSet::Western.value_of(c).is_some() {
+        return Class::EN;
+    }
+    if DigitSet::ArabicIndic.value_of(c).is_some()
+        || DigitSet::EasternArabicIndic.value_of(c).is_some()
+        || c == '\u{066B}'
+        || c == '\u{066C}'
+    {
+        return Class::AN;
+    }
+    match c {
+        '+' | '-' => Class::ES,
+        '.' | ',' | ':' | '/' | '\u{060C}' => Class::CS,
+        '\u{0600}'..='\u{06FF}' | '\u{0750}'..='\u{077F}' | '\u{FB50}'..='\u{FDFF}' => Class::R,
+        '\u{FE70}'..='\u{FEFF}' => Class::R,
+        _ if c.is_alphabetic() => Class::L,
+        _ => Class::N,
+    }
+}
+
+/// A character and the marks that combine with it, which are displayed in one cell.
+fn clusters(line: &str) -> Vec<String> {
+    let mut clusters: Vec<String> = Vec::new();
+    for c in line.chars() {
+        let combining = c.is_arabic_diacritic() || ('\u{0300}'..='\u{036F}').contains(&c);
+        match clusters.last_mut() {
+            Some(cluster) if combining => cluster.push(c),
+            _ => clusters.push(c.to_string()),
+        }
+    }
+    clusters
+}
+
+fn is_rtl_paragraph(classes: &[Class]) -> bool {
+    classes
+        .iter()
+        .find(|class| matches!(class, Class::L | Class::R))
+        .is_some_and(|class| *class == Class::R)
+}
+
+/// The embedding level of each class, in a paragraph at level `paragraph` (0 or 1).
+fn resolve_levels(mut classes: Vec<Class>, paragraph: u8) -> Vec<u8> {
+    let paragraph_class = if paragraph == 1 { Class::R } else { Class::L };
+
+    // western digits after Arabic letters are Arabic numbers
+    let mut last_strong = paragraph_class;
+    for class in classes.iter_mut() {
+        match *class {
+            Class::L | Class::R => last_strong = *class,
+            Class::EN if last_strong == Class::R => *class = Class::AN,
+            _ => {}
+        }
+    }
+
+    // a single separator between two numbers of the same kind joins them
+    for i in 1..classes.len().saturating_sub(1) {
+        let (before, after) = (classes[i - 1], classes[i + 1]);
+        classes[i] = match (classes[i], before, after) {
+            (Class::ES, Class::EN, Class::EN) | (Class::CS, Class::EN, Class::EN) => Class::EN,
+            (Class::CS, Class::AN, Class::AN) => Class::AN,
+            (class, _, _) => class,
+        };
+    }
+    for class in classes.iter_mut() {
+        if matches!(*class, Class::ES | Class::CS) {
+            *class = Class::N;
+        }
+    }
+
+    // western numbers after english read as english
+    let mut last_strong = paragraph_class;
+    for class in classes.iter_mut() {
+        match *class {
+            Class::L | Class::R => last_strong = *class,
+            Class::EN if last_strong == Class::L => *class = Class::L,
+            _ => {}
+        }
+    }
+
+    // neutrals between text of one direction take it, numbers counting as right to left
+    let direction = |class: Class| match class {
+        Class::L => Class::L,
+        _ => Class::R,
+    };
+    let mut i = 0;
+    while i < classes.len() {
+        if classes[i] != Class::N {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < classes.len() && classes[i] == Class::N {
+            i += 1;
+        }
+        let before = start
+            .checked_sub(1)
+            .map_or(paragraph_class, |j| direction(classes[j]));
+        let after = classes.get(i).map_or(paragraph_class, |c| direction(*c));
+        let resolved = if before == after {
+            before
+        } else {
+            paragraph_class
+        };
+        classes[start..i].fill(resolved);
+    }
+
+    classes
+        .into_iter()
+        .map(|class| match (paragraph, class) {
+            (0, Class::L) => 0,
+            (0, Class::R) => 1,
+            (0, _) => 2,
+            (_, Class::R) => 1,
+            _ => 2,
+        })
+        .collect()
+}
+
+/// The logical index of the cluster shown at each visual position, from the left.
+fn visual_order(levels: &[u8]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..levels.len()).collect();
+    let highest = levels.iter().copied().max().unwrap_or(0);
+    for level in (1..=highest).rev() {
+        let mut i = 0;
+        while i < order.len() {
+            if levels[order[i]] < level {
+                i += 1;
+                continue;
+            }
+            let start = i;
+            while i < order.len() && levels[order[i]] >= level {
+                i += 1;
+            }
+            order[start..i].reverse();
+        }
+    }
+    order
+}
+
+fn mirrored(cluster: &str) -> &str {
+    match cluster {
+        "(" => ")",
+        ")" => "(",
+        "[" => "]",
+        "]" => "[",
+        "{" => "}",
+        "}" => "{",
+        "<" => ">",
+        ">" => "<",
+        _ => cluster,
+    }
+}
+
+/// `line` with a line below it holding a caret under the cluster at `col`, counting clusters
+/// from zero in memory order. A line that contains right-to-left text is printed in the order
+/// it displays in.
+pub fn caret_excerpt(line: &str, col: usize) -> String {
+    let line = line.replace('\t', " ");
+    let clusters = clusters(&line);
+    let col = col.min(clusters.len().saturating_sub(1));
+
+    let classes: Vec<Class> = clusters
+        .iter()
+        .map(|cluster| class_of(cluster.chars().next().unwrap()))
+        .collect();
+    let paragraph = u8::from(is_rtl_paragraph(&classes));
+    let levels = resolve_levels(classes, paragraph);
+
+    if levels.iter().all(|level| *level == 0) {
+        return format!("{}\n{}^", line, " ".repeat(col));
+    }
+
+    let order = visual_order(&levels);
+    let mut visual = String::from(LEFT_TO_RIGHT_OVERRIDE);
+    for &i in &order {
+        match levels[i] % 2 {
+            1 => visual.push_str(mirrored(&clusters[i])),
+            _ => visual.push_str(&clusters[i]),
+        }
+    }
+    visual.push(POP_DIRECTIONAL_FORMATTING);
+
+    let caret = order.iter().position(|&i| i == col).unwrap_or(0);
+    format!("{}\n{}^", visual, " ".repeat(caret))
+}
+
+/// Wraps each run of english words and western numbers in `text` in a first strong isolate, so a
+/// terminal showing the text right to left moves the run as a whole. Text without right-to-left
+/// characters is returned as is.
+pub fn isolate_ltr_runs(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    if !chars.iter().any(|&c| class_of(c) == Class::R) {
+        return text.to_string();
+    }
+
+    let starts_run = |i: usize| match class_of(chars[i]) {
+        Class::L | Class::EN => true,
+        // a sign belongs to the number after it
+        Class::ES => chars.get(i + 1).is_some_and(|&c| class_of(c) == Class::EN),
+        _ => false,
+    };
+
+    let mut res = String::with_capacity(text.len());
+    let mut in_run = false;
+    // neutrals seen in a run, which only belong to it if it goes on after them
+    let mut pending = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        let class = class_of(c);
+        if in_run && (starts_run(i) || class == Class::EN) {
+            res.push_str(&pending);
+            pending.clear();
+            res.push(c);
+        } else if in_run && (class == Class::R || class == Class::AN || c == '\n') {
+            res.push(POP_DIRECTIONAL_ISOLATE);
+            res.push_str(&pending);
+            pending.clear();
+            res.push(c);
+            in_run = false;
+        } else if in_run {
+            pending.push(c);
+        } else if starts_run(i) {
+            res.push(FIRST_STRONG_ISOLATE);
+            res.push(c);
+            in_run = true;
+        } else {
+            res.push(c);
+        }
+    }
+    if in_run {
+        res.push(POP_DIRECTIONAL_ISOLATE);
+    }
+    res.push_str(&pending);
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::caret_excerpt;
+    use super::isolate_ltr_runs;
+
+    #[test]
+    fn it_should_isolate_ltr_runs() {
+        assert_eq!(
+            isolate_ltr_runs("القيمة x = 42 تقريبا"),
+            "القيمة \u{2068}x = 42\u{2069} تقريبا"
+        );
+        assert_eq!(
+            isolate_ltr_runs("الرصيد -5 ريال"),
+            "الرصيد \u{2068}-5\u{2069} ريال"
+        );
+        assert_eq!(isolate_ltr_runs("العدد ٤٢"), "العدد ٤٢");
+        assert_eq!(
+            isolate_ltr_runs("مرحبا hello"),
+            "مرحبا \u{2068}hello\u{2069}"
+        );
+    }
+
+    #[test]
+    fn it_should_leave_ltr_text_alone() {
+        assert_eq!(isolate_ltr_runs("x = 42 (ok)"), "x = 42 (ok)");
+    }
+
+    #[test]
+    fn it_should_point_into_ltr_lines() {
+        assert_eq!(caret_excerpt("var x = 1", 4), "var x = 1\n    ^");
+        assert_eq!(caret_excerpt("x", 5), "x\n^");
+    }
+
+    #[test]
+    fn it_should_lay_out_rtl_lines_in_display_order() {
+        // دع س = ١٠؛ shows from right to left, the number keeping its digit order
+        assert_eq!(
+            caret_excerpt("دع س = ١٠؛", 3),
+            "\u{202D}؛١٠ = س عد\u{202C}\n      ^"
+        );
+        assert_eq!(
+            caret_excerpt("دع س = ١٠؛", 7),
+            "\u{202D}؛١٠ = س عد\u{202C}\n ^"
+        );
+    }
+
+    #[test]
+    fn it_should_keep_ltr_runs_in_rtl_lines() {
+        assert_eq!(
+            caret_excerpt("اطبع(abc)", 5),
+            "\u{202D}(abc)عبطا\u{202C}\n ^"
+        );
+    }
+}
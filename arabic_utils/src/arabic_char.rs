@@ -1,12 +1,24 @@
+use crate::arabic_digits::digit_value;
+use crate::arabic_digits::DigitSet;
+
 pub trait ArabicChar {
     fn is_arabic_digit(&self) -> bool;
     #[allow(clippy::wrong_self_convention)]
     fn is_arabic_alphabetic(self) -> bool;
+    /// Tashkeel: tanween, the short vowels, shadda, sukun and the dagger alef.
+    fn is_arabic_diacritic(&self) -> bool;
+    /// The kashida used to stretch words, e.g. `مـحـمـد`.
+    fn is_tatweel(&self) -> bool;
 
     /// (a)rabic (c)har (to) (e)nglish (c)har
     fn actoec(self) -> Option<char>;
     /// (e)nglish (c)har (to) (a)rabic (c)har
     fn ectoac(self) -> Option<char>;
+
+    /// The letter that spelling variants of it are folded into: alef with hamza or madda
+    /// becomes bare alef, hamza on waw or yaa becomes its seat, taa marbuta becomes haa,
+    /// alef maqsura becomes yaa, and the Persian forms of kaf and yaa become the Arabic ones.
+    fn normalize_letter(self) -> char;
 }
 
 impl ArabicChar for char {
@@ -15,12 +27,26 @@ impl ArabicChar for char {
     }
 
     fn is_arabic_alphabetic(self) -> bool {
-        ('\u{0621}'..='\u{063A}').contains(&self) || ('\u{0641}'..='\u{064A}').contains(&self)
+        ('\u{0621}'..='\u{063A}').contains(&self)
+            || ('\u{0641}'..='\u{064A}').contains(&self)
+            // alef wasla and the letters added for Persian, Urdu, Kurdish and other languages
+            || ('\u{0671}'..='\u{06D3}').contains(&self)
+            || ('\u{0750}'..='\u{077F}').contains(&self)
+    }
+
+    fn is_arabic_diacritic(&self) -> bool {
+        ('\u{064B}'..='\u{0652}').contains(self) || *self == '\u{0670}'
+    }
+
+    fn is_tatweel(&self) -> bool {
+        *self == '\u{0640}'
     }
 
     fn actoec(self) -> Option<char> {
-        if self.is_arabic_digit() {
-            return char::from_u32((self as u32) - ('\u{0660}' as u32) + ('0' as u32));
+        if let Some(value) =
+            digit_value(self, &[DigitSet::ArabicIndic, DigitSet::EasternArabicIndic])
+        {
+            return Some(DigitSet::Western.digit(value));
         }
         match self {
             // Arabic decimal seperator
@@ -38,6 +64,17 @@ impl ArabicChar for char {
         }
         None
     }
+
+    fn normalize_letter(self) -> char {
+        match self {
+            'أ' | 'إ' | 'آ' | 'ٱ' => 'ا',
+            'ؤ' => 'و',
+            'ئ' | 'ى' | 'ی' => 'ي',
+            'ک' => 'ك',
+            'ة' => 'ه',
+            _ => self,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -47,10 +84,10 @@ mod tests {
     #[test]
     fn it_should_return_true_when_provided_with_arabic_digit() {
         let c = '٢';
-        assert_eq!(c.is_arabic_digit(), true);
+        assert!(c.is_arabic_digit());
 
         let c = '٣';
-        assert_eq!(c.is_arabic_digit(), true);
+        assert!(c.is_arabic_digit());
     }
 
     #[test]
@@ -60,5 +97,52 @@ mod tests {
 
         let c = '٣';
         assert_eq!(c.actoec().unwrap(), '3');
+
+        let c = '۳';
+        assert_eq!(c.actoec().unwrap(), '3');
+    }
+
+    #[test]
+    fn it_should_normalize_letter_variants() {
+        assert_eq!('أ'.normalize_letter(), 'ا');
+        assert_eq!('إ'.normalize_letter(), 'ا');
+        assert_eq!('آ'.normalize_letter(), 'ا');
+        assert_eq!('ؤ'.normalize_letter(), 'و');
+        assert_eq!('ئ'.normalize_letter(), 'ي');
+        assert_eq!('ى'.normalize_letter(), 'ي');
+        assert_eq!('ة'.normalize_letter(), 'ه');
+        assert_eq!('ء'.normalize_letter(), 'ء');
+        assert_eq!('ب'.normalize_letter(), 'ب');
+    }
+
+    #[test]
+    fn it_should_accept_extended_letters() {
+        for c in ['پ', 'چ', 'ژ', 'گ', 'ک', 'ی', 'ٱ', 'ے', 'ݐ'] {
+            assert!(c.is_arabic_alphabetic());
+        }
+        // Urdu
+        for c in ['ٹ', 'ڈ', 'ڑ', 'ں', 'ہ', 'ھ', 'ے'] {
+            assert!(c.is_arabic_alphabetic());
+        }
+        // Kurdish
+        for c in ['ڵ', 'ۆ', 'ێ', 'ڕ', 'ڤ'] {
+            assert!(c.is_arabic_alphabetic());
+        }
+        assert!(!'۔'.is_arabic_alphabetic());
+        assert!(!'\u{064E}'.is_arabic_alphabetic());
+        assert!(!'\u{0640}'.is_arabic_alphabetic());
+    }
+
+    #[test]
+    fn it_should_recognize_tashkeel_and_tatweel() {
+        // fatha, kasra, shadda, sukun, tanween fath and the dagger alef
+        for c in [
+            '\u{064E}', '\u{0650}', '\u{0651}', '\u{0652}', '\u{064B}', '\u{0670}',
+        ] {
+            assert!(c.is_arabic_diacritic());
+        }
+        assert!(!'ب'.is_arabic_diacritic());
+        assert!('ـ'.is_tatweel());
+        assert!(!'ب'.is_tatweel());
     }
 }
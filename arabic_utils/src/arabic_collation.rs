@@ -0,0 +1,141 @@
+use crate::arabic_char::ArabicChar;
+use crate::arabic_digits::digit_value;
+use crate::arabic_digits::DigitSet;
+use std::cmp::Ordering;
+
+/*
+Code point order mostly follows the Arabic alphabet, but puts every hamza form of alef before
+the bare one and the letters added for Persian after all of the Arabic ones, and a word with
+tashkeel can land far from the same word without. Collating compares words by their letters
+first: tashkeel and tatweel are skipped, alef, waw and yaa are grouped with their hamza forms
+(`ArabicChar::normalize_letter`), lam-alef ligatures read as lam then alef, the Persian letters
+follow the Arabic letters they are drawn from, and digits of every set sort by value. Words that
+only differ in those respects are then ordered by code point, so distinct strings never compare
+equal.
+ */
+
+/// How strings are ordered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Collation {
+    /// By letter, as in a dictionary.
+    #[default]
+    Arabic,
+    /// By code point, as rust orders strings.
+    CodePoint,
+}
+
+impl Collation {
+    pub fn compare(self, left: &str, right: &str) -> Ordering {
+        match self {
+            Collation::Arabic => collation_key(left)
+                .cmp(&collation_key(right))
+                .then_with(|| left.cmp(right)),
+            Collation::CodePoint => left.cmp(right),
+        }
+    }
+}
+
+/// The weights `Collation::Arabic` compares `s` by.
+pub fn collation_key(s: &str) -> Vec<u32> {
+    let mut key = Vec::with_capacity(s.len());
+    for c in s.chars() {
+        if c.is_arabic_diacritic() || c.is_tatweel() {
+            continue;
+        }
+        match lam_alef(c) {
+            Some(alef) => {
+                key.push(weight('ل'));
+                key.push(weight(alef));
+            }
+            None => key.push(weight(c)),
+        }
+    }
+    key
+}
+
+/// The alef of a lam-alef ligature (U+FEF5 to U+FEFC).
+fn lam_alef(c: char) -> Option<char> {
+    match c {
+        '\u{FEF5}' | '\u{FEF6}' => Some('آ'),
+        '\u{FEF7}' | '\u{FEF8}' => Some('أ'),
+        '\u{FEF9}' | '\u{FEFA}' => Some('إ'),
+        '\u{FEFB}' | '\u{FEFC}' => Some('ا'),
+        _ => None,
+    }
+}
+
+// every character gets an even weight so the Persian letters fit right after their Arabic ones
+fn weight(c: char) -> u32 {
+    if let Some(value) = digit_value(c, &DigitSet::ALL) {
+        return DigitSet::Western.digit(value) as u32 * 2;
+    }
+    let after = |base: char| base as u32 * 2 + 1;
+    match c.normalize_letter() {
+        'پ' => after('ب'),
+        'چ' => after('ج'),
+        'ژ' => after('ز'),
+        'گ' => after('ك'),
+        c => c as u32 * 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Collation;
+    use std::cmp::Ordering;
+
+    fn sorted(words: &[&str], collation: Collation) -> Vec<String> {
+        let mut words: Vec<String> = words.iter().map(|word| word.to_string()).collect();
+        words.sort_by(|left, right| collation.compare(left, right));
+        words
+    }
+
+    #[test]
+    fn it_should_group_alef_variants() {
+        assert_eq!(
+            sorted(&["باب", "أحمد", "اسم", "إبراهيم", "آدم"], Collation::Arabic),
+            vec!["إبراهيم", "أحمد", "آدم", "اسم", "باب"]
+        );
+        assert_eq!(
+            sorted(&["باب", "أحمد", "اسم"], Collation::CodePoint),
+            vec!["أحمد", "اسم", "باب"]
+        );
+    }
+
+    #[test]
+    fn it_should_ignore_tashkeel_and_tatweel() {
+        assert_eq!(
+            sorted(&["كتب", "كَبير", "كـاتب"], Collation::Arabic),
+            vec!["كـاتب", "كَبير", "كتب"]
+        );
+        assert_ne!(Collation::Arabic.compare("عَدَد", "عدد"), Ordering::Equal);
+        assert_eq!(Collation::Arabic.compare("عدد", "عدد"), Ordering::Equal);
+    }
+
+    #[test]
+    fn it_should_read_lam_alef_ligatures_as_two_letters() {
+        assert_eq!(
+            sorted(&["لم", "\u{FEFB}زم", "لب"], Collation::Arabic),
+            vec!["\u{FEFB}زم", "لب", "لم"]
+        );
+    }
+
+    #[test]
+    fn it_should_put_persian_letters_after_their_arabic_ones() {
+        assert_eq!(
+            sorted(
+                &["تاج", "پدر", "بيت", "گل", "لون", "كتاب"],
+                Collation::Arabic
+            ),
+            vec!["بيت", "پدر", "تاج", "كتاب", "گل", "لون"]
+        );
+    }
+
+    #[test]
+    fn it_should_sort_digits_by_value() {
+        assert_eq!(
+            sorted(&["٣", "۲", "1"], Collation::Arabic),
+            vec!["1", "۲", "٣"]
+        );
+    }
+}
@@ -1 +1,7 @@
 pub const DECIMAL_SEPARATOR: char = '\u{066B}';
+/// Groups digits in long numbers, as in `١٬٠٠٠٬٠٠٠`.
+pub const THOUSANDS_SEPARATOR: char = '\u{066C}';
+pub const INFINITY: &str = "لانهاية";
+pub const NOT_A_NUMBER: &str = "ليس_رقما";
+/// Stands for `e` in scientific notation, as in `١٫٥أس٢١`.
+pub const EXPONENT_MARKER: &str = "أس";
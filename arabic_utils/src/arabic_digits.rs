@@ -0,0 +1,90 @@
+use crate::arabic_consts::DECIMAL_SEPARATOR;
+
+/// The digits a number is written with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DigitSet {
+    /// `٠١٢٣٤٥٦٧٨٩`, used across the Arab world.
+    #[default]
+    ArabicIndic,
+    /// `۰۱۲۳۴۵۶۷۸۹`, typed on Persian and Urdu keyboards.
+    EasternArabicIndic,
+    /// `0123456789`.
+    Western,
+}
+
+impl DigitSet {
+    pub const ALL: [DigitSet; 3] = [
+        DigitSet::ArabicIndic,
+        DigitSet::EasternArabicIndic,
+        DigitSet::Western,
+    ];
+
+    fn zero(self) -> char {
+        match self {
+            DigitSet::ArabicIndic => '\u{0660}',
+            DigitSet::EasternArabicIndic => '\u{06F0}',
+            DigitSet::Western => '0',
+        }
+    }
+
+    /// The value of `c` if it's one of this set's digits.
+    pub fn value_of(self, c: char) -> Option<u32> {
+        let value = (c as u32).checked_sub(self.zero() as u32)?;
+        (value < 10).then_some(value)
+    }
+
+    pub fn digit(self, value: u32) -> char {
+        assert!(value < 10, "{} is not a digit", value);
+        char::from_u32(self.zero() as u32 + value).unwrap()
+    }
+
+    /// `٫` goes with the Arabic-Indic digits and `.` with the western ones.
+    pub fn decimal_separator(self) -> char {
+        match self {
+            DigitSet::ArabicIndic | DigitSet::EasternArabicIndic => DECIMAL_SEPARATOR,
+            DigitSet::Western => '.',
+        }
+    }
+
+    /// Rewrites the western digits and decimal point of an already formatted number in this
+    /// set, leaving signs, exponents and the like as they are.
+    pub fn localize(self, formatted: &str) -> String {
+        formatted
+            .chars()
+            .map(|c| match c {
+                '0'..='9' => self.digit(c as u32 - '0' as u32),
+                '.' => self.decimal_separator(),
+                _ => c,
+            })
+            .collect()
+    }
+}
+
+/// The value of `c` if it's a digit in any of `sets`.
+pub fn digit_value(c: char, sets: &[DigitSet]) -> Option<u32> {
+    sets.iter().find_map(|set| set.value_of(c))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::digit_value;
+    use super::DigitSet;
+
+    #[test]
+    fn it_should_read_digits_of_every_set() {
+        assert_eq!(DigitSet::ArabicIndic.value_of('٧'), Some(7));
+        assert_eq!(DigitSet::EasternArabicIndic.value_of('۷'), Some(7));
+        assert_eq!(DigitSet::Western.value_of('7'), Some(7));
+        assert_eq!(DigitSet::ArabicIndic.value_of('۷'), None);
+        assert_eq!(DigitSet::Western.value_of('a'), None);
+        assert_eq!(digit_value('۴', &DigitSet::ALL), Some(4));
+        assert_eq!(digit_value('۴', &[DigitSet::ArabicIndic]), None);
+    }
+
+    #[test]
+    fn it_should_localize_formatted_numbers() {
+        assert_eq!(DigitSet::ArabicIndic.localize("-12.5"), "-١٢٫٥");
+        assert_eq!(DigitSet::EasternArabicIndic.localize("1/3"), "۱/۳");
+        assert_eq!(DigitSet::Western.localize("-12.5"), "-12.5");
+    }
+}
@@ -1,33 +1,103 @@
 use crate::arabic_char::ArabicChar;
+use crate::arabic_consts::EXPONENT_MARKER;
+use crate::arabic_consts::INFINITY;
+use crate::arabic_consts::NOT_A_NUMBER;
+use crate::arabic_digits::DigitSet;
+
+/// How `to_arabic_decimal_with` lays out a number.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NumberFormat {
+    /// Digits after the decimal separator, or `None` for the shortest form that reads back
+    /// as the same number.
+    pub precision: Option<usize>,
+    pub digits: DigitSet,
+}
+
+impl NumberFormat {
+    pub fn with_precision(precision: usize) -> NumberFormat {
+        NumberFormat {
+            precision: Some(precision),
+            ..NumberFormat::default()
+        }
+    }
+
+    pub fn with_digits(self, digits: DigitSet) -> NumberFormat {
+        NumberFormat { digits, ..self }
+    }
+}
 
 pub trait ArabicNumber {
-    fn to_arabic_decimal(&self) -> Option<String>;
+    fn to_arabic_decimal_with(&self, format: NumberFormat) -> Option<String>;
+
+    fn to_arabic_decimal(&self) -> Option<String> {
+        self.to_arabic_decimal_with(NumberFormat::default())
+    }
 }
 
+/*
+Like javascript, magnitudes from 1e21 up and below 1e-6 are written in scientific notation,
+since their fixed forms are mostly zeros.
+ */
+const SCIENTIFIC_ABOVE: f64 = 1e21;
+const SCIENTIFIC_BELOW: f64 = 1e-6;
+
 impl ArabicNumber for f64 {
-    fn to_arabic_decimal(&self) -> Option<String> {
-        if self == &f64::INFINITY {
-            return Some(String::from("لانهاية"));
-        }
-        if self == &f64::NEG_INFINITY {
-            todo!("Handle negative infinity")
-        }
+    fn to_arabic_decimal_with(&self, format: NumberFormat) -> Option<String> {
         if self.is_nan() {
-            todo!("Handle not a number (for example divide zero by itself)")
+            return Some(String::from(NOT_A_NUMBER));
         }
+        if self.is_infinite() {
+            let sign = if self.is_sign_negative() { "-" } else { "" };
+            return Some(format!("{sign}{INFINITY}"));
+        }
+
+        let magnitude = self.abs();
+        let scientific =
+            magnitude >= SCIENTIFIC_ABOVE || (magnitude != 0.0 && magnitude < SCIENTIFIC_BELOW);
+        let formatted = match (scientific, format.precision) {
+            (false, None) => format!("{self}"),
+            (false, Some(precision)) => format!("{self:.precision$}"),
+            (true, None) => format!("{self:e}"),
+            (true, Some(precision)) => format!("{self:.precision$e}"),
+        };
 
-        let num = format!("{self}")
-            .chars()
-            .map(|c| c.ectoac().unwrap())
-            .collect::<String>();
+        let formatted = format.digits.localize(&formatted);
+        Some(formatted.replacen('e', EXPONENT_MARKER, 1))
+    }
+}
 
-        Some(num)
+impl ArabicNumber for i64 {
+    fn to_arabic_decimal_with(&self, format: NumberFormat) -> Option<String> {
+        let digits = format.digits.localize(&self.to_string());
+        match format.precision {
+            Some(precision) if precision > 0 => Some(format!(
+                "{}{}{}",
+                digits,
+                format.digits.decimal_separator(),
+                format.digits.digit(0).to_string().repeat(precision)
+            )),
+            _ => Some(digits),
+        }
     }
 }
 
+/// Replaces the digits of an already formatted integer, decimal or fraction with arabic
+/// digits, e.g. `-12/7` becomes `-١٢/٧` and `0.5` becomes `٠٫٥`.
+pub fn to_arabic_digits(num: &str) -> Option<String> {
+    num.chars()
+        .map(|c| match c {
+            '-' | '/' => Some(c),
+            _ => c.ectoac(),
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
+    use super::to_arabic_digits;
     use super::ArabicNumber;
+    use super::NumberFormat;
+    use crate::arabic_digits::DigitSet;
 
     #[test]
     fn it_should_transform_arabic_number_to_english_number() {
@@ -40,4 +110,81 @@ mod tests {
         let s = 3.3;
         assert_eq!(s.to_arabic_decimal().unwrap(), "٣٫٣");
     }
+
+    #[test]
+    fn it_should_transform_integer_to_arabic_number() {
+        let s: i64 = 3;
+        assert_eq!(s.to_arabic_decimal().unwrap(), "٣");
+
+        let s: i64 = 120;
+        assert_eq!(s.to_arabic_decimal().unwrap(), "١٢٠");
+
+        let s: i64 = -45;
+        assert_eq!(s.to_arabic_decimal().unwrap(), "-٤٥");
+    }
+
+    #[test]
+    fn it_should_transform_formatted_digits_to_arabic_digits() {
+        assert_eq!(
+            to_arabic_digits("123456789012345678901234567890").unwrap(),
+            "١٢٣٤٥٦٧٨٩٠١٢٣٤٥٦٧٨٩٠١٢٣٤٥٦٧٨٩٠"
+        );
+        assert_eq!(to_arabic_digits("-1/3").unwrap(), "-١/٣");
+    }
+
+    #[test]
+    fn it_should_transform_special_floats() {
+        assert_eq!(f64::NAN.to_arabic_decimal().unwrap(), "ليس_رقما");
+        assert_eq!(f64::INFINITY.to_arabic_decimal().unwrap(), "لانهاية");
+        assert_eq!(f64::NEG_INFINITY.to_arabic_decimal().unwrap(), "-لانهاية");
+    }
+
+    #[test]
+    fn it_should_transform_negative_floats() {
+        assert_eq!((-5.0).to_arabic_decimal().unwrap(), "-٥");
+        assert_eq!((-0.25).to_arabic_decimal().unwrap(), "-٠٫٢٥");
+    }
+
+    #[test]
+    fn it_should_use_scientific_notation_for_extreme_magnitudes() {
+        assert_eq!(1e21.to_arabic_decimal().unwrap(), "١أس٢١");
+        assert_eq!((-1.5e300).to_arabic_decimal().unwrap(), "-١٫٥أس٣٠٠");
+        assert_eq!(2.5e-7.to_arabic_decimal().unwrap(), "٢٫٥أس-٧");
+        assert_eq!(1e20.to_arabic_decimal().unwrap(), "١٠٠٠٠٠٠٠٠٠٠٠٠٠٠٠٠٠٠٠٠");
+        assert_eq!(0.000001.to_arabic_decimal().unwrap(), "٠٫٠٠٠٠٠١");
+    }
+
+    #[test]
+    fn it_should_round_to_precision() {
+        let format = NumberFormat::with_precision(2);
+        assert_eq!(1.23456.to_arabic_decimal_with(format).unwrap(), "١٫٢٣");
+        assert_eq!((-2.0).to_arabic_decimal_with(format).unwrap(), "-٢٫٠٠");
+        assert_eq!(
+            1.23456e25.to_arabic_decimal_with(format).unwrap(),
+            "١٫٢٣أس٢٥"
+        );
+        assert_eq!(7_i64.to_arabic_decimal_with(format).unwrap(), "٧٫٠٠");
+        assert_eq!(
+            7_i64
+                .to_arabic_decimal_with(NumberFormat::with_precision(0))
+                .unwrap(),
+            "٧"
+        );
+    }
+
+    #[test]
+    fn it_should_write_numbers_in_any_digit_set() {
+        let persian = NumberFormat::default().with_digits(DigitSet::EasternArabicIndic);
+        assert_eq!(12.5.to_arabic_decimal_with(persian).unwrap(), "۱۲٫۵");
+        assert_eq!((-3_i64).to_arabic_decimal_with(persian).unwrap(), "-۳");
+
+        let western = NumberFormat::with_precision(1).with_digits(DigitSet::Western);
+        assert_eq!(12.25.to_arabic_decimal_with(western).unwrap(), "12.2");
+        assert_eq!(7_i64.to_arabic_decimal_with(western).unwrap(), "7.0");
+        assert_eq!(
+            1e21.to_arabic_decimal_with(NumberFormat::default().with_digits(DigitSet::Western))
+                .unwrap(),
+            "1أس21"
+        );
+    }
 }
@@ -1,80 +1,128 @@
 use crate::arabic_char::ArabicChar;
 use crate::arabic_consts;
+use crate::arabic_digits::digit_value;
+use crate::arabic_digits::DigitSet;
 
 pub trait ArabicStr {
-    fn parse_arabic_decimal(&self) -> Option<String>;
-    fn is_arabic_number(&self) -> bool;
+    /// Whether this is a number written with any of `digits`, with at most one decimal
+    /// separator and `٬` thousands separators before it, e.g. `١٬٢٣٤٫٥`.
+    fn is_number_in(&self, digits: &[DigitSet]) -> bool;
+    /// Rewrites a number accepted by `is_number_in` the way `str::parse` expects it.
+    fn parse_decimal_in(&self, digits: &[DigitSet]) -> Option<String>;
+
+    fn is_arabic_number(&self) -> bool {
+        self.is_number_in(&DigitSet::ALL)
+    }
+
+    fn parse_arabic_decimal(&self) -> Option<String> {
+        self.parse_decimal_in(&DigitSet::ALL)
+    }
+
+    /// Drops tashkeel and tatweel, which don't change what word is written.
+    fn strip_tashkeel(&self) -> String;
+    /// Strips tashkeel and folds spelling variants so that, e.g., `أَساس` and `اساس`
+    /// compare equal.
+    fn normalize_arabic(&self) -> String;
 }
 
 impl ArabicStr for str {
-    fn is_arabic_number(&self) -> bool {
-        if self.starts_with(arabic_consts::DECIMAL_SEPARATOR)
-            || self.ends_with(arabic_consts::DECIMAL_SEPARATOR)
-            || self.is_empty()
-        {
-            return false;
-        }
+    fn is_number_in(&self, digits: &[DigitSet]) -> bool {
+        let chars: Vec<char> = self.chars().collect();
+        let is_digit = |i: usize| {
+            chars
+                .get(i)
+                .is_some_and(|&c| digit_value(c, digits).is_some())
+        };
 
         let mut sep_encountered = false;
-        let mut res = true;
-        self.chars().for_each(|c| {
-            if !c.is_arabic_digit() && c != arabic_consts::DECIMAL_SEPARATOR {
-                res = false;
-            } else if c == arabic_consts::DECIMAL_SEPARATOR {
-                if sep_encountered {
-                    res = false;
+        for (i, &c) in chars.iter().enumerate() {
+            if is_digit(i) {
+                continue;
+            }
+            let between_digits = i > 0 && is_digit(i - 1) && is_digit(i + 1);
+            match c {
+                arabic_consts::DECIMAL_SEPARATOR | '.' if between_digits && !sep_encountered => {
+                    sep_encountered = true
                 }
-                sep_encountered = true
+                arabic_consts::THOUSANDS_SEPARATOR if between_digits && !sep_encountered => {}
+                _ => return false,
             }
-        });
+        }
 
-        res
+        !chars.is_empty()
     }
 
-    fn parse_arabic_decimal(&self) -> Option<String> {
-        if !self.is_arabic_number() {
+    fn parse_decimal_in(&self, digits: &[DigitSet]) -> Option<String> {
+        if !self.is_number_in(digits) {
             return None;
         }
         let s = self
             .chars()
-            .map(|c| c.actoec().unwrap())
+            .filter(|&c| c != arabic_consts::THOUSANDS_SEPARATOR)
+            .map(|c| match digit_value(c, digits) {
+                Some(value) => DigitSet::Western.digit(value),
+                None => '.',
+            })
             .collect::<String>();
         Some(s)
     }
+
+    fn strip_tashkeel(&self) -> String {
+        self.chars()
+            .filter(|c| !c.is_arabic_diacritic() && !c.is_tatweel())
+            .collect()
+    }
+
+    fn normalize_arabic(&self) -> String {
+        self.strip_tashkeel()
+            .chars()
+            .map(ArabicChar::normalize_letter)
+            .collect()
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::arabic_digits::DigitSet;
     use crate::arabic_str::ArabicStr;
 
     #[test]
     fn it_should_not_be_arabic_number() {
         let s: &str = "";
-        assert_eq!(s.is_arabic_number(), false);
+        assert!(!s.is_arabic_number());
 
         let s = "٫٣";
-        assert_eq!(s.is_arabic_number(), false);
+        assert!(!s.is_arabic_number());
 
         let s = "٣٫";
-        assert_eq!(s.is_arabic_number(), false);
+        assert!(!s.is_arabic_number());
 
         let s = "٣٫٣٫";
-        assert_eq!(s.is_arabic_number(), false);
+        assert!(!s.is_arabic_number());
 
         let s = "٣٫٫٣";
-        assert_eq!(s.is_arabic_number(), false);
+        assert!(!s.is_arabic_number());
+
+        let s = "٬٣";
+        assert!(!s.is_arabic_number());
+
+        let s = "٣٬٬٣";
+        assert!(!s.is_arabic_number());
+
+        let s = "٣٫٣٬٣";
+        assert!(!s.is_arabic_number());
     }
 
     #[test]
     fn it_should_be_arabic_number() {
         let s = "٣";
-        assert_eq!(s.is_arabic_number(), true);
+        assert!(s.is_arabic_number());
 
         let s = "٣٣";
-        assert_eq!(s.is_arabic_number(), true);
+        assert!(s.is_arabic_number());
 
         let s = "٣٫٣";
-        assert_eq!(s.is_arabic_number(), true);
+        assert!(s.is_arabic_number());
     }
 
     #[test]
@@ -88,4 +136,37 @@ mod tests {
         let s = "٣٫٣";
         assert_eq!(s.parse_arabic_decimal().unwrap(), "3.3");
     }
+
+    #[test]
+    fn it_should_parse_every_digit_set() {
+        assert_eq!("۱۲٫۵".parse_arabic_decimal().unwrap(), "12.5");
+        assert_eq!("12.5".parse_arabic_decimal().unwrap(), "12.5");
+        assert_eq!("١٬٠٠٠٬٠٠٠".parse_arabic_decimal().unwrap(), "1000000");
+        assert_eq!("۱۲".parse_decimal_in(&[DigitSet::ArabicIndic]), None);
+        assert_eq!(
+            "۱۲"
+                .parse_decimal_in(&[DigitSet::EasternArabicIndic])
+                .unwrap(),
+            "12"
+        );
+    }
+
+    #[test]
+    fn it_should_normalize_spelling_variants() {
+        assert_eq!("أساس".normalize_arabic(), "اساس");
+        assert_eq!("إذا_كان".normalize_arabic(), "اذا_كان");
+        assert_eq!("مدرسة".normalize_arabic(), "مدرسه");
+        assert_eq!("مستشفى".normalize_arabic(), "مستشفي");
+        assert_eq!("مسؤول".normalize_arabic(), "مسوول");
+        assert_eq!("x_1".normalize_arabic(), "x_1");
+    }
+
+    #[test]
+    fn it_should_strip_tashkeel_and_tatweel() {
+        assert_eq!("مُحَمَّدٌ".strip_tashkeel(), "محمد");
+        assert_eq!("مـحـمـد".strip_tashkeel(), "محمد");
+        assert_eq!("أَسَاس".strip_tashkeel(), "أساس");
+        assert_eq!("أَسَاس".normalize_arabic(), "اساس");
+        assert_eq!("کتابی".normalize_arabic(), "كتابي");
+    }
 }
@@ -0,0 +1,450 @@
+/*
+Spelling numbers out in words (تفقيط), as amounts are written on invoices and cheques. Numbers are
+in the nominative, and agree with what they count:
+
+- one and two agree in gender with the counted noun, and the noun comes in the singular or dual;
+- three to ten take the opposite gender, and the noun comes in the plural (ثلاثة ريالات);
+- eleven to ninety-nine are followed by the singular in the accusative (أحد عشر ريالاً);
+- round hundreds, thousands and so on are followed by the singular (مائتا ريال).
+
+The last two digits of a number decide the form of the noun, so `١٠٣` is مائة وثلاثة ريالات.
+ */
+
+/// Numbers from here on are past the trillions and aren't written in words.
+const LIMIT: u64 = 1_000_000_000_000_000;
+/// How many digits after the decimal point `decimal_to_words` reads.
+const FRACTION_DIGITS: usize = 9;
+
+const ZERO: &str = "صفر";
+const MINUS: &str = "سالب";
+const POINT: &str = "فاصلة";
+const AND: &str = " و";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gender {
+    Masculine,
+    Feminine,
+}
+
+/// A noun in the forms a number chooses between.
+#[derive(Debug, Clone, Copy)]
+pub struct CountedNoun {
+    /// After one, and after round numbers: `ريال`.
+    pub singular: &'static str,
+    pub dual: &'static str,
+    /// After three to ten: `ريالات`.
+    pub plural: &'static str,
+    /// After eleven to ninety-nine: `ريالاً`.
+    pub accusative: &'static str,
+    pub gender: Gender,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Currency {
+    pub unit: CountedNoun,
+    pub subunit: CountedNoun,
+    /// How many subunits make a unit.
+    pub subunits: u64,
+}
+
+pub const RIYAL: Currency = Currency {
+    unit: CountedNoun {
+        singular: "ريال",
+        dual: "ريالان",
+        plural: "ريالات",
+        accusative: "ريالاً",
+        gender: Gender::Masculine,
+    },
+    subunit: CountedNoun {
+        singular: "هللة",
+        dual: "هللتان",
+        plural: "هللات",
+        accusative: "هللةً",
+        gender: Gender::Feminine,
+    },
+    subunits: 100,
+};
+
+pub const DINAR: Currency = Currency {
+    unit: CountedNoun {
+        singular: "دينار",
+        dual: "ديناران",
+        plural: "دنانير",
+        accusative: "ديناراً",
+        gender: Gender::Masculine,
+    },
+    subunit: CountedNoun {
+        singular: "فلس",
+        dual: "فلسان",
+        plural: "فلوس",
+        accusative: "فلساً",
+        gender: Gender::Masculine,
+    },
+    subunits: 1000,
+};
+
+const SCALES: [CountedNoun; 4] = [
+    CountedNoun {
+        singular: "تريليون",
+        dual: "تريليونان",
+        plural: "تريليونات",
+        accusative: "تريليوناً",
+        gender: Gender::Masculine,
+    },
+    CountedNoun {
+        singular: "مليار",
+        dual: "ملياران",
+        plural: "مليارات",
+        accusative: "ملياراً",
+        gender: Gender::Masculine,
+    },
+    CountedNoun {
+        singular: "مليون",
+        dual: "مليونان",
+        plural: "ملايين",
+        accusative: "مليوناً",
+        gender: Gender::Masculine,
+    },
+    CountedNoun {
+        singular: "ألف",
+        dual: "ألفان",
+        plural: "آلاف",
+        accusative: "ألفاً",
+        gender: Gender::Masculine,
+    },
+];
+
+// indexed by the number, for masculine and feminine counted nouns
+const ONES_MASCULINE: [&str; 11] = [
+    "",
+    "واحد",
+    "اثنان",
+    "ثلاثة",
+    "أربعة",
+    "خمسة",
+    "ستة",
+    "سبعة",
+    "ثمانية",
+    "تسعة",
+    "عشرة",
+];
+const ONES_FEMININE: [&str; 11] = [
+    "",
+    "واحدة",
+    "اثنتان",
+    "ثلاث",
+    "أربع",
+    "خمس",
+    "ست",
+    "سبع",
+    "ثماني",
+    "تسع",
+    "عشر",
+];
+const TENS: [&str; 10] = [
+    "",
+    "عشرة",
+    "عشرون",
+    "ثلاثون",
+    "أربعون",
+    "خمسون",
+    "ستون",
+    "سبعون",
+    "ثمانون",
+    "تسعون",
+];
+const HUNDREDS: [&str; 10] = [
+    "",
+    "مائة",
+    "مائتان",
+    "ثلاثمائة",
+    "أربعمائة",
+    "خمسمائة",
+    "ستمائة",
+    "سبعمائة",
+    "ثمانمائة",
+    "تسعمائة",
+];
+
+fn below_hundred(n: u64, gender: Gender) -> String {
+    let ones = match gender {
+        Gender::Masculine => &ONES_MASCULINE,
+        Gender::Feminine => &ONES_FEMININE,
+    };
+    let (tens, unit) = ((n / 10) as usize, (n % 10) as usize);
+    match (n, gender) {
+        (0..=10, _) => ones[n as usize].to_string(),
+        (11, Gender::Masculine) => String::from("أحد عشر"),
+        (11, Gender::Feminine) => String::from("إحدى عشرة"),
+        (12, Gender::Masculine) => String::from("اثنا عشر"),
+        (12, Gender::Feminine) => String::from("اثنتا عشرة"),
+        (13..=19, Gender::Masculine) => format!("{} عشر", ones[unit]),
+        (13..=19, Gender::Feminine) => format!("{} عشرة", ones[unit]),
+        _ if unit == 0 => TENS[tens].to_string(),
+        (_, Gender::Feminine) if unit == 1 => format!("إحدى{}{}", AND, TENS[tens]),
+        _ => format!("{}{}{}", ones[unit], AND, TENS[tens]),
+    }
+}
+
+fn below_thousand(n: u64, gender: Gender) -> String {
+    let (hundreds, rest) = (n / 100, n % 100);
+    match (hundreds, rest) {
+        (0, _) => below_hundred(rest, gender),
+        (_, 0) => HUNDREDS[hundreds as usize].to_string(),
+        _ => format!(
+            "{}{}{}",
+            HUNDREDS[hundreds as usize],
+            AND,
+            below_hundred(rest, gender)
+        ),
+    }
+}
+
+/// `words` for `n` followed by `noun`, which `n`'s last two digits put in its form.
+fn with_noun(n: u64, words: String, noun: &CountedNoun) -> String {
+    match n % 100 {
+        3..=10 => format!("{} {}", words, noun.plural),
+        11..=99 => format!("{} {}", words, noun.accusative),
+        _ => {
+            // مائتان loses its nun before the noun it counts
+            let words = match words.strip_suffix("مائتان") {
+                Some(rest) if n.is_multiple_of(100) => format!("{}مائتا", rest),
+                _ => words,
+            };
+            format!("{} {}", words, noun.singular)
+        }
+    }
+}
+
+fn integer_words(n: u64, gender: Gender) -> String {
+    if n == 0 {
+        return String::from(ZERO);
+    }
+
+    let mut parts = Vec::new();
+    let mut scale = LIMIT / 1000;
+    for noun in &SCALES {
+        let group = n / scale % 1000;
+        match group {
+            0 => {}
+            1 => parts.push(noun.singular.to_string()),
+            2 => parts.push(noun.dual.to_string()),
+            _ => parts.push(with_noun(group, below_thousand(group, noun.gender), noun)),
+        }
+        scale /= 1000;
+    }
+    if !n.is_multiple_of(1000) {
+        parts.push(below_thousand(n % 1000, gender));
+    }
+    parts.join(AND)
+}
+
+/// `n` in words, counting something of the given gender, e.g. `ثلاث` for three
+/// feminine things. `None` from a quadrillion on.
+pub fn integer_to_words(n: i64, gender: Gender) -> Option<String> {
+    let magnitude = n.unsigned_abs();
+    if magnitude >= LIMIT {
+        return None;
+    }
+    let words = integer_words(magnitude, gender);
+    Some(if n < 0 {
+        format!("{} {}", MINUS, words)
+    } else {
+        words
+    })
+}
+
+/// `num` in words, reading the digits after the decimal point as a number of their own, so
+/// `٣٫٠٥` is ثلاثة فاصلة صفر خمسة. The fraction is rounded to nine digits, so `0.1 + 0.2` is
+/// صفر فاصلة ثلاثة. `None` for NaN, infinities and from a quadrillion on.
+pub fn decimal_to_words(num: f64) -> Option<String> {
+    if !num.is_finite() {
+        return None;
+    }
+    let formatted = format!("{:.*}", FRACTION_DIGITS, num.abs());
+    let formatted = formatted.trim_end_matches('0').trim_end_matches('.');
+    let (whole, fraction) = formatted.split_once('.').unwrap_or((formatted, ""));
+    let whole: u64 = whole.parse().ok().filter(|&whole| whole < LIMIT)?;
+
+    let mut words = integer_words(whole, Gender::Masculine);
+    if !fraction.is_empty() {
+        let significant = fraction.trim_start_matches('0');
+        let zeros = vec![ZERO; fraction.len() - significant.len()];
+        let significant: u64 = significant.parse().ok()?;
+        words = format!(
+            "{} {} {}",
+            words,
+            POINT,
+            zeros
+                .into_iter()
+                .map(String::from)
+                .chain(std::iter::once(integer_words(
+                    significant,
+                    Gender::Masculine
+                )))
+                .collect::<Vec<_>>()
+                .join(" ")
+        );
+    }
+
+    Some(if num < 0.0 && formatted != "0" {
+        format!("{} {}", MINUS, words)
+    } else {
+        words
+    })
+}
+
+/// `n` of `noun` in words: `ريال واحد`, `ريالان`, `ثلاثة ريالات`, `أحد عشر ريالاً`.
+pub fn count_to_words(n: u64, noun: &CountedNoun) -> Option<String> {
+    if n >= LIMIT {
+        return None;
+    }
+    Some(match n {
+        1 => {
+            let one = match noun.gender {
+                Gender::Masculine => ONES_MASCULINE[1],
+                Gender::Feminine => ONES_FEMININE[1],
+            };
+            format!("{} {}", noun.singular, one)
+        }
+        2 => noun.dual.to_string(),
+        _ => with_noun(n, integer_words(n, noun.gender), noun),
+    })
+}
+
+/// An amount of money in words, rounded to the currency's subunits, e.g. `١٢٥٠٫٧٥` riyals is
+/// ألف ومائتان وخمسون ريالاً وخمس وسبعون هللةً.
+pub fn amount_to_words(amount: f64, currency: &Currency) -> Option<String> {
+    let total = (amount.abs() * currency.subunits as f64).round();
+    if !total.is_finite() || total >= (LIMIT * currency.subunits) as f64 {
+        return None;
+    }
+    let total = total as u64;
+    let (units, subunits) = (total / currency.subunits, total % currency.subunits);
+
+    let mut parts = Vec::new();
+    if units != 0 || subunits == 0 {
+        parts.push(count_to_words(units, &currency.unit)?);
+    }
+    if subunits != 0 {
+        parts.push(count_to_words(subunits, &currency.subunit)?);
+    }
+    let words = parts.join(AND);
+
+    Some(if amount < 0.0 && total != 0 {
+        format!("{} {}", MINUS, words)
+    } else {
+        words
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::amount_to_words;
+    use super::count_to_words;
+    use super::decimal_to_words;
+    use super::integer_to_words;
+    use super::Gender;
+    use super::DINAR;
+    use super::RIYAL;
+
+    fn words(n: i64) -> String {
+        integer_to_words(n, Gender::Masculine).unwrap()
+    }
+
+    #[test]
+    fn it_should_spell_small_numbers() {
+        assert_eq!(words(0), "صفر");
+        assert_eq!(words(1), "واحد");
+        assert_eq!(words(8), "ثمانية");
+        assert_eq!(words(11), "أحد عشر");
+        assert_eq!(words(12), "اثنا عشر");
+        assert_eq!(words(15), "خمسة عشر");
+        assert_eq!(words(20), "عشرون");
+        assert_eq!(words(21), "واحد وعشرون");
+        assert_eq!(words(99), "تسعة وتسعون");
+        assert_eq!(words(-7), "سالب سبعة");
+    }
+
+    #[test]
+    fn it_should_agree_with_feminine_nouns() {
+        let words = |n| integer_to_words(n, Gender::Feminine).unwrap();
+        assert_eq!(words(1), "واحدة");
+        assert_eq!(words(3), "ثلاث");
+        assert_eq!(words(8), "ثماني");
+        assert_eq!(words(11), "إحدى عشرة");
+        assert_eq!(words(12), "اثنتا عشرة");
+        assert_eq!(words(18), "ثماني عشرة");
+        assert_eq!(words(21), "إحدى وعشرون");
+    }
+
+    #[test]
+    fn it_should_spell_hundreds_and_scales() {
+        assert_eq!(words(100), "مائة");
+        assert_eq!(words(200), "مائتان");
+        assert_eq!(words(305), "ثلاثمائة وخمسة");
+        assert_eq!(words(1000), "ألف");
+        assert_eq!(words(2000), "ألفان");
+        assert_eq!(words(3000), "ثلاثة آلاف");
+        assert_eq!(words(11000), "أحد عشر ألفاً");
+        assert_eq!(words(200_000), "مائتا ألف");
+        assert_eq!(words(1250), "ألف ومائتان وخمسون");
+        assert_eq!(words(2_500_000), "مليونان وخمسمائة ألف");
+        assert_eq!(
+            words(999_999_999_999_999),
+            "تسعمائة وتسعة وتسعون تريليوناً وتسعمائة وتسعة وتسعون ملياراً \
+             وتسعمائة وتسعة وتسعون مليوناً وتسعمائة وتسعة وتسعون ألفاً وتسعمائة وتسعة وتسعون"
+        );
+        assert_eq!(
+            integer_to_words(1_000_000_000_000_000, Gender::Masculine),
+            None
+        );
+    }
+
+    #[test]
+    fn it_should_spell_decimals() {
+        assert_eq!(decimal_to_words(3.5).unwrap(), "ثلاثة فاصلة خمسة");
+        assert_eq!(decimal_to_words(3.05).unwrap(), "ثلاثة فاصلة صفر خمسة");
+        assert_eq!(
+            decimal_to_words(-0.25).unwrap(),
+            "سالب صفر فاصلة خمسة وعشرون"
+        );
+        assert_eq!(decimal_to_words(0.1 + 0.2).unwrap(), "صفر فاصلة ثلاثة");
+        assert_eq!(decimal_to_words(-0.0000000001).unwrap(), "صفر");
+        assert_eq!(decimal_to_words(f64::NAN), None);
+        assert_eq!(decimal_to_words(1e15), None);
+    }
+
+    #[test]
+    fn it_should_count_nouns() {
+        assert_eq!(count_to_words(1, &RIYAL.unit).unwrap(), "ريال واحد");
+        assert_eq!(count_to_words(2, &RIYAL.unit).unwrap(), "ريالان");
+        assert_eq!(count_to_words(3, &RIYAL.unit).unwrap(), "ثلاثة ريالات");
+        assert_eq!(count_to_words(3, &RIYAL.subunit).unwrap(), "ثلاث هللات");
+        assert_eq!(count_to_words(15, &RIYAL.unit).unwrap(), "خمسة عشر ريالاً");
+        assert_eq!(count_to_words(100, &RIYAL.unit).unwrap(), "مائة ريال");
+        assert_eq!(count_to_words(200, &RIYAL.unit).unwrap(), "مائتا ريال");
+        assert_eq!(
+            count_to_words(103, &RIYAL.unit).unwrap(),
+            "مائة وثلاثة ريالات"
+        );
+        assert_eq!(count_to_words(1000, &DINAR.unit).unwrap(), "ألف دينار");
+        assert_eq!(count_to_words(5, &DINAR.unit).unwrap(), "خمسة دنانير");
+    }
+
+    #[test]
+    fn it_should_spell_amounts() {
+        assert_eq!(
+            amount_to_words(1250.75, &RIYAL).unwrap(),
+            "ألف ومائتان وخمسون ريالاً وخمس وسبعون هللةً"
+        );
+        assert_eq!(amount_to_words(0.5, &RIYAL).unwrap(), "خمسون هللةً");
+        assert_eq!(amount_to_words(0.0, &RIYAL).unwrap(), "صفر ريال");
+        assert_eq!(
+            amount_to_words(3.250, &DINAR).unwrap(),
+            "ثلاثة دنانير ومائتان وخمسون فلساً"
+        );
+        assert_eq!(amount_to_words(2.001, &DINAR).unwrap(), "ديناران وفلس واحد");
+        assert_eq!(amount_to_words(f64::INFINITY, &RIYAL), None);
+    }
+}
@@ -1,4 +1,8 @@
+pub mod arabic_bidi;
 pub mod arabic_char;
+pub mod arabic_collation;
 pub mod arabic_consts;
+pub mod arabic_digits;
 pub mod arabic_number;
 pub mod arabic_str;
+pub mod arabic_words;
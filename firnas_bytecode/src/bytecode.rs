@@ -31,6 +31,8 @@ pub enum Op {
     Subtract,
     Multiply,
     Divide,
+    Union,
+    Intersection,
     Not,
     Equal,
     Greater,
@@ -57,8 +59,14 @@ pub enum Op {
     GetSuper(usize),
     SuperInvoke(/*method_name*/ String, /*arg count*/ u8),
     BuildList(usize),
+    BuildTuple(usize),
+    BuildSet(usize),
+    BuildDict(/*entry count*/ usize),
+    Unpack(usize),
     Subscr,
     SetItem,
+    Slice,
+    SetSlice,
 }
 
 #[derive(Default, Clone, Debug)]
@@ -77,6 +85,9 @@ pub struct Closure {
 #[derive(Debug, Clone)]
 pub enum Constant {
     Number(f64),
+    Integer(i64),
+    BigInteger(String),
+    Rational(String),
     String(String),
     Function(Closure),
 }
@@ -85,6 +96,9 @@ impl fmt::Display for Constant {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Constant::Number(n) => write!(f, "{}", n),
+            Constant::Integer(n) => write!(f, "{}", n),
+            Constant::BigInteger(n) => write!(f, "{}", n),
+            Constant::Rational(n) => write!(f, "{}r", n),
             Constant::String(s) => write!(f, "\"{}\"", s),
             Constant::Function(Closure {
                 function:
@@ -114,6 +128,14 @@ impl Chunk {
         }
     }
 
+    pub fn add_constant_integer(&mut self, c: i64) -> usize {
+        if let Some(id) = self.find_integer(c) {
+            id
+        } else {
+            self.add_constant(Constant::Integer(c))
+        }
+    }
+
     pub fn add_constant_string(&mut self, s: String) -> usize {
         if let Some(id) = self.find_string(&s) {
             id
@@ -147,6 +169,16 @@ impl Chunk {
             }
         })
     }
+
+    fn find_integer(&self, num: i64) -> Option<usize> {
+        self.constants.iter().position(|c| {
+            if let Constant::Integer(num2) = c {
+                num == *num2
+            } else {
+                false
+            }
+        })
+    }
 }
 
 pub fn disassemble_code(chunk: &Chunk) -> Vec<String> {
@@ -167,6 +199,8 @@ pub fn disassemble_code(chunk: &Chunk) -> Vec<String> {
             Op::Subtract => "OP_SUBTRACT".to_string(),
             Op::Multiply => "OP_MULTIPLY".to_string(),
             Op::Divide => "OP_DIVIDE".to_string(),
+            Op::Union => "OP_UNION".to_string(),
+            Op::Intersection => "OP_INTERSECTION".to_string(),
             Op::Not => "OP_NOT".to_string(),
             Op::Equal => "OP_NOT".to_string(),
             Op::Greater => "OP_GREATER".to_string(),
@@ -207,8 +241,14 @@ pub fn disassemble_code(chunk: &Chunk) -> Vec<String> {
                 format!("OP_SUPER_INOKE {} nargs={}", method_name, arg_count)
             }
             Op::BuildList(size) => format!("OP_BUILD_LIST {}", size),
+            Op::BuildTuple(size) => format!("OP_BUILD_TUPLE {}", size),
+            Op::BuildSet(size) => format!("OP_BUILD_SET {}", size),
+            Op::BuildDict(size) => format!("OP_BUILD_DICT {}", size),
+            Op::Unpack(size) => format!("OP_UNPACK {}", size),
             Op::Subscr => "OP_SUBSCR".to_string(),
             Op::SetItem => "OP_SETITEM".to_string(),
+            Op::Slice => "OP_SLICE".to_string(),
+            Op::SetSlice => "OP_SETSLICE".to_string(),
         };
 
         lines.push(format!(
@@ -1,36 +1,230 @@
+use arabic_utils::arabic_bidi::caret_excerpt;
+use clap::Args;
 use clap::Subcommand;
 use clap::ValueEnum;
 use firnas_compiler::compiler;
+use firnas_compiler::diagnostic;
+use firnas_compiler::diagnostic::Language;
+#[cfg(feature = "ar")]
+use firnas_tokenizer::tokenizer;
+#[cfg(feature = "ar")]
+use firnas_tokenizer::tokenizer::IdentifierSpelling;
+#[cfg(feature = "ar")]
+use firnas_tokenizer::tokenizer::KeywordProfile;
+use firnas_vm::stdlib::file::FileAccess;
+use firnas_vm::stdlib::io::DefaultStdIO;
+use firnas_vm::stdlib::io::OutputMode;
 use firnas_vm::virtual_machine;
 use std::fs;
+use std::path::PathBuf;
 
 #[derive(Subcommand)]
 pub enum Firnas {
     /// Compile a file
-    Compile {
-        /// Path to file
-        path: String,
-        /// Extend the language with Work-in-Progress features
-        #[clap(short = 'X', value_parser, num_args = 1.., value_delimiter = ' ')]
-        extentions: Vec<Extension>,
-    },
+    Compile(CompileArgs),
+    /// Print a file with the keywords of a profile spelled as the standard keywords, the form
+    /// other tools read
+    Canonicalize(CanonicalizeArgs),
+}
+
+#[derive(Args)]
+pub struct CompileArgs {
+    /// Path to file
+    path: String,
+    /// Extend the language with Work-in-Progress features
+    #[clap(short = 'X', value_parser, num_args = 1.., value_delimiter = ' ')]
+    extentions: Vec<Extension>,
+    /// Keep english words and numbers in place when printing Arabic text to terminals
+    /// that lay out right-to-left text
+    #[clap(long)]
+    bidi: bool,
+    /// The language errors are reported in, by default the language of the dialect
+    #[clap(long, value_enum)]
+    lang: Option<Lang>,
+    /// Also accept the keywords of a profile: a built-in one (تعليمي) or a file of
+    /// 'synonym = keyword' lines
+    #[clap(long)]
+    keywords: Option<String>,
+    /// How names spelled with different letter variants are read: as one name, or as
+    /// different names with a warning
+    #[clap(long, value_enum)]
+    spelling: Option<Spelling>,
+    /// Make every file function fail
+    #[clap(long, conflicts_with = "files_within")]
+    no_files: bool,
+    /// Only let the file functions use relative paths inside this directory
+    #[clap(long, value_name = "DIR")]
+    files_within: Option<PathBuf>,
+}
+
+#[derive(Args)]
+pub struct CanonicalizeArgs {
+    /// Path to file
+    path: String,
+    /// The profile the file is written with: a built-in one (تعليمي) or a file of
+    /// 'synonym = keyword' lines
+    #[clap(long)]
+    keywords: String,
 }
 
 impl Firnas {
-    pub fn handle_file(path: String, extentions: &[Extension]) -> anyhow::Result<()> {
-        let content = fs::read_to_string(path)?;
+    #[cfg(feature = "ar")]
+    pub fn handle_canonicalize(args: CanonicalizeArgs) -> anyhow::Result<()> {
+        let content = fs::read_to_string(&args.path)?;
+        let options = tokenizer::ScanOptions {
+            keywords: load_keyword_profile(&args.keywords)?,
+            ..Default::default()
+        };
+        let canonical = tokenizer::canonicalize(content.clone(), options).map_err(|err| {
+            let message =
+                diagnostic::LEXICAL_ERROR.render(Language::default(), &[&err.line, &err.what]);
+            anyhow::anyhow!(excerpt(&content, message, err.line, err.col))
+        })?;
+        print!("{}", canonical);
+        Ok(())
+    }
+
+    #[cfg(not(feature = "ar"))]
+    pub fn handle_canonicalize(_args: CanonicalizeArgs) -> anyhow::Result<()> {
+        anyhow::bail!("Keyword profiles are only for the Arabic dialect")
+    }
+
+    pub fn handle_file(args: CompileArgs) -> anyhow::Result<()> {
+        let content = fs::read_to_string(&args.path)?;
         let extensions = firnas_ext::Extensions {
-            lists: extentions.contains(&Extension::Lists),
-            lambdas: extentions.contains(&Extension::Lambdas),
+            lists: args.extentions.contains(&Extension::Lists),
+            lambdas: args.extentions.contains(&Extension::Lambdas),
         };
 
-        let func_or_err = compiler::Compiler::compile(content, extensions);
+        let language = args.lang.map_or_else(Language::default, Language::from);
 
-        let _ = func_or_err.map(|f| {
-            let mut interpreter = virtual_machine::VirtualMachine::default();
-            interpreter.interpret(f).unwrap();
-        });
-        Ok(())
+        let compiled = match (args.keywords, args.spelling) {
+            (None, None) => {
+                compiler::Compiler::compile_with_language(content.clone(), extensions, language)
+            }
+            #[cfg(feature = "ar")]
+            (keywords, spelling) => {
+                let options = tokenizer::ScanOptions {
+                    keywords: match keywords {
+                        Some(profile) => load_keyword_profile(&profile)?,
+                        None => KeywordProfile::default(),
+                    },
+                    spelling: spelling.map_or_else(Default::default, IdentifierSpelling::from),
+                    ..Default::default()
+                };
+                compiler::Compiler::compile_with_options(
+                    content.clone(),
+                    extensions,
+                    language,
+                    options,
+                )
+                .map(|(f, warnings)| {
+                    for warning in warnings {
+                        let message =
+                            diagnostic::WARNING.render(language, &[&warning.line, &warning.what]);
+                        eprintln!("{}", excerpt(&content, message, warning.line, warning.col));
+                    }
+                    f
+                })
+            }
+            #[cfg(not(feature = "ar"))]
+            (Some(_), _) => anyhow::bail!("Keyword profiles are only for the Arabic dialect"),
+            #[cfg(not(feature = "ar"))]
+            (None, Some(_)) => anyhow::bail!("Spellings are only for the Arabic dialect"),
+        };
+        let f = compiled
+            .map_err(|err| anyhow::anyhow!(describe_compile_error(&content, &err, language)))?;
+
+        let mut interpreter = virtual_machine::VirtualMachine::default();
+        interpreter.language = language;
+        interpreter.file_access = match args.files_within {
+            _ if args.no_files => FileAccess::Denied,
+            Some(root) => FileAccess::Within(root),
+            None => FileAccess::Unrestricted,
+        };
+        if args.bidi {
+            interpreter.std_io = Box::new(DefaultStdIO {
+                mode: OutputMode::IsolateLtr,
+            });
+        }
+        interpreter
+            .interpret(f)
+            .map_err(|err| anyhow::anyhow!("{}", err))
+    }
+}
+
+/// The built-in profile called `profile`, or else the profile in the file at that path.
+#[cfg(feature = "ar")]
+fn load_keyword_profile(profile: &str) -> anyhow::Result<KeywordProfile> {
+    if let Some(builtin) = KeywordProfile::builtin(profile) {
+        return Ok(builtin);
+    }
+    let text = fs::read_to_string(profile)
+        .map_err(|err| anyhow::anyhow!("Can't read keyword profile {}: {}", profile, err))?;
+    KeywordProfile::parse(&text)
+        .map_err(|err| anyhow::anyhow!("{}:{}: {}", profile, err.line, err.what))
+}
+
+/// The error with the line it's on and a caret under where it is.
+fn describe_compile_error(source: &str, err: &compiler::Error, language: Language) -> String {
+    let (kind, what, line, col) = match err {
+        compiler::Error::Lexical(err) => (diagnostic::LEXICAL_ERROR, &err.what, err.line, err.col),
+        compiler::Error::Parse(info) => (diagnostic::PARSE_ERROR, &info.what, info.line, info.col),
+        compiler::Error::Semantic(info) => {
+            (diagnostic::SEMANTIC_ERROR, &info.what, info.line, info.col)
+        }
+        compiler::Error::Internal(what) => {
+            return diagnostic::INTERNAL_ERROR.render(language, &[what])
+        }
+    };
+
+    excerpt(source, kind.render(language, &[&line, what]), line, col)
+}
+
+/// The message followed by the source line it's about, with a caret under the column.
+fn excerpt(source: &str, message: String, line: usize, col: i64) -> String {
+    match source.lines().nth(line.saturating_sub(1)) {
+        Some(text) => format!(
+            "{}\n{}",
+            message,
+            caret_excerpt(text, usize::try_from(col).unwrap_or(0))
+        ),
+        None => message,
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum Lang {
+    /// English
+    En,
+    /// Arabic
+    Ar,
+}
+
+impl From<Lang> for Language {
+    fn from(lang: Lang) -> Language {
+        match lang {
+            Lang::En => Language::English,
+            Lang::Ar => Language::Arabic,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum Spelling {
+    /// Different spellings of a name are one name
+    Normalize,
+    /// Different spellings are different names, and each is reported
+    Warn,
+}
+
+#[cfg(feature = "ar")]
+impl From<Spelling> for IdentifierSpelling {
+    fn from(spelling: Spelling) -> IdentifierSpelling {
+        match spelling {
+            Spelling::Normalize => IdentifierSpelling::Normalize,
+            Spelling::Warn => IdentifierSpelling::Warn,
+        }
     }
 }
 
@@ -12,6 +12,7 @@ pub struct Cli {
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
     match cli.command {
-        Firnas::Compile { path, extentions } => Firnas::handle_file(path.to_string(), &extentions),
+        Firnas::Compile(args) => Firnas::handle_file(args),
+        Firnas::Canonicalize(args) => Firnas::handle_canonicalize(args),
     }
 }
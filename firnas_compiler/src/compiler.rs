@@ -1,8 +1,14 @@
+use std::fmt;
+
 use firnas_bytecode;
 use firnas_ext;
 use firnas_tokenizer::token;
 use firnas_tokenizer::tokenizer;
 
+use crate::diagnostic;
+use crate::diagnostic::Language;
+use crate::diagnostic::Message;
+
 #[derive(Debug)]
 struct Local {
     name: token::Token,
@@ -29,6 +35,7 @@ pub struct Compiler {
     level_idx: usize,
     current_class: Option<ClassCompiler>,
     extensions: firnas_ext::Extensions,
+    language: Language,
 }
 
 impl Default for Compiler {
@@ -40,6 +47,7 @@ impl Default for Compiler {
             level_idx: 0,
             current_class: None,
             extensions: Default::default(),
+            language: Default::default(),
         }
     }
 }
@@ -82,6 +90,8 @@ enum Precedence {
     And,
     Equality,
     Comparison,
+    Union,
+    Intersection,
     Term,
     Factor,
     Unary,
@@ -106,6 +116,7 @@ enum ParseFn {
     Super,
     List,
     Subscript,
+    SetOrDict,
 }
 
 struct ParseRule {
@@ -122,6 +133,8 @@ enum Resolution {
 
 #[derive(Debug)]
 pub struct ErrorInfo {
+    /// The code of the catalogue entry the error was reported with.
+    pub code: &'static str,
     pub what: String,
     pub line: usize,
     pub col: i64,
@@ -139,19 +152,60 @@ impl Compiler {
     pub fn compile(
         input: String,
         extensions: firnas_ext::Extensions,
+    ) -> Result<firnas_bytecode::Function, Error> {
+        Compiler::compile_with_language(input, extensions, Default::default())
+    }
+
+    /// Compiles `input`, reporting errors in `language`.
+    pub fn compile_with_language(
+        input: String,
+        extensions: firnas_ext::Extensions,
+        language: Language,
+    ) -> Result<firnas_bytecode::Function, Error> {
+        Compiler::compile_scanned(tokenizer::scan_tokens(input), extensions, language)
+    }
+
+    /// Compiles `input` scanned with `options`, e.g. to accept the keywords of a profile,
+    /// along with the warnings the scanner reported.
+    #[cfg(feature = "ar")]
+    pub fn compile_with_options(
+        input: String,
+        extensions: firnas_ext::Extensions,
+        language: Language,
+        options: tokenizer::ScanOptions,
+    ) -> Result<
+        (
+            firnas_bytecode::Function,
+            Vec<firnas_tokenizer::error::Warning>,
+        ),
+        Error,
+    > {
+        let (tokens, warnings) = match tokenizer::scan_tokens_with(input, options) {
+            Ok(scanned) => (Ok(scanned.tokens), scanned.warnings),
+            Err(err) => (Err(err), Vec::new()),
+        };
+        let function = Compiler::compile_scanned(tokens, extensions, language)?;
+        Ok((function, warnings))
+    }
+
+    fn compile_scanned(
+        scanned: Result<Vec<token::Token>, firnas_tokenizer::error::Error>,
+        extensions: firnas_ext::Extensions,
+        language: Language,
     ) -> Result<firnas_bytecode::Function, Error> {
         let mut compiler = Compiler {
             extensions,
+            language,
             ..Default::default()
         };
 
         if extensions.lambdas {
             return Err(Error::Internal(
-                "lambdas extension not implemented for bytecode interpreter".to_string(),
+                diagnostic::LAMBDAS_NOT_IMPLEMENTED.render(language, &[]),
             ));
         }
 
-        match tokenizer::scan_tokens(input) {
+        match scanned {
             Ok(tokens) => {
                 compiler.tokens = tokens;
 
@@ -180,12 +234,17 @@ impl Compiler {
     }
 
     fn class_decl(&mut self) -> Result<(), Error> {
-        self.consume(token::TokenType::Identifier, "Expected class name.")?;
+        self.consume(
+            token::TokenType::Identifier,
+            diagnostic::EXPECTED_CLASS_NAME,
+        )?;
         let class_name_tok = self.previous().clone();
-        let class_name = class_name_tok.clone().lexeme;
+        let class_name = Compiler::identifier_name(&class_name_tok);
         let name_constant = self.identifier_constant(class_name.clone());
+        // the class's own name is shown to the user, so it's spelled as written
+        let display_name_constant = self.identifier_constant(class_name_tok.lexeme.clone());
         let line = self.previous().line;
-        self.emit_op(firnas_bytecode::Op::Class(name_constant), line);
+        self.emit_op(firnas_bytecode::Op::Class(display_name_constant), line);
         self.define_variable(name_constant);
 
         let mut saved_class_compiler = None;
@@ -196,18 +255,18 @@ impl Compiler {
         });
 
         if self.matches(token::TokenType::Less) {
-            self.consume(token::TokenType::Identifier, "Expected superclass name.")?;
+            self.consume(
+                token::TokenType::Identifier,
+                diagnostic::EXPECTED_SUPERCLASS_NAME,
+            )?;
             self.variable(false)?;
 
             if Compiler::identifiers_equal(&class_name_tok.literal, &self.previous().literal) {
-                return Err(Error::Semantic(ErrorInfo {
-                    what: format!(
-                        "A class cannot inherit from itself. Class name = {}",
-                        class_name
-                    ),
-                    line: self.previous().line,
-                    col: self.previous().col,
-                }));
+                return Err(Error::Semantic(self.error_info(
+                    diagnostic::CLASS_INHERITS_ITSELF,
+                    &[&class_name],
+                    self.previous(),
+                )));
             }
 
             self.begin_scope();
@@ -228,7 +287,7 @@ impl Compiler {
 
         self.consume(
             token::TokenType::LeftCurlyBracket,
-            "Expected '{' before class body.",
+            diagnostic::EXPECTED_CLASS_BODY,
         )?;
         loop {
             if self.check(token::TokenType::RightCurlyBracket) || self.check(token::TokenType::Eof)
@@ -240,7 +299,7 @@ impl Compiler {
         }
         self.consume(
             token::TokenType::RightCurlyBracket,
-            "Expected '}' after class body.",
+            diagnostic::UNTERMINATED_CLASS_BODY,
         )?;
         self.emit_op(firnas_bytecode::Op::Pop, self.previous().line);
 
@@ -256,7 +315,10 @@ impl Compiler {
     }
 
     fn method(&mut self) -> Result<(), Error> {
-        self.consume(token::TokenType::Identifier, "Expected method name.")?;
+        self.consume(
+            token::TokenType::Identifier,
+            diagnostic::EXPECTED_METHOD_NAME,
+        )?;
         let method_name = if let Some(token::Literal::Identifier(method_name)) =
             &self.previous().literal.clone()
         {
@@ -270,13 +332,16 @@ impl Compiler {
 
         let constant = self.identifier_constant(method_name.clone());
 
-        let init_name = if cfg!(feature = "en") {
-            "init"
-        } else {
-            "تهيئة"
+        #[cfg(feature = "en")]
+        let is_init = method_name == "init";
+        // identifiers may have been normalized by the tokenizer
+        #[cfg(feature = "ar")]
+        let is_init = {
+            use arabic_utils::arabic_str::ArabicStr;
+            method_name.normalize_arabic() == "تهيئة".normalize_arabic()
         };
 
-        let function_type = if method_name == init_name {
+        let function_type = if is_init {
             FunctionType::Initializer
         } else {
             FunctionType::Method
@@ -290,7 +355,7 @@ impl Compiler {
     }
 
     fn fun_decl(&mut self) -> Result<(), Error> {
-        let global_idx = self.parse_variable("Expected function name.")?;
+        let global_idx = self.parse_variable(diagnostic::EXPECTED_FUNCTION_NAME)?;
         self.mark_initialized();
         self.function(FunctionType::Function)?;
         self.define_variable(global_idx);
@@ -301,11 +366,8 @@ impl Compiler {
         let level = Level {
             function_type,
             function: firnas_bytecode::Function {
-                name: if let Some(token::Literal::Identifier(funname)) = &self.previous().literal {
-                    funname.clone()
-                } else {
-                    panic!("expected identifier");
-                },
+                // shown to the user, so spelled as written
+                name: self.previous().lexeme.clone(),
                 ..Default::default()
             },
             ..Default::default()
@@ -320,13 +382,13 @@ impl Compiler {
         self.begin_scope();
         self.consume(
             token::TokenType::LeftRoundBracket,
-            "Expected '(' after function name.",
+            diagnostic::EXPECTED_PARAMETER_LIST,
         )?;
 
         if !self.check(token::TokenType::RightRoundBracket) {
             loop {
                 self.current_function_mut().arity += 1;
-                let param_const_idx = self.parse_variable("Expected parameter name")?;
+                let param_const_idx = self.parse_variable(diagnostic::EXPECTED_PARAMETER_NAME)?;
                 self.define_variable(param_const_idx);
 
                 if !self.matches(token::TokenType::Comma) {
@@ -337,12 +399,12 @@ impl Compiler {
 
         self.consume(
             token::TokenType::RightRoundBracket,
-            "Expected ')' after parameter list.",
+            diagnostic::UNTERMINATED_PARAMETER_LIST,
         )?;
 
         self.consume(
             token::TokenType::LeftCurlyBracket,
-            "Expected '{' before function body.",
+            diagnostic::EXPECTED_FUNCTION_BODY,
         )?;
         self.block()?;
         self.emit_return();
@@ -367,7 +429,11 @@ impl Compiler {
     }
 
     fn var_decl(&mut self) -> Result<(), Error> {
-        let global_idx = self.parse_variable("Expected variable name.")?;
+        if self.matches(token::TokenType::LeftRoundBracket) {
+            return self.destructuring_var_decl();
+        }
+
+        let global_idx = self.parse_variable(diagnostic::EXPECTED_VARIABLE_NAME)?;
 
         if self.matches(token::TokenType::Equal) {
             self.expression()?;
@@ -378,13 +444,59 @@ impl Compiler {
 
         self.consume(
             token::TokenType::Semicolon,
-            "Expected ';' after variable declaration",
+            diagnostic::UNTERMINATED_VARIABLE_DECLARATION,
         )?;
 
         self.define_variable(global_idx);
         Ok(())
     }
 
+    /*
+    `var (a, b) = expr;` leaves the tuple's elements on the stack in order, so locals pick up
+    their slots directly while globals are defined from the top of the stack down.
+     */
+    fn destructuring_var_decl(&mut self) -> Result<(), Error> {
+        let mut global_idxs = Vec::new();
+        loop {
+            global_idxs.push(self.parse_variable(diagnostic::EXPECTED_VARIABLE_NAME)?);
+            if !self.matches(token::TokenType::Comma) {
+                break;
+            }
+        }
+        self.consume(
+            token::TokenType::RightRoundBracket,
+            diagnostic::UNTERMINATED_VARIABLE_NAMES,
+        )?;
+        self.consume(
+            token::TokenType::Equal,
+            diagnostic::EXPECTED_UNPACK_ASSIGNMENT,
+        )?;
+
+        self.expression()?;
+        let line = self.previous().line;
+        self.emit_op(firnas_bytecode::Op::Unpack(global_idxs.len()), line);
+
+        self.consume(
+            token::TokenType::Semicolon,
+            diagnostic::UNTERMINATED_VARIABLE_DECLARATION,
+        )?;
+
+        let scope_depth = self.scope_depth();
+        if scope_depth > 0 {
+            let num_locals = self.locals().len();
+            for local in &mut self.locals_mut()[num_locals - global_idxs.len()..] {
+                local.depth = scope_depth;
+            }
+            return Ok(());
+        }
+
+        let line = self.previous().line;
+        for global_idx in global_idxs.into_iter().rev() {
+            self.emit_op(firnas_bytecode::Op::DefineGlobal(global_idx), line);
+        }
+        Ok(())
+    }
+
     fn mark_initialized(&mut self) -> bool {
         let scope_depth = self.scope_depth();
         if scope_depth > 0 {
@@ -421,14 +533,11 @@ impl Compiler {
                 && Compiler::identifiers_equal(&local.name.literal, &name.literal)
         });
         if has_redeclaration {
-            return Err(Error::Semantic(ErrorInfo {
-                what: format!(
-                    "Redeclaration of variable {} in the same scope.",
-                    name.lexeme
-                ),
-                line: self.previous().line,
-                col: self.previous().col,
-            }));
+            return Err(Error::Semantic(self.error_info(
+                diagnostic::REDECLARED_VARIABLE,
+                &[&name.lexeme],
+                self.previous(),
+            )));
         }
 
         self.add_local(name);
@@ -461,6 +570,15 @@ impl Compiler {
         }
     }
 
+    /// The name an identifier token stands for, which under `ar` may be a normalized
+    /// spelling of its lexeme.
+    fn identifier_name(tok: &token::Token) -> String {
+        match &tok.literal {
+            Some(token::Literal::Identifier(name)) => name.clone(),
+            _ => panic!("expected identifier but found {:?}.", tok),
+        }
+    }
+
     fn synthetic_token(text: &str) -> token::Token {
         token::Token {
             ty: token::TokenType::Identifier,
@@ -479,7 +597,7 @@ impl Compiler {
         });
     }
 
-    fn parse_variable(&mut self, error_msg: &str) -> Result<usize, Error> {
+    fn parse_variable(&mut self, error_msg: Message) -> Result<usize, Error> {
         self.consume(token::TokenType::Identifier, error_msg)?;
         self.declare_variable()?;
 
@@ -522,21 +640,14 @@ impl Compiler {
 
     fn return_statement(&mut self) -> Result<(), Error> {
         if self.function_type() == FunctionType::Script {
-            return Err(Error::Semantic(ErrorInfo {
-                what: "Cannot return from top-level code.".to_string(),
-                line: self.previous().line,
-                col: self.previous().col,
-            }));
+            return Err(self.error(diagnostic::TOP_LEVEL_RETURN));
         }
 
         if self.matches(token::TokenType::Semicolon) {
             self.emit_return();
         } else {
             self.expression()?;
-            self.consume(
-                token::TokenType::Semicolon,
-                "Expected ';' after return value.",
-            )?;
+            self.consume(token::TokenType::Semicolon, diagnostic::UNTERMINATED_RETURN)?;
             self.emit_op(firnas_bytecode::Op::Return, self.previous().line);
         }
         Ok(())
@@ -546,7 +657,7 @@ impl Compiler {
         self.begin_scope();
         self.consume(
             token::TokenType::LeftRoundBracket,
-            "Expected '(' after 'for'.",
+            diagnostic::EXPECTED_FOR_CLAUSES,
         )?;
         if self.matches(token::TokenType::Semicolon) {
         } else if self.matches(token::TokenType::Var) {
@@ -563,7 +674,7 @@ impl Compiler {
             self.expression()?;
             self.consume(
                 token::TokenType::Semicolon,
-                "Expected ';' after loop condition",
+                diagnostic::UNTERMINATED_LOOP_CONDITION,
             )?;
             maybe_exit_jump =
                 Some(self.emit_jump(firnas_bytecode::Op::JumpIfFalse(/*placeholder*/ 0)));
@@ -575,12 +686,12 @@ impl Compiler {
         if !self.matches(token::TokenType::RightRoundBracket) {
             let body_jump = self.emit_jump(firnas_bytecode::Op::Jump(/*placeholder*/ 0));
 
-            let increment_start = self.current_chunk().code.len() + 1;
+            let increment_start = self.current_chunk().code.len();
             self.expression()?;
             self.emit_op(firnas_bytecode::Op::Pop, self.previous().line);
             self.consume(
                 token::TokenType::RightRoundBracket,
-                "Expected ')' after for clauses.",
+                diagnostic::UNTERMINATED_FOR_CLAUSES,
             )?;
 
             self.emit_loop(loop_start);
@@ -606,12 +717,12 @@ impl Compiler {
         let loop_start = self.current_chunk().code.len();
         self.consume(
             token::TokenType::LeftRoundBracket,
-            "Expected '(' after 'while'.",
+            diagnostic::EXPECTED_WHILE_CONDITION,
         )?;
         self.expression()?;
         self.consume(
             token::TokenType::RightRoundBracket,
-            "Expected ')' after condition.",
+            diagnostic::UNTERMINATED_CONDITION,
         )?;
 
         let exit_jump = self.emit_jump(firnas_bytecode::Op::JumpIfFalse(/*placeholder*/ 0));
@@ -627,19 +738,20 @@ impl Compiler {
     }
 
     fn emit_loop(&mut self, loop_start: usize) {
-        let offset = self.current_chunk().code.len() - loop_start + 2;
+        // the ip has already moved past the loop op when it runs
+        let offset = self.current_chunk().code.len() - loop_start + 1;
         self.emit_op(firnas_bytecode::Op::Loop(offset), self.previous().line);
     }
 
     fn if_statement(&mut self) -> Result<(), Error> {
         self.consume(
             token::TokenType::LeftRoundBracket,
-            "Expected '(' after 'if'.",
+            diagnostic::EXPECTED_IF_CONDITION,
         )?;
         self.expression()?;
         self.consume(
             token::TokenType::RightRoundBracket,
-            "Expected ')' after condition.",
+            diagnostic::UNTERMINATED_CONDITION,
         )?;
 
         let then_jump = self.emit_jump(firnas_bytecode::Op::JumpIfFalse(
@@ -690,7 +802,7 @@ impl Compiler {
 
         self.consume(
             token::TokenType::RightCurlyBracket,
-            "Expected '}' after block",
+            diagnostic::UNTERMINATED_BLOCK,
         )?;
 
         Ok(())
@@ -730,7 +842,7 @@ impl Compiler {
         self.expression()?;
         self.consume(
             token::TokenType::Semicolon,
-            "Expected ';' after expression.",
+            diagnostic::UNTERMINATED_EXPRESSION_STATEMENT,
         )?;
         let line = self.previous().line;
         self.emit_op(firnas_bytecode::Op::Pop, line);
@@ -758,11 +870,25 @@ impl Compiler {
     }
 
     fn grouping(&mut self, _can_assign: bool) -> Result<(), Error> {
+        if self.matches(token::TokenType::RightRoundBracket) {
+            self.emit_op(firnas_bytecode::Op::BuildTuple(0), self.previous().line);
+            return Ok(());
+        }
+
         self.expression()?;
 
+        if self.matches(token::TokenType::Comma) {
+            let num_elements = self.tuple_elements()?;
+            self.emit_op(
+                firnas_bytecode::Op::BuildTuple(num_elements),
+                self.previous().line,
+            );
+            return Ok(());
+        }
+
         self.consume(
             token::TokenType::RightRoundBracket,
-            "Expected ')' after expression.",
+            diagnostic::UNTERMINATED_GROUPING,
         )?;
         Ok(())
     }
@@ -775,6 +901,18 @@ impl Compiler {
                 self.emit_number(n, tok.line);
                 Ok(())
             }
+            Some(token::Literal::Integer(n)) => {
+                self.emit_integer(n, tok.line);
+                Ok(())
+            }
+            Some(token::Literal::BigInteger(digits)) => {
+                self.emit_constant(firnas_bytecode::Constant::BigInteger(digits), tok.line);
+                Ok(())
+            }
+            Some(token::Literal::Rational(text)) => {
+                self.emit_constant(firnas_bytecode::Constant::Rational(text), tok.line);
+                Ok(())
+            }
             _ => panic!(
                 "Expected number at line={},col={}. current token {:?}",
                 tok.line, tok.col, tok
@@ -872,9 +1010,7 @@ impl Compiler {
 
         let prev_level_idx = self.level_idx - 1;
 
-        if let Some(local_idx) =
-            Compiler::resolve_local_static(&self.levels[prev_level_idx], name, self.previous())?
-        {
+        if let Some(local_idx) = self.resolve_local_in(&self.levels[prev_level_idx], name)? {
             self.levels[prev_level_idx].locals[local_idx].is_captured = true;
 
             return Ok(Some(
@@ -910,21 +1046,14 @@ impl Compiler {
     }
 
     fn resolve_local(&self, name: &str) -> Result<Option<usize>, Error> {
-        Compiler::resolve_local_static(self.current_level(), name, self.previous())
+        self.resolve_local_in(self.current_level(), name)
     }
 
-    fn resolve_local_static(
-        level: &Level,
-        name: &str,
-        prev_tok: &token::Token,
-    ) -> Result<Option<usize>, Error> {
+    fn resolve_local_in(&self, level: &Level, name: &str) -> Result<Option<usize>, Error> {
         for (idx, local) in level.locals.iter().rev().enumerate() {
             if Compiler::identifier_equal(&local.name.literal, name) {
                 if local.depth == -1 {
-                    return Err(Compiler::error_at_tok(
-                        "Cannot read local variable in its own initializer.",
-                        prev_tok,
-                    ));
+                    return Err(self.error(diagnostic::LOCAL_IN_OWN_INITIALIZER));
                 }
                 return Ok(Some(level.locals.len() - 1 - idx));
             }
@@ -969,6 +1098,14 @@ impl Compiler {
                 self.emit_op(firnas_bytecode::Op::Divide, operator.line);
                 Ok(())
             }
+            token::TokenType::Pipe => {
+                self.emit_op(firnas_bytecode::Op::Union, operator.line);
+                Ok(())
+            }
+            token::TokenType::Ampersand => {
+                self.emit_op(firnas_bytecode::Op::Intersection, operator.line);
+                Ok(())
+            }
             token::TokenType::BangEqual => {
                 self.emit_op(firnas_bytecode::Op::Equal, operator.line);
                 self.emit_op(firnas_bytecode::Op::Not, operator.line);
@@ -996,11 +1133,11 @@ impl Compiler {
                 self.emit_op(firnas_bytecode::Op::Not, operator.line);
                 Ok(())
             }
-            _ => Err(Error::Parse(ErrorInfo {
-                what: format!("Invalid token {:?} in binary expression", operator.ty),
-                line: operator.line,
-                col: operator.col,
-            })),
+            _ => Err(Error::Parse(self.error_info(
+                diagnostic::INVALID_BINARY_OPERATOR,
+                &[&diagnostic::token_name(operator.ty, self.language)],
+                &operator,
+            ))),
         }
     }
 
@@ -1034,26 +1171,26 @@ impl Compiler {
         let tok = self.previous().clone();
         match &self.current_class {
             None => {
-                return Err(Error::Semantic(ErrorInfo {
-                    what: "Can't use 'super' outside of a class".to_string(),
-                    line: tok.line,
-                    col: tok.col,
-                }))
+                return Err(Error::Semantic(self.error_info(
+                    diagnostic::SUPER_OUTSIDE_CLASS,
+                    &[],
+                    &tok,
+                )))
             }
             Some(class) => {
                 if !class.has_superclass {
-                    return Err(Error::Semantic(ErrorInfo {
-                        what: "Can't use 'super' in a class with no superclass".to_string(),
-                        line: tok.line,
-                        col: tok.col,
-                    }));
+                    return Err(Error::Semantic(self.error_info(
+                        diagnostic::SUPER_WITHOUT_SUPERCLASS,
+                        &[],
+                        &tok,
+                    )));
                 }
             }
         }
-        self.consume(token::TokenType::Dot, "Expected '.' after 'super' keyword.")?;
+        self.consume(token::TokenType::Dot, diagnostic::EXPECTED_DOT_AFTER_SUPER)?;
         self.consume(
             token::TokenType::Identifier,
-            "Expected superclass method name.",
+            diagnostic::EXPECTED_SUPERCLASS_METHOD_NAME,
         )?;
 
         let method_name = if let Some(token::Literal::Identifier(method_name)) =
@@ -1081,11 +1218,11 @@ impl Compiler {
     fn this(&mut self, _can_assign: bool) -> Result<(), Error> {
         let tok = self.previous().clone();
         if self.current_class.is_none() {
-            return Err(Error::Semantic(ErrorInfo {
-                what: "Cannot use 'this' outside of class.".to_string(),
-                line: tok.line,
-                col: tok.col,
-            }));
+            return Err(Error::Semantic(self.error_info(
+                diagnostic::THIS_OUTSIDE_CLASS,
+                &[],
+                &tok,
+            )));
         }
 
         self.variable(false)
@@ -1094,9 +1231,9 @@ impl Compiler {
     fn dot(&mut self, can_assign: bool) -> Result<(), Error> {
         self.consume(
             token::TokenType::Identifier,
-            "Expected property name after '.'.",
+            diagnostic::EXPECTED_PROPERTY_NAME,
         )?;
-        let property_name = self.previous().clone().lexeme;
+        let property_name = Compiler::identifier_name(self.previous());
         let property_constant = self.identifier_constant(property_name.clone());
         let op = if can_assign && self.matches(token::TokenType::Equal) {
             self.expression()?;
@@ -1113,29 +1250,59 @@ impl Compiler {
 
     fn subscr(&mut self, _can_assign: bool) -> Result<(), Error> {
         if !self.extensions.lists {
-            return Err(Error::Parse(ErrorInfo {
-                what: "Unexpected '['".to_string(),
-                line: self.previous().line,
-                col: self.previous().col,
-            }));
+            return Err(Error::Parse(self.error_info(
+                diagnostic::UNEXPECTED_SQUARE_BRACKET,
+                &[],
+                self.previous(),
+            )));
         }
 
-        self.expression()?;
+        // `a[start:end:step]` is compiled as a slice with a nil in place of each omitted part
+        if self.check(token::TokenType::Colon) {
+            self.emit_op(firnas_bytecode::Op::Nil, self.previous().line);
+        } else {
+            self.expression()?;
+        }
+
+        if !self.matches(token::TokenType::Colon) {
+            self.consume(
+                token::TokenType::RightSquareBracket,
+                diagnostic::UNTERMINATED_SUBSCRIPT,
+            )?;
+            self.emit_op(firnas_bytecode::Op::Subscr, self.previous().line);
+            return Ok(());
+        }
+
+        self.slice_bound()?;
+        if self.matches(token::TokenType::Colon) {
+            self.slice_bound()?;
+        } else {
+            self.emit_op(firnas_bytecode::Op::Nil, self.previous().line);
+        }
         self.consume(
             token::TokenType::RightSquareBracket,
-            "Expected ] after subscript",
+            diagnostic::UNTERMINATED_SLICE,
         )?;
-        self.emit_op(firnas_bytecode::Op::Subscr, self.previous().line);
+        self.emit_op(firnas_bytecode::Op::Slice, self.previous().line);
         Ok(())
     }
 
+    fn slice_bound(&mut self) -> Result<(), Error> {
+        if self.check(token::TokenType::Colon) || self.check(token::TokenType::RightSquareBracket) {
+            self.emit_op(firnas_bytecode::Op::Nil, self.previous().line);
+            Ok(())
+        } else {
+            self.expression()
+        }
+    }
+
     fn list(&mut self, _can_assign: bool) -> Result<(), Error> {
         if !self.extensions.lists {
-            return Err(Error::Parse(ErrorInfo {
-                what: "Unexpected '['".to_string(),
-                line: self.previous().line,
-                col: self.previous().col,
-            }));
+            return Err(Error::Parse(self.error_info(
+                diagnostic::UNEXPECTED_SQUARE_BRACKET,
+                &[],
+                self.previous(),
+            )));
         }
 
         let arg_count = self.list_elements()?;
@@ -1157,10 +1324,96 @@ impl Compiler {
                 }
             }
         }
-        self.consume(token::TokenType::RightSquareBracket, "Expected ']'.")?;
+        self.consume(
+            token::TokenType::RightSquareBracket,
+            diagnostic::UNTERMINATED_LIST,
+        )?;
         Ok(num_elements)
     }
 
+    // the first element and its trailing comma have already been consumed, as in `(a,)`
+    fn tuple_elements(&mut self) -> Result<usize, Error> {
+        let mut num_elements: usize = 1;
+        while !self.check(token::TokenType::RightRoundBracket) {
+            self.expression()?;
+            num_elements += 1;
+            if !self.matches(token::TokenType::Comma) {
+                break;
+            }
+        }
+        self.consume(
+            token::TokenType::RightRoundBracket,
+            diagnostic::UNTERMINATED_TUPLE,
+        )?;
+        Ok(num_elements)
+    }
+
+    /*
+    `{a, b}` is a set and `{k: v}` is a dictionary; the first element decides which one.
+    `{}` stays the empty set, so the empty dictionary is written `{:}`.
+     */
+    fn set_or_dict(&mut self, _can_assign: bool) -> Result<(), Error> {
+        if self.matches(token::TokenType::Colon) {
+            self.consume(
+                token::TokenType::RightCurlyBracket,
+                diagnostic::UNTERMINATED_SET_OR_DICT,
+            )?;
+            self.emit_op(firnas_bytecode::Op::BuildDict(0), self.previous().line);
+            return Ok(());
+        }
+        if self.check(token::TokenType::RightCurlyBracket) {
+            self.advance();
+            self.emit_op(firnas_bytecode::Op::BuildSet(0), self.previous().line);
+            return Ok(());
+        }
+
+        self.expression()?;
+        if self.matches(token::TokenType::Colon) {
+            self.dict()
+        } else {
+            self.set()
+        }
+    }
+
+    /// The rest of a set literal whose first element has been compiled.
+    fn set(&mut self) -> Result<(), Error> {
+        let mut num_elements: usize = 1;
+        while self.matches(token::TokenType::Comma) {
+            self.expression()?;
+            num_elements += 1;
+        }
+        self.consume(
+            token::TokenType::RightCurlyBracket,
+            diagnostic::UNTERMINATED_SET_OR_DICT,
+        )?;
+        self.emit_op(
+            firnas_bytecode::Op::BuildSet(num_elements),
+            self.previous().line,
+        );
+        Ok(())
+    }
+
+    /// The rest of a dictionary literal whose first key and ':' have been compiled.
+    fn dict(&mut self) -> Result<(), Error> {
+        self.expression()?;
+        let mut num_entries: usize = 1;
+        while self.matches(token::TokenType::Comma) {
+            self.expression()?;
+            self.consume(token::TokenType::Colon, diagnostic::EXPECTED_DICT_COLON)?;
+            self.expression()?;
+            num_entries += 1;
+        }
+        self.consume(
+            token::TokenType::RightCurlyBracket,
+            diagnostic::UNTERMINATED_SET_OR_DICT,
+        )?;
+        self.emit_op(
+            firnas_bytecode::Op::BuildDict(num_entries),
+            self.previous().line,
+        );
+        Ok(())
+    }
+
     fn argument_list(&mut self) -> Result<u8, Error> {
         let mut arg_count: u8 = 0;
         if !self.check(token::TokenType::RightRoundBracket) {
@@ -1174,7 +1427,7 @@ impl Compiler {
         }
         self.consume(
             token::TokenType::RightRoundBracket,
-            "Expected ')' after argument list.",
+            diagnostic::UNTERMINATED_ARGUMENT_LIST,
         )?;
         Ok(arg_count)
     }
@@ -1193,11 +1446,11 @@ impl Compiler {
                 self.emit_op(firnas_bytecode::Op::Not, operator.line);
                 Ok(())
             }
-            _ => Err(Error::Parse(ErrorInfo {
-                what: format!("Invalid token in unary op {:?}", operator.ty),
-                line: operator.line,
-                col: operator.col,
-            })),
+            _ => Err(Error::Parse(self.error_info(
+                diagnostic::INVALID_UNARY_OPERATOR,
+                &[&diagnostic::token_name(operator.ty, self.language)],
+                &operator,
+            ))),
         }
     }
 
@@ -1206,6 +1459,16 @@ impl Compiler {
         self.emit_op(firnas_bytecode::Op::Constant(const_idx), lineno);
     }
 
+    fn emit_integer(&mut self, n: i64, lineno: usize) {
+        let const_idx = self.current_chunk().add_constant_integer(n);
+        self.emit_op(firnas_bytecode::Op::Constant(const_idx), lineno);
+    }
+
+    fn emit_constant(&mut self, constant: firnas_bytecode::Constant, lineno: usize) {
+        let const_idx = self.current_chunk().add_constant(constant);
+        self.emit_op(firnas_bytecode::Op::Constant(const_idx), lineno);
+    }
+
     fn emit_op(&mut self, op: firnas_bytecode::Op, lineno: usize) {
         self.current_chunk()
             .code
@@ -1230,7 +1493,7 @@ impl Compiler {
         match Compiler::get_rule(self.previous().ty).prefix {
             Some(parse_fn) => self.apply_parse_fn(parse_fn, can_assign)?,
             None => {
-                return Err(self.error("Expected expression."));
+                return Err(self.error(diagnostic::EXPECTED_EXPRESSION));
             }
         }
 
@@ -1243,33 +1506,43 @@ impl Compiler {
         }
 
         if can_assign && self.matches(token::TokenType::Equal) {
-            if let Some((firnas_bytecode::Op::Subscr, _)) = self.current_chunk().code.last() {
-                self.fixup_subscript_to_setitem()?;
-            } else {
-                return Err(self.error("Invalid assignment target"));
+            match self.current_chunk().code.last() {
+                Some((firnas_bytecode::Op::Subscr, _)) => {
+                    self.fixup_subscript_to_setitem(firnas_bytecode::Op::SetItem)?
+                }
+                Some((firnas_bytecode::Op::Slice, _)) => {
+                    self.fixup_subscript_to_setitem(firnas_bytecode::Op::SetSlice)?
+                }
+                _ => return Err(self.error(diagnostic::INVALID_ASSIGNMENT_TARGET)),
             }
         }
 
         Ok(())
     }
 
-    fn fixup_subscript_to_setitem(&mut self) -> Result<(), Error> {
-        self.current_chunk().code.pop(); // pop the subscript op
+    fn fixup_subscript_to_setitem(&mut self, set_op: firnas_bytecode::Op) -> Result<(), Error> {
+        self.current_chunk().code.pop(); // pop the subscript or slice op
         self.expression()?; // consume right hand side
-        self.emit_op(firnas_bytecode::Op::SetItem, self.previous().line);
+        self.emit_op(set_op, self.previous().line);
         Ok(())
     }
 
-    fn error(&self, what: &str) -> Error {
-        Compiler::error_at_tok(what, self.previous())
+    fn error(&self, message: Message) -> Error {
+        Error::Semantic(self.error_info(message, &[], self.previous()))
     }
 
-    fn error_at_tok(what: &str, prev_tok: &token::Token) -> Error {
-        Error::Semantic(ErrorInfo {
-            what: what.to_string(),
-            line: prev_tok.line,
-            col: prev_tok.col,
-        })
+    fn error_info(
+        &self,
+        message: Message,
+        args: &[&dyn fmt::Display],
+        tok: &token::Token,
+    ) -> ErrorInfo {
+        ErrorInfo {
+            code: message.code,
+            what: message.render(self.language, args),
+            line: tok.line,
+            col: tok.col,
+        }
     }
 
     fn apply_parse_fn(&mut self, parse_fn: ParseFn, can_assign: bool) -> Result<(), Error> {
@@ -1289,23 +1562,27 @@ impl Compiler {
             ParseFn::Super => self.super_(can_assign),
             ParseFn::List => self.list(can_assign),
             ParseFn::Subscript => self.subscr(can_assign),
+            ParseFn::SetOrDict => self.set_or_dict(can_assign),
         }
     }
 
-    fn consume(&mut self, tok: token::TokenType, on_err_str: &str) -> Result<&token::Token, Error> {
+    /// Reports a missing token under the code of `on_err`, which says what the token was for.
+    fn consume(&mut self, tok: token::TokenType, on_err: Message) -> Result<&token::Token, Error> {
         if self.check(tok) {
             return Ok(self.advance());
         }
-        Err(Error::Parse(ErrorInfo {
-            what: format!(
-                "Expected token {:?}, but found token {:?}: {}",
-                tok,
-                self.peek().ty,
-                on_err_str
-            ),
-            line: self.peek().line,
-            col: self.peek().col,
-        }))
+        let found = self.peek().ty;
+        let mut info = self.error_info(
+            diagnostic::EXPECTED_TOKEN,
+            &[
+                &diagnostic::token_name(tok, self.language),
+                &diagnostic::token_name(found, self.language),
+                &on_err.render(self.language, &[]),
+            ],
+            self.peek(),
+        );
+        info.code = on_err.code;
+        Err(Error::Parse(info))
     }
 
     fn advance(&mut self) -> &token::Token {
@@ -1335,7 +1612,9 @@ impl Compiler {
             Precedence::Or => Precedence::And,
             Precedence::And => Precedence::Equality,
             Precedence::Equality => Precedence::Comparison,
-            Precedence::Comparison => Precedence::Term,
+            Precedence::Comparison => Precedence::Union,
+            Precedence::Union => Precedence::Intersection,
+            Precedence::Intersection => Precedence::Term,
             Precedence::Term => Precedence::Factor,
             Precedence::Factor => Precedence::Unary,
             Precedence::Unary => Precedence::Call,
@@ -1357,7 +1636,7 @@ impl Compiler {
                 precedence: Precedence::None,
             },
             token::TokenType::LeftCurlyBracket => ParseRule {
-                prefix: None,
+                prefix: Some(ParseFn::SetOrDict),
                 infix: None,
                 precedence: Precedence::None,
             },
@@ -1381,6 +1660,11 @@ impl Compiler {
                 infix: None,
                 precedence: Precedence::None,
             },
+            token::TokenType::Colon => ParseRule {
+                prefix: None,
+                infix: None,
+                precedence: Precedence::None,
+            },
             token::TokenType::Dot => ParseRule {
                 prefix: None,
                 infix: Some(ParseFn::Dot),
@@ -1411,6 +1695,16 @@ impl Compiler {
                 infix: Some(ParseFn::Binary),
                 precedence: Precedence::Factor,
             },
+            token::TokenType::Pipe => ParseRule {
+                prefix: None,
+                infix: Some(ParseFn::Binary),
+                precedence: Precedence::Union,
+            },
+            token::TokenType::Ampersand => ParseRule {
+                prefix: None,
+                infix: Some(ParseFn::Binary),
+                precedence: Precedence::Intersection,
+            },
             token::TokenType::Bang => ParseRule {
                 prefix: Some(ParseFn::Unary),
                 infix: None,
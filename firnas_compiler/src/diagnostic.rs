@@ -0,0 +1,470 @@
+use std::fmt;
+use std::fmt::Write;
+
+use firnas_tokenizer::token::TokenType;
+
+/*
+Every diagnostic the compiler and the virtual machine report is an entry of a catalogue: a
+stable code, which tools and tests can match on, and the text of the message in each language
+the messages are written in. Codes starting with C are reported by the compiler, codes starting
+with R while running.
+
+The language is chosen when compiling or running, and defaults to the language of the dialect
+the build reads.
+ */
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    English,
+    Arabic,
+}
+
+impl Default for Language {
+    fn default() -> Language {
+        if cfg!(feature = "ar") {
+            Language::Arabic
+        } else {
+            Language::English
+        }
+    }
+}
+
+/// A catalogue entry, with `{}` marking where each of its arguments goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Message {
+    pub code: &'static str,
+    pub english: &'static str,
+    pub arabic: &'static str,
+}
+
+impl Message {
+    pub fn template(&self, language: Language) -> &'static str {
+        match language {
+            Language::English => self.english,
+            Language::Arabic => self.arabic,
+        }
+    }
+
+    /// The message in `language`, with the placeholders filled by `args` in order.
+    pub fn render(&self, language: Language, args: &[&dyn fmt::Display]) -> String {
+        let mut res = String::new();
+        let mut args = args.iter();
+        let mut rest = self.template(language);
+        while let Some(idx) = rest.find("{}") {
+            res.push_str(&rest[..idx]);
+            if let Some(arg) = args.next() {
+                write!(res, "{}", arg).unwrap();
+            }
+            rest = &rest[idx + 2..];
+        }
+        res.push_str(rest);
+        res
+    }
+}
+
+/// The name of a kind of token in messages: its variant in English, and how it is written in
+/// the Arabic dialect in Arabic.
+pub fn token_name(ty: TokenType, language: Language) -> String {
+    if language == Language::English {
+        return format!("{:?}", ty);
+    }
+    let spelling = match ty {
+        TokenType::LeftRoundBracket => "(",
+        TokenType::RightRoundBracket => ")",
+        TokenType::LeftCurlyBracket => "{",
+        TokenType::RightCurlyBracket => "}",
+        TokenType::LeftSquareBracket => "[",
+        TokenType::RightSquareBracket => "]",
+        TokenType::Comma => "،",
+        TokenType::Dot => ".",
+        TokenType::Minus => "-",
+        TokenType::Plus => "+",
+        TokenType::Semicolon => "؛",
+        TokenType::Slash => "\\",
+        TokenType::Star => "*",
+        TokenType::Pipe => "|",
+        TokenType::Ampersand => "&",
+        TokenType::Colon => ":",
+        TokenType::Bang => "!",
+        TokenType::BangEqual => "!=",
+        TokenType::Equal => "=",
+        TokenType::EqualEqual => "==",
+        TokenType::Greater => ">",
+        TokenType::GreaterEqual => ">=",
+        TokenType::Less => "<",
+        TokenType::LessEqual => "<=",
+        TokenType::Identifier => return "اسم".to_string(),
+        TokenType::String => return "نص".to_string(),
+        TokenType::Number => return "رقم".to_string(),
+        TokenType::And => "و",
+        TokenType::Class => "صنف",
+        TokenType::Else => "غير_ذلك",
+        TokenType::False => "خطا",
+        TokenType::Fun => "دالة",
+        TokenType::For => "من",
+        TokenType::If => "اذا_كان",
+        TokenType::Nil => "عدم",
+        TokenType::Or => "او",
+        TokenType::Return => "رد",
+        TokenType::Super => "اساس",
+        TokenType::This => "هذا",
+        TokenType::True => "صح",
+        TokenType::Var => "دع",
+        TokenType::While => "طالما",
+        TokenType::Lambda => "لامدا",
+        TokenType::Eof => return "نهاية الملف".to_string(),
+    };
+    format!("'{}'", spelling)
+}
+
+macro_rules! messages {
+    ($($name:ident = $code:literal, $english:literal, $arabic:literal;)*) => {
+        $(
+            pub const $name: Message = Message {
+                code: $code,
+                english: $english,
+                arabic: $arabic,
+            };
+        )*
+
+        /// Every entry of the catalogue.
+        pub const CATALOGUE: &[Message] = &[$($name),*];
+    };
+}
+
+messages! {
+    // compiler
+    EXPECTED_TOKEN = "C000",
+        "Expected token {}, but found token {}: {}",
+        "متوقع الرمز {} لكن وجد الرمز {}: {}";
+    EXPECTED_CLASS_NAME = "C001",
+        "Expected class name.",
+        "متوقع اسم الصنف.";
+    EXPECTED_SUPERCLASS_NAME = "C002",
+        "Expected superclass name.",
+        "متوقع اسم الصنف الأساس.";
+    CLASS_INHERITS_ITSELF = "C003",
+        "A class cannot inherit from itself. Class name = {}",
+        "لا يمكن لصنف أن يرث من نفسه. اسم الصنف = {}";
+    EXPECTED_CLASS_BODY = "C004",
+        "Expected '{' before class body.",
+        "متوقع '{' قبل متن الصنف.";
+    UNTERMINATED_CLASS_BODY = "C005",
+        "Expected '}' after class body.",
+        "متوقع '}' بعد متن الصنف.";
+    EXPECTED_METHOD_NAME = "C006",
+        "Expected method name.",
+        "متوقع اسم الطريقة.";
+    EXPECTED_PARAMETER_LIST = "C007",
+        "Expected '(' after function name.",
+        "متوقع '(' بعد اسم الدالة.";
+    UNTERMINATED_PARAMETER_LIST = "C008",
+        "Expected ')' after parameter list.",
+        "متوقع ')' بعد قائمة المعاملات.";
+    EXPECTED_FUNCTION_BODY = "C009",
+        "Expected '{' before function body.",
+        "متوقع '{' قبل متن الدالة.";
+    UNTERMINATED_VARIABLE_DECLARATION = "C010",
+        "Expected ';' after variable declaration",
+        "متوقع '؛' بعد تعريف المتغير";
+    UNTERMINATED_VARIABLE_NAMES = "C011",
+        "Expected ')' after variable names.",
+        "متوقع ')' بعد أسماء المتغيرات.";
+    EXPECTED_UNPACK_ASSIGNMENT = "C012",
+        "Expected '=' after variable names.",
+        "متوقع '=' بعد أسماء المتغيرات.";
+    REDECLARED_VARIABLE = "C013",
+        "Redeclaration of variable {} in the same scope.",
+        "إعادة تعريف المتغير {} في النطاق نفسه.";
+    TOP_LEVEL_RETURN = "C014",
+        "Cannot return from top-level code.",
+        "لا يمكن الرد من خارج الدوال.";
+    UNTERMINATED_RETURN = "C015",
+        "Expected ';' after return value.",
+        "متوقع '؛' بعد القيمة المردودة.";
+    EXPECTED_FOR_CLAUSES = "C016",
+        "Expected '(' after 'for'.",
+        "متوقع '(' بعد 'من'.";
+    UNTERMINATED_LOOP_CONDITION = "C017",
+        "Expected ';' after loop condition",
+        "متوقع '؛' بعد شرط الحلقة";
+    UNTERMINATED_FOR_CLAUSES = "C018",
+        "Expected ')' after for clauses.",
+        "متوقع ')' بعد أجزاء 'من'.";
+    EXPECTED_WHILE_CONDITION = "C019",
+        "Expected '(' after 'while'.",
+        "متوقع '(' بعد 'طالما'.";
+    UNTERMINATED_CONDITION = "C020",
+        "Expected ')' after condition.",
+        "متوقع ')' بعد الشرط.";
+    EXPECTED_IF_CONDITION = "C021",
+        "Expected '(' after 'if'.",
+        "متوقع '(' بعد 'اذا_كان'.";
+    UNTERMINATED_BLOCK = "C022",
+        "Expected '}' after block",
+        "متوقع '}' بعد الكتلة";
+    UNTERMINATED_EXPRESSION_STATEMENT = "C023",
+        "Expected ';' after expression.",
+        "متوقع '؛' بعد التعبير.";
+    UNTERMINATED_GROUPING = "C024",
+        "Expected ')' after expression.",
+        "متوقع ')' بعد التعبير.";
+    LOCAL_IN_OWN_INITIALIZER = "C025",
+        "Cannot read local variable in its own initializer.",
+        "لا يمكن قراءة متغير محلي في قيمته الابتدائية.";
+    INVALID_BINARY_OPERATOR = "C026",
+        "Invalid token {} in binary expression",
+        "رمز غير صالح {} في تعبير ثنائي";
+    SUPER_OUTSIDE_CLASS = "C027",
+        "Can't use 'super' outside of a class",
+        "لا يمكن استخدام 'اساس' خارج صنف";
+    SUPER_WITHOUT_SUPERCLASS = "C028",
+        "Can't use 'super' in a class with no superclass",
+        "لا يمكن استخدام 'اساس' في صنف ليس له صنف أساس";
+    EXPECTED_DOT_AFTER_SUPER = "C029",
+        "Expected '.' after 'super' keyword.",
+        "متوقع '.' بعد 'اساس'.";
+    EXPECTED_SUPERCLASS_METHOD_NAME = "C030",
+        "Expected superclass method name.",
+        "متوقع اسم طريقة الصنف الأساس.";
+    THIS_OUTSIDE_CLASS = "C031",
+        "Cannot use 'this' outside of class.",
+        "لا يمكن استخدام 'هذا' خارج صنف.";
+    EXPECTED_PROPERTY_NAME = "C032",
+        "Expected property name after '.'.",
+        "متوقع اسم الخاصية بعد '.'.";
+    UNEXPECTED_SQUARE_BRACKET = "C033",
+        "Unexpected '['",
+        "'[' غير متوقع";
+    UNTERMINATED_SUBSCRIPT = "C034",
+        "Expected ] after subscript",
+        "متوقع ] بعد الفهرس";
+    UNTERMINATED_SLICE = "C035",
+        "Expected ] after slice",
+        "متوقع ] بعد الشريحة";
+    UNTERMINATED_LIST = "C036",
+        "Expected ']'.",
+        "متوقع ']'.";
+    UNTERMINATED_TUPLE = "C037",
+        "Expected ')' after tuple elements.",
+        "متوقع ')' بعد عناصر الصف.";
+    UNTERMINATED_SET_OR_DICT = "C038",
+        "Expected '}'.",
+        "متوقع '}'.";
+    EXPECTED_DICT_COLON = "C039",
+        "Expected ':' after dictionary key.",
+        "متوقع ':' بعد مفتاح القاموس.";
+    UNTERMINATED_ARGUMENT_LIST = "C040",
+        "Expected ')' after argument list.",
+        "متوقع ')' بعد قائمة الوسائط.";
+    INVALID_UNARY_OPERATOR = "C041",
+        "Invalid token in unary op {}",
+        "رمز غير صالح في عملية أحادية {}";
+    EXPECTED_EXPRESSION = "C042",
+        "Expected expression.",
+        "متوقع تعبير.";
+    INVALID_ASSIGNMENT_TARGET = "C043",
+        "Invalid assignment target",
+        "هدف إسناد غير صالح";
+    EXPECTED_FUNCTION_NAME = "C044",
+        "Expected function name.",
+        "متوقع اسم الدالة.";
+    EXPECTED_PARAMETER_NAME = "C045",
+        "Expected parameter name",
+        "متوقع اسم المعامل";
+    EXPECTED_VARIABLE_NAME = "C046",
+        "Expected variable name.",
+        "متوقع اسم المتغير.";
+    LAMBDAS_NOT_IMPLEMENTED = "C047",
+        "lambdas extension not implemented for bytecode interpreter",
+        "امتداد لامدا غير مدعوم في مفسر الشيفرة الوسيطة";
+    LEXICAL_ERROR = "C048",
+        "Lexical error at line {}: {}",
+        "خطأ لغوي في السطر {}: {}";
+    PARSE_ERROR = "C049",
+        "Parse error at line {}: {}",
+        "خطأ نحوي في السطر {}: {}";
+    SEMANTIC_ERROR = "C050",
+        "Semantic error at line {}: {}",
+        "خطأ دلالي في السطر {}: {}";
+    INTERNAL_ERROR = "C051",
+        "Internal error: {}",
+        "خطأ داخلي: {}";
+    WARNING = "C052",
+        "Warning at line {}: {}",
+        "تحذير في السطر {}: {}";
+
+    // virtual machine
+    RUNTIME_ERROR = "R000",
+        "Firnas runtime error: {}",
+        "خطأ وقت التشغيل: {}";
+    INVALID_NEGATE_OPERAND = "R001",
+        "invalid operand to unary op negate. Expected number, found {} at line {}",
+        "معامل غير صالح للسالب. متوقع رقم، وجد {} في السطر {}";
+    INVALID_ADD_OPERANDS = "R002",
+        "invalid operands of type {} and {} in add expression: both operands must be number or string (line={})",
+        "معاملان غير صالحين من النوعين {} و{} في الجمع: يجب أن يكونا رقمين أو نصين (السطر={})";
+    INVALID_NOT_OPERAND = "R003",
+        "invalid operand in not expression. Expected boolean, found {} at line {}",
+        "معامل غير صالح في النفي. متوقع منطقي، وجد {} في السطر {}";
+    INVALID_GREATER_OPERANDS = "R004",
+        "invalid operands in Greater expression. Expected numbers, strings or tuples, found {} and {} at line {}",
+        "معاملان غير صالحين في المقارنة '>'. متوقع أرقام أو نصوص أو صفوف، وجد {} و{} في السطر {}";
+    INVALID_LESS_OPERANDS = "R005",
+        "invalid operands in Less expression. Expected numbers, strings or tuples, found {} and {} at line {}",
+        "معاملان غير صالحين في المقارنة '<'. متوقع أرقام أو نصوص أو صفوف، وجد {} و{} في السطر {}";
+    UNDEFINED_VARIABLE = "R006",
+        "Undefined variable '{}' at line {}.",
+        "متغير غير معرف '{}' في السطر {}.";
+    UNDEFINED_SETITEM_VARIABLE = "R007",
+        "Use of undefined variable {} in setitem expression at line {}.",
+        "استخدام متغير غير معرف {} في إسناد عنصر في السطر {}.";
+    NO_ATTRIBUTE = "R008",
+        "value {} has no attribute {}.",
+        "القيمة {} ليس لها الخاصية {}.";
+    SUPERCLASS_NOT_A_CLASS = "R009",
+        "Superclass must be a class, found {} at lineno={}",
+        "يجب أن يكون الصنف الأساس صنفا، وجد {} في السطر {}";
+    NO_SUPERCLASS_ATTRIBUTE = "R010",
+        "superclass {} has no attribute {}.",
+        "الصنف الأساس {} ليس له الخاصية {}.";
+    CANNOT_UNPACK = "R011",
+        "Can't unpack value of type {} at line {}",
+        "لا يمكن تفكيك قيمة من النوع {} في السطر {}";
+    UNPACK_COUNT_MISMATCH = "R012",
+        "Expected {} values to unpack, found {} at line {}",
+        "متوقع {} قيم للتفكيك، وجد {} في السطر {}";
+    TUPLE_ITEM_ASSIGNMENT = "R013",
+        "Tuples are immutable, can't assign to an item of a tuple at line {}",
+        "الصفوف غير قابلة للتعديل، لا يمكن الإسناد إلى عنصر صف في السطر {}";
+    INVALID_SUBSCRIPT = "R014",
+        "Invalid subscript of type {} in subscript expression",
+        "فهرس غير صالح من النوع {} في تعبير الفهرسة";
+    INVALID_SUBSCRIPTED_VALUE = "R015",
+        "Invalid value of type {} in subscript expression",
+        "قيمة غير صالحة من النوع {} في تعبير الفهرسة";
+    KEY_NOT_FOUND = "R016",
+        "Key {} not found at line {}",
+        "المفتاح {} غير موجود في السطر {}";
+    INVALID_SLICED_VALUE = "R017",
+        "Invalid value of type {} in slice expression at line {}",
+        "قيمة غير صالحة من النوع {} في تعبير التقطيع في السطر {}";
+    INVALID_SLICE_ASSIGNMENT_TARGET = "R018",
+        "Can't assign to a slice of value of type {} at line {}",
+        "لا يمكن الإسناد إلى شريحة من قيمة من النوع {} في السطر {}";
+    INVALID_SLICE_ASSIGNMENT_VALUE = "R019",
+        "Can't assign value of type {} to a slice at line {}",
+        "لا يمكن إسناد قيمة من النوع {} إلى شريحة في السطر {}";
+    EXTENDED_SLICE_SIZE_MISMATCH = "R020",
+        "Can't assign {} values to an extended slice of size {} at line {}",
+        "لا يمكن إسناد {} قيم إلى شريحة ممتدة حجمها {} في السطر {}";
+    INVALID_SLICE_INDEX = "R021",
+        "Invalid slice index of type {} at line {}",
+        "فهرس تقطيع غير صالح من النوع {} في السطر {}";
+    ZERO_SLICE_STEP = "R022",
+        "Slice step can't be zero at line {}",
+        "لا يمكن أن تكون خطوة التقطيع صفرا في السطر {}";
+    METHOD_ON_NON_INSTANCE = "R023",
+        "Only instances have methods.",
+        "الكائنات فقط لها طرق.";
+    UNDEFINED_PROPERTY = "R024",
+        "Undefined property {}.",
+        "خاصية غير معرفة {}.";
+    CLASS_CTOR_ARITY = "R025",
+        "Call to class ctor expected 0 arguments, got {}.",
+        "استدعاء منشئ الصنف لا يتوقع وسائط، وجد {}.";
+    NOT_CALLABLE = "R026",
+        "attempted to call non-callable value of type {}.",
+        "محاولة استدعاء قيمة غير قابلة للاستدعاء من النوع {}.";
+    NATIVE_ARITY = "R027",
+        "Native function {} expected {} arguments but found {}.",
+        "الدالة المدمجة {} تتوقع {} وسائط لكن وجد {}.";
+    NATIVE_CALL_FAILED = "R028",
+        "When calling {}: {}.",
+        "عند استدعاء {}: {}.";
+    ARITY = "R029",
+        "Expected {} arguments but found {}.",
+        "متوقع {} وسائط لكن وجد {}.";
+    UNHASHABLE = "R030",
+        "Unhashable value of type {} at line {}",
+        "قيمة غير قابلة للتجزئة من النوع {} في السطر {}";
+    INVALID_SET_OPERANDS = "R031",
+        "Expected sets in {} expression, found {} and {} at line {}",
+        "متوقع مجموعات في عملية {}، وجد {} و{} في السطر {}";
+    NUMERIC_ERROR = "R032",
+        "{} (line={})",
+        "{} (السطر={})";
+    INVALID_NUMERIC_OPERANDS = "R033",
+        "Expected numbers in {} expression. Found {} and {} (line={})",
+        "متوقع أرقام في عملية {}. وجد {} و{} (السطر={})";
+    SET_ATTRIBUTE_ON_NON_INSTANCE = "R034",
+        "can't set attribute on value of type {}. Need class instance. val = {}",
+        "لا يمكن تعيين خاصية لقيمة من النوع {}. يلزم كائن. القيمة = {}";
+    GET_ATTRIBUTE_ON_NON_INSTANCE = "R035",
+        "can't get attribute {}  on value of type {}. Need class instance.",
+        "لا يمكن قراءة الخاصية {} لقيمة من النوع {}. يلزم كائن.";
+    LIST_INDEX_OUT_OF_RANGE = "R036",
+        "List subscript index out of range at {}",
+        "فهرس القائمة خارج المدى في السطر {}";
+    READ_ONLY_ATTRIBUTE = "R037",
+        "can't set attribute {} of {}, its attributes are read-only.",
+        "لا يمكن تعيين الخاصية {} للقيمة {}، خصائصها للقراءة فقط.";
+
+    // native functions, reported through NATIVE_CALL_FAILED
+    EXPECTED_NUMBER_ARGUMENT = "R038",
+        "Invalid call: expected number, got {}",
+        "استدعاء غير صالح: متوقع رقم، وجد {}";
+    EXPECTED_INTEGER_ARGUMENT = "R039",
+        "Invalid call: expected integer, got {}",
+        "استدعاء غير صالح: متوقع عدد صحيح، وجد {}";
+    EXPECTED_STRING_ARGUMENT = "R040",
+        "Invalid call: expected string, got {}",
+        "استدعاء غير صالح: متوقع نص، وجد {}";
+    EXPECTED_LIST_ARGUMENT = "R041",
+        "Invalid call: expected list, got {}",
+        "استدعاء غير صالح: متوقع قائمة، وجد {}";
+    DATE_OUT_OF_RANGE = "R042",
+        "Date out of range",
+        "التاريخ خارج المدى";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_fill_placeholders_in_order() {
+        assert_eq!(
+            REDECLARED_VARIABLE.render(Language::English, &[&"x"]),
+            "Redeclaration of variable x in the same scope."
+        );
+        assert_eq!(
+            REDECLARED_VARIABLE.render(Language::Arabic, &[&"س"]),
+            "إعادة تعريف المتغير س في النطاق نفسه."
+        );
+        assert_eq!(
+            EXPECTED_CLASS_BODY.render(Language::English, &[]),
+            "Expected '{' before class body."
+        );
+    }
+
+    #[test]
+    fn it_should_give_each_entry_its_own_code() {
+        let mut codes: Vec<&str> = CATALOGUE.iter().map(|message| message.code).collect();
+        codes.sort();
+        codes.dedup();
+        assert_eq!(codes.len(), CATALOGUE.len());
+    }
+
+    #[test]
+    fn it_should_take_as_many_arguments_in_every_language() {
+        for message in CATALOGUE {
+            assert_eq!(
+                message.english.matches("{}").count(),
+                message.arabic.matches("{}").count(),
+                "{}",
+                message.code
+            );
+        }
+    }
+}
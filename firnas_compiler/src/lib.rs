@@ -1 +1,2 @@
 pub mod compiler;
+pub mod diagnostic;
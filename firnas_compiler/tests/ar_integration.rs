@@ -2,8 +2,12 @@
 
 use firnas_compiler::compiler::Compiler;
 use firnas_compiler::compiler::Error;
+use firnas_compiler::diagnostic::Language;
+use firnas_tokenizer::tokenizer::IdentifierSpelling;
+use firnas_tokenizer::tokenizer::KeywordProfile;
+use firnas_tokenizer::tokenizer::ScanOptions;
 
-fn check_semantic_error(code: &str, f: &dyn Fn(&str) -> ()) {
+fn check_semantic_error(code: &str, f: &dyn Fn(&str)) {
     let func_or_err = Compiler::compile(String::from(code), firnas_ext::Extensions::default());
 
     match func_or_err {
@@ -82,7 +86,7 @@ fn test_this_outside_method_1() {
         r#"
 اطبع_سطر(هذا)؛
     "#,
-        &|err: &str| assert!(err.starts_with("Cannot use 'this' outside of class")),
+        &|err: &str| assert!(err.starts_with("لا يمكن استخدام 'هذا' خارج صنف")),
     )
 }
 
@@ -92,7 +96,7 @@ fn test_this_outside_method_2() {
     دالة فوو() { اطبع_سطر(هذا)؛ }
     "#;
     check_semantic_error(code, &|err: &str| {
-        assert!(err.starts_with("Cannot use 'this' outside of class"))
+        assert!(err.starts_with("لا يمكن استخدام 'هذا' خارج صنف"))
     })
 }
 
@@ -100,7 +104,7 @@ fn test_this_outside_method_2() {
 fn test_self_ineritance_is_error() {
     let code = "صنف ط < ط { }؛";
     check_semantic_error(code, &|err: &str| {
-        assert!(err.starts_with("A class cannot inherit from itself."))
+        assert!(err.starts_with("لا يمكن لصنف أن يرث من نفسه."))
     })
 }
 
@@ -108,7 +112,7 @@ fn test_self_ineritance_is_error() {
 fn test_cant_use_super_outside_class() {
     let code = "دالة د() { اساس.بار()؛ }؛";
     check_semantic_error(code, &|err: &str| {
-        assert!(err.starts_with("Can't use 'super' outside of a class"))
+        assert!(err.starts_with("لا يمكن استخدام 'اساس' خارج صنف"))
     })
 }
 
@@ -116,7 +120,7 @@ fn test_cant_use_super_outside_class() {
 fn test_cant_use_super_in_class_with_no_superclass() {
     let code = "صنف فوو { بار() { اساس.بار() } }؛";
     check_semantic_error(code, &|err: &str| {
-        assert!(err.starts_with("Can't use 'super' in a class with no superclass"))
+        assert!(err.starts_with("لا يمكن استخدام 'اساس' في صنف ليس له صنف أساس"))
     })
 }
 
@@ -134,7 +138,7 @@ fn test_setitem_illegal_target_globals() {
     );
 
     match func_or_err {
-        Err(Error::Semantic(err)) => assert!(err.what.starts_with("Invalid assignment target")),
+        Err(Error::Semantic(err)) => assert!(err.what.starts_with("هدف إسناد غير صالح")),
         _ => panic!("expected semantic error"),
     }
 }
@@ -155,7 +159,7 @@ fn test_setitem_illegal_target_locals() {
     );
 
     match func_or_err {
-        Err(Error::Semantic(err)) => assert!(err.what.starts_with("Invalid assignment target")),
+        Err(Error::Semantic(err)) => assert!(err.what.starts_with("هدف إسناد غير صالح")),
         _ => panic!("expected semantic error"),
     }
 }
@@ -175,7 +179,84 @@ fn test_redeclaration_of_locals_is_error() {
     );
 
     match func_or_err {
-        Err(Error::Semantic(err)) => assert!(err.what.starts_with("Redeclaration of variable")),
+        Err(Error::Semantic(err)) => assert!(err.what.starts_with("إعادة تعريف المتغير س")),
         _ => panic!("expected semantic error"),
     }
 }
+
+#[test]
+fn test_errors_in_english() {
+    let func_or_err = Compiler::compile_with_language(
+        String::from("اطبع_سطر(هذا)؛"),
+        firnas_ext::Extensions::default(),
+        Language::English,
+    );
+
+    match func_or_err {
+        Err(Error::Semantic(err)) => {
+            assert_eq!(err.code, "C031");
+            assert_eq!(err.what, "Cannot use 'this' outside of class.");
+        }
+        _ => panic!("expected semantic error"),
+    }
+}
+
+#[test]
+fn test_expected_token_names_arabic_spelling() {
+    let func_or_err = Compiler::compile(
+        String::from("اطبع_سطر(١)"),
+        firnas_ext::Extensions::default(),
+    );
+
+    match func_or_err {
+        Err(Error::Parse(err)) => {
+            assert_eq!(err.code, "C023");
+            assert_eq!(
+                err.what,
+                "متوقع الرمز '؛' لكن وجد الرمز نهاية الملف: متوقع '؛' بعد التعبير."
+            );
+        }
+        _ => panic!("expected parse error"),
+    }
+}
+
+#[test]
+fn test_compiles_with_keyword_profile() {
+    let code = "متغير س = ١؛ إذا (س) { اطبع_سطر(س)؛ } وإلا { اطبع_سطر(٠)؛ }";
+    let options = ScanOptions {
+        keywords: KeywordProfile::builtin("تعليمي").unwrap(),
+        ..ScanOptions::default()
+    };
+    Compiler::compile_with_options(
+        String::from(code),
+        firnas_ext::Extensions::default(),
+        Language::default(),
+        options,
+    )
+    .unwrap();
+
+    assert!(Compiler::compile(String::from(code), firnas_ext::Extensions::default()).is_err());
+}
+
+#[test]
+fn test_compile_with_options_returns_spelling_warnings() {
+    let code = "دع مدرسة = ١؛\nدع مدرسه = ٢؛";
+    let options = ScanOptions {
+        spelling: IdentifierSpelling::Warn,
+        ..ScanOptions::default()
+    };
+    let (_, warnings) = Compiler::compile_with_options(
+        String::from(code),
+        firnas_ext::Extensions::default(),
+        Language::default(),
+        options,
+    )
+    .unwrap();
+
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].line, 2);
+    assert_eq!(
+        warnings[0].what,
+        "'مدرسه' is spelled like 'مدرسة' but is a different name"
+    );
+}
@@ -3,7 +3,7 @@
 use firnas_compiler::compiler::Compiler;
 use firnas_compiler::compiler::Error;
 
-fn check_semantic_error(code: &str, f: &dyn Fn(&str) -> ()) {
+fn check_semantic_error(code: &str, f: &dyn Fn(&str)) {
     let func_or_err = Compiler::compile(String::from(code), firnas_ext::Extensions::default());
 
     match func_or_err {
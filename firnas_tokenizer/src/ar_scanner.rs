@@ -1,21 +1,100 @@
 use crate::error::Error;
+use crate::error::Warning;
+use crate::keyword_profile::canonical_keyword;
+use crate::keyword_profile::KeywordProfile;
+use crate::keyword_profile::KEYWORDS;
 use crate::token::Literal;
 use crate::token::Token;
 use crate::token::TokenType;
 use arabic_utils::arabic_char::ArabicChar;
+use arabic_utils::arabic_consts;
+use arabic_utils::arabic_digits::digit_value;
+use arabic_utils::arabic_digits::DigitSet;
 use arabic_utils::arabic_str::ArabicStr;
 use std::collections::HashMap;
 use unicode_segmentation::UnicodeSegmentation;
 
+/*
+Hamza forms of alef, taa marbuta and alef maqsura are easy to mix up when typing, so by default
+identifiers and keywords are compared after folding those variants together (see
+`ArabicStr::normalize_arabic`): `أساس` and `اساس` are the same keyword, and `مدرسة` and `مدرسه`
+the same variable. Tashkeel and tatweel are always ignored, so vocalised names like `عَدَد` can
+be written for beginners.
+ */
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IdentifierSpelling {
+    /// Identifiers are normalized, so different spellings of a name are one name.
+    #[default]
+    Normalize,
+    /// Identifiers are kept as written, apart from tashkeel and tatweel, and every spelling
+    /// of a name that differs from an earlier one is reported as a warning. Keywords are
+    /// still normalized.
+    Warn,
+}
+
+/// How `scan_tokens_with` reads names and numbers.
+#[derive(Debug, Clone)]
+pub struct ScanOptions {
+    pub spelling: IdentifierSpelling,
+    /// The digits numeric literals may be written with. All of them are accepted by default,
+    /// so sources typed on Persian or western keyboards scan as well.
+    pub digits: Vec<DigitSet>,
+    /// Other spellings accepted for keywords.
+    pub keywords: KeywordProfile,
+}
+
+impl Default for ScanOptions {
+    fn default() -> ScanOptions {
+        ScanOptions {
+            spelling: IdentifierSpelling::default(),
+            digits: DigitSet::ALL.to_vec(),
+            keywords: KeywordProfile::default(),
+        }
+    }
+}
+
+pub struct Scanned {
+    pub tokens: Vec<Token>,
+    pub warnings: Vec<Warning>,
+}
+
 pub fn scan_tokens(input: String) -> Result<Vec<Token>, Error> {
-    let mut scanner: Scanner = Default::default();
+    scan_tokens_with(input, ScanOptions::default()).map(|scanned| scanned.tokens)
+}
+
+pub fn scan_tokens_with(input: String, options: ScanOptions) -> Result<Scanned, Error> {
+    let mut scanner = Scanner::with_options(options);
 
     scanner.scan_tokens(input);
 
     match scanner.err {
         Some(err) => Err(err),
-        None => Ok(scanner.tokens),
+        None => Ok(Scanned {
+            tokens: scanner.tokens,
+            warnings: scanner.warnings,
+        }),
+    }
+}
+
+/// `input` with the keywords spelled as synonyms of `options.keywords` spelled canonically
+/// instead, and everything else as it was.
+pub fn canonicalize(input: String, options: ScanOptions) -> Result<String, Error> {
+    let mut scanner = Scanner::with_options(options);
+
+    scanner.scan_tokens(input);
+
+    if let Some(err) = scanner.err {
+        return Err(err);
+    }
+    let mut res = String::new();
+    let mut pos = 0;
+    for (start, end, ty) in scanner.synonyms {
+        res.extend(scanner.source[pos..start].iter().map(String::as_str));
+        res.push_str(canonical_keyword(ty).unwrap_or_default());
+        pos = end;
     }
+    res.extend(scanner.source[pos..].iter().map(String::as_str));
+    Ok(res)
 }
 
 struct Scanner {
@@ -27,6 +106,13 @@ struct Scanner {
     line: usize,
     col: i64,
     keywords: HashMap<String, TokenType>,
+    spelling: IdentifierSpelling,
+    digits: Vec<DigitSet>,
+    /// The first spelling seen of each normalized identifier.
+    spellings: HashMap<String, String>,
+    warnings: Vec<Warning>,
+    /// Where keywords are spelled as synonyms, by their start and end in `source`.
+    synonyms: Vec<(usize, usize, TokenType)>,
 }
 
 impl Default for Scanner {
@@ -39,36 +125,32 @@ impl Default for Scanner {
             current: 0,
             line: 1,
             col: -1,
-            keywords: vec![
-                ("و", TokenType::And),
-                ("صنف", TokenType::Class),
-                ("غير_ذلك", TokenType::Else),
-                ("خطا", TokenType::False),
-                ("خطأ", TokenType::False),
-                ("من", TokenType::For),
-                ("دالة", TokenType::Fun),
-                ("اذا_كان", TokenType::If),
-                ("إذا_كان", TokenType::If),
-                ("عدم", TokenType::Nil),
-                ("او", TokenType::Or),
-                ("أو", TokenType::Or),
-                ("رد", TokenType::Return),
-                ("اساس", TokenType::Super),
-                ("أساس", TokenType::Super),
-                ("هذا", TokenType::This),
-                ("صح", TokenType::True),
-                ("دع", TokenType::Var),
-                ("طالما", TokenType::While),
-                ("لامدا", TokenType::Lambda),
-            ]
-            .into_iter()
-            .map(|(k, v)| (String::from(k), v))
-            .collect(),
+            keywords: KEYWORDS
+                .iter()
+                .map(|(k, v)| (k.normalize_arabic(), *v))
+                .collect(),
+            spelling: Default::default(),
+            digits: DigitSet::ALL.to_vec(),
+            spellings: HashMap::new(),
+            warnings: Vec::new(),
+            synonyms: Vec::new(),
         }
     }
 }
 
 impl Scanner {
+    fn with_options(options: ScanOptions) -> Scanner {
+        let mut scanner = Scanner {
+            spelling: options.spelling,
+            digits: options.digits,
+            ..Default::default()
+        };
+        for (spelling, ty) in options.keywords.synonyms() {
+            scanner.keywords.insert(spelling.normalize_arabic(), *ty);
+        }
+        scanner
+    }
+
     fn scan_tokens(&mut self, input: String) {
         self.source = input
             .graphemes(true)
@@ -116,6 +198,9 @@ impl Scanner {
             "+" => self.add_token(TokenType::Plus),
             ";" | "\u{061B}" => self.add_token(TokenType::Semicolon),
             "*" => self.add_token(TokenType::Star),
+            "|" => self.add_token(TokenType::Pipe),
+            "&" => self.add_token(TokenType::Ampersand),
+            ":" => self.add_token(TokenType::Colon),
             "!" => {
                 let matches_eq = self.matches("=");
                 self.add_token(if matches_eq {
@@ -160,11 +245,11 @@ impl Scanner {
             " " | "\r" | "\t" => {}
             "\n" => {
                 self.line += 1;
-                self.col = 0
+                self.col = -1
             }
             "\"" => self.string(),
             _ => {
-                if Scanner::is_decimal_digit(c) {
+                if self.is_decimal_digit(c) {
                     self.number()
                 } else if Scanner::is_alpha(c) {
                     self.identifier()
@@ -179,20 +264,33 @@ impl Scanner {
         }
     }
 
+    /// A letter, possibly carrying tashkeel.
     fn is_alpha(c: &str) -> bool {
-        c.chars().all(char::is_arabic_alphabetic)
+        let mut chars = c.chars();
+        chars.next().is_some_and(char::is_arabic_alphabetic)
+            && chars.all(|c| c.is_arabic_diacritic())
     }
 
-    fn is_decimal_digit(c: &str) -> bool {
-        c.is_arabic_number()
+    /// Tatweel only stretches letters, so it can't start an identifier.
+    fn is_tatweel(c: &str) -> bool {
+        let mut chars = c.chars();
+        chars.next().is_some_and(|c| c.is_tatweel()) && chars.all(|c| c.is_arabic_diacritic())
     }
 
-    fn is_alphanumeric(c: &str) -> bool {
-        Scanner::is_alpha(c) || Scanner::is_decimal_digit(c) || c == "_"
+    fn is_decimal_digit(&self, c: &str) -> bool {
+        let mut chars = c.chars();
+        chars
+            .next()
+            .is_some_and(|c| digit_value(c, &self.digits).is_some())
+            && chars.next().is_none()
+    }
+
+    fn is_alphanumeric(&self, c: &str) -> bool {
+        Scanner::is_alpha(c) || Scanner::is_tatweel(c) || self.is_decimal_digit(c) || c == "_"
     }
 
     fn identifier(&mut self) {
-        while Scanner::is_alphanumeric(&self.peek()) {
+        while self.is_alphanumeric(&self.peek()) {
             self.advance();
         }
 
@@ -200,32 +298,71 @@ impl Scanner {
             .iter()
             .fold(String::new(), |cur, nxt| cur + nxt);
 
-        let token_type = match self.keywords.get(&literal_val) {
+        let normalized = literal_val.normalize_arabic();
+
+        let token_type = match self.keywords.get(&normalized) {
             Some(kw_token_type) => *kw_token_type,
             None => TokenType::Identifier,
         };
+        let is_synonym = canonical_keyword(token_type)
+            .is_some_and(|keyword| keyword.normalize_arabic() != normalized);
+        if is_synonym {
+            self.synonyms.push((self.start, self.current, token_type));
+        }
 
         match token_type {
-            TokenType::Identifier => self.add_token_literal(
-                TokenType::Identifier,
-                Some(Literal::Identifier(literal_val)),
-            ),
+            TokenType::Identifier => {
+                // tashkeel and tatweel never make a different name
+                let name = match self.spelling {
+                    IdentifierSpelling::Normalize => normalized,
+                    IdentifierSpelling::Warn => {
+                        let stripped = literal_val.strip_tashkeel();
+                        self.check_spelling(&stripped, normalized);
+                        stripped
+                    }
+                };
+                self.add_token_literal(TokenType::Identifier, Some(Literal::Identifier(name)))
+            }
             _ => self.add_token(token_type),
         }
     }
 
+    fn check_spelling(&mut self, spelled: &str, normalized: String) {
+        let first = self
+            .spellings
+            .entry(normalized)
+            .or_insert_with(|| spelled.to_string());
+        if first != spelled {
+            self.warnings.push(Warning {
+                what: format!(
+                    "'{}' is spelled like '{}' but is a different name",
+                    spelled, first
+                ),
+                line: self.line,
+                col: self.col,
+            });
+        }
+    }
+
     fn number(&mut self) {
-        while Scanner::is_decimal_digit(&self.peek()) {
+        let thousands_sep = arabic_consts::THOUSANDS_SEPARATOR.to_string();
+        while self.is_decimal_digit(&self.peek())
+            || (self.peek() == thousands_sep && self.is_decimal_digit(&self.peek_next()))
+        {
             self.advance();
         }
 
-        let decimal_sep = arabic_utils::arabic_consts::DECIMAL_SEPARATOR.to_string();
+        // `.` too, for numbers pasted from western sources
+        let is_decimal_sep =
+            |c: &str| c == arabic_consts::DECIMAL_SEPARATOR.to_string() || c == ".";
 
-        if self.peek() == decimal_sep && Scanner::is_decimal_digit(&self.peek_next()) {
+        let mut is_float = false;
+        if is_decimal_sep(&self.peek()) && self.is_decimal_digit(&self.peek_next()) {
+            is_float = true;
             self.advance();
         }
 
-        while Scanner::is_decimal_digit(&self.peek()) {
+        while self.is_decimal_digit(&self.peek()) {
             self.advance();
         }
 
@@ -233,9 +370,29 @@ impl Scanner {
             .iter()
             .fold(String::new(), |cur, nxt| cur + nxt);
 
-        let val: f64 = val.parse_arabic_decimal().unwrap().parse().unwrap();
+        let val = val.parse_decimal_in(&self.digits).unwrap();
+
+        // `ن` as in عدد نسبي
+        if self.peek() == "ن" && !self.is_alphanumeric(&self.peek_next()) {
+            self.advance();
+            self.add_token_literal(TokenType::Number, Some(Literal::Rational(val)));
+            return;
+        }
+
+        self.add_token_literal(
+            TokenType::Number,
+            Some(Scanner::number_literal(val, is_float)),
+        )
+    }
 
-        self.add_token_literal(TokenType::Number, Some(Literal::Number(val)))
+    fn number_literal(val: String, is_float: bool) -> Literal {
+        if is_float {
+            return Literal::Number(val.parse().unwrap());
+        }
+        match val.parse::<i64>() {
+            Ok(int) => Literal::Integer(int),
+            Err(_) => Literal::BigInteger(val),
+        }
     }
 
     fn string(&mut self) {
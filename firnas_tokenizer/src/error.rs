@@ -4,3 +4,11 @@ pub struct Error {
     pub line: usize,
     pub col: i64,
 }
+
+/// Something suspicious in the source that still scans, e.g. two spellings of one name.
+#[derive(Debug, PartialEq)]
+pub struct Warning {
+    pub what: String,
+    pub line: usize,
+    pub col: i64,
+}
@@ -0,0 +1,157 @@
+use crate::error::Error;
+use crate::token::TokenType;
+use arabic_utils::arabic_char::ArabicChar;
+use arabic_utils::arabic_str::ArabicStr;
+
+/*
+The keywords of the Arabic dialect are spelled as in `KEYWORDS`. Teaching materials don't all use
+the same words, so a keyword profile adds other spellings for them, e.g. `متغير` for `دع` and
+`إذا` for `اذا_كان`. A synonym scans to the same token as the keyword it stands for, and
+`canonicalize` rewrites a source to the canonical spellings, which is the form other tools read
+without knowing the profile.
+
+Besides the built-in profiles, a profile can be loaded from a file with one synonym a line:
+
+    # المنهج الجديد
+    متغير = دع
+    إذا = اذا_كان
+
+Like every keyword, synonyms are compared after normalization, so `إذا` and `اذا` are one synonym.
+ */
+
+/// The canonical spelling of every keyword.
+pub const KEYWORDS: &[(&str, TokenType)] = &[
+    ("و", TokenType::And),
+    ("صنف", TokenType::Class),
+    ("غير_ذلك", TokenType::Else),
+    ("خطا", TokenType::False),
+    ("من", TokenType::For),
+    ("دالة", TokenType::Fun),
+    ("اذا_كان", TokenType::If),
+    ("عدم", TokenType::Nil),
+    ("او", TokenType::Or),
+    ("رد", TokenType::Return),
+    ("اساس", TokenType::Super),
+    ("هذا", TokenType::This),
+    ("صح", TokenType::True),
+    ("دع", TokenType::Var),
+    ("طالما", TokenType::While),
+    ("لامدا", TokenType::Lambda),
+];
+
+/// The canonical spelling of the keyword `ty`, if it is one.
+pub fn canonical_keyword(ty: TokenType) -> Option<&'static str> {
+    KEYWORDS
+        .iter()
+        .find(|(_, keyword)| *keyword == ty)
+        .map(|(spelling, _)| *spelling)
+}
+
+fn keyword_of(spelling: &str) -> Option<TokenType> {
+    let normalized = spelling.normalize_arabic();
+    KEYWORDS
+        .iter()
+        .find(|(keyword, _)| keyword.normalize_arabic() == normalized)
+        .map(|(_, ty)| *ty)
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KeywordProfile {
+    synonyms: Vec<(String, TokenType)>,
+}
+
+impl KeywordProfile {
+    /// The names of the built-in profiles.
+    pub const BUILTIN: &'static [&'static str] = &["قياسي", "تعليمي"];
+
+    /// The built-in profile called `name`: `قياسي` (`standard`), the canonical keywords only,
+    /// or `تعليمي` (`teaching`), the words common in school curricula.
+    pub fn builtin(name: &str) -> Option<KeywordProfile> {
+        match name.normalize_arabic().as_str() {
+            "قياسي" | "standard" => Some(KeywordProfile::default()),
+            "تعليمي" | "teaching" => Some(
+                KeywordProfile::default()
+                    .with_synonym("متغير", TokenType::Var)
+                    .with_synonym("إذا", TokenType::If)
+                    .with_synonym("وإلا", TokenType::Else)
+                    .with_synonym("بينما", TokenType::While)
+                    .with_synonym("ارجع", TokenType::Return)
+                    .with_synonym("صواب", TokenType::True)
+                    .with_synonym("فئة", TokenType::Class),
+            ),
+            _ => None,
+        }
+    }
+
+    pub fn with_synonym(mut self, spelling: &str, ty: TokenType) -> KeywordProfile {
+        self.synonyms.push((spelling.to_string(), ty));
+        self
+    }
+
+    pub fn synonyms(&self) -> &[(String, TokenType)] {
+        &self.synonyms
+    }
+
+    /// Reads a profile file. Every synonym must stand for a canonical keyword, and can't be
+    /// a keyword already or a synonym of another one.
+    pub fn parse(text: &str) -> Result<KeywordProfile, Error> {
+        let mut profile = KeywordProfile::default();
+        for (idx, line) in text.lines().enumerate() {
+            let error = |what: String| Error {
+                what,
+                line: idx + 1,
+                col: 0,
+            };
+
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((spelling, keyword)) = line.split_once('=') else {
+                return Err(error(format!(
+                    "Expected 'synonym = keyword', found '{}'",
+                    line
+                )));
+            };
+            let (spelling, keyword) = (spelling.trim(), keyword.trim());
+
+            // spelled so the Arabic scanner reads it as one word
+            let is_word = |word: &str| {
+                let mut chars = word.chars();
+                chars.next().is_some_and(char::is_arabic_alphabetic)
+                    && chars.all(|c| {
+                        c.is_arabic_alphabetic()
+                            || c == '_'
+                            || c.is_tatweel()
+                            || c.is_arabic_diacritic()
+                    })
+            };
+            if !is_word(spelling) {
+                return Err(error(format!("'{}' can't be a keyword", spelling)));
+            }
+            let Some(ty) = keyword_of(keyword) else {
+                return Err(error(format!("'{}' is not a keyword", keyword)));
+            };
+            if keyword_of(spelling).is_some() {
+                return Err(error(format!("'{}' is a keyword already", spelling)));
+            }
+            let normalized = spelling.normalize_arabic();
+            let taken = profile
+                .synonyms
+                .iter()
+                .find(|(other, _)| other.normalize_arabic() == normalized);
+            match taken {
+                Some((_, other_ty)) if *other_ty != ty => {
+                    return Err(error(format!(
+                        "'{}' is a synonym of '{}' already",
+                        spelling,
+                        canonical_keyword(*other_ty).unwrap_or_default()
+                    )));
+                }
+                Some(_) => {}
+                None => profile.synonyms.push((spelling.to_string(), ty)),
+            }
+        }
+        Ok(profile)
+    }
+}
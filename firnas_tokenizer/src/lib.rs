@@ -3,6 +3,8 @@ pub mod scanner;
 
 #[cfg(feature = "ar")]
 pub mod ar_scanner;
+#[cfg(feature = "ar")]
+pub mod keyword_profile;
 
 pub mod error;
 pub mod token;
@@ -11,6 +13,20 @@ pub mod tokenizer {
     #[cfg(feature = "en")]
     pub use super::scanner::scan_tokens;
 
+    #[cfg(feature = "ar")]
+    pub use super::ar_scanner::canonicalize;
     #[cfg(feature = "ar")]
     pub use super::ar_scanner::scan_tokens;
+    #[cfg(feature = "ar")]
+    pub use super::ar_scanner::scan_tokens_with;
+    #[cfg(feature = "ar")]
+    pub use super::ar_scanner::IdentifierSpelling;
+    #[cfg(feature = "ar")]
+    pub use super::ar_scanner::ScanOptions;
+    #[cfg(feature = "ar")]
+    pub use super::ar_scanner::Scanned;
+    #[cfg(feature = "ar")]
+    pub use super::keyword_profile::KeywordProfile;
+    #[cfg(feature = "ar")]
+    pub use arabic_utils::arabic_digits::DigitSet;
 }
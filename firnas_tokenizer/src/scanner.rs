@@ -3,6 +3,7 @@ use crate::token::Literal;
 use crate::token::Token;
 use crate::token::TokenType;
 use std::collections::HashMap;
+use unicode_segmentation::UnicodeSegmentation;
 
 pub fn scan_tokens(input: String) -> Result<Vec<Token>, Error> {
     let mut scanner: Scanner = Default::default();
@@ -17,6 +18,9 @@ pub fn scan_tokens(input: String) -> Result<Vec<Token>, Error> {
 
 struct Scanner {
     source: Vec<u8>,
+    /// Whether each byte of `source` starts a grapheme cluster, so that `col` counts clusters
+    /// like the Arabic scanner's does.
+    cluster_starts: Vec<bool>,
     tokens: Vec<Token>,
     err: Option<Error>,
     start: usize,
@@ -30,6 +34,7 @@ impl Default for Scanner {
     fn default() -> Scanner {
         Scanner {
             source: Vec::new(),
+            cluster_starts: Vec::new(),
             tokens: Vec::new(),
             err: None,
             start: 0,
@@ -63,6 +68,10 @@ impl Default for Scanner {
 
 impl Scanner {
     fn scan_tokens(&mut self, input: String) {
+        self.cluster_starts = vec![false; input.len()];
+        for (i, _) in input.grapheme_indices(true) {
+            self.cluster_starts[i] = true;
+        }
         self.source = input.into_bytes();
 
         while !self.done() {
@@ -83,8 +92,7 @@ impl Scanner {
     }
 
     fn advance(&mut self) -> char {
-        self.current += 1;
-        self.col += 1;
+        self.step();
 
         char::from(self.source[self.current - 1])
     }
@@ -105,6 +113,9 @@ impl Scanner {
             '+' => self.add_token(TokenType::Plus),
             ';' => self.add_token(TokenType::Semicolon),
             '*' => self.add_token(TokenType::Star),
+            '|' => self.add_token(TokenType::Pipe),
+            '&' => self.add_token(TokenType::Ampersand),
+            ':' => self.add_token(TokenType::Colon),
             '!' => {
                 let matches_eq = self.matches('=');
                 self.add_token(if matches_eq {
@@ -149,7 +160,7 @@ impl Scanner {
             ' ' | '\r' | '\t' => {}
             '\n' => {
                 self.line += 1;
-                self.col = 0
+                self.col = -1
             }
             '"' => self.string(),
             _ => {
@@ -207,7 +218,9 @@ impl Scanner {
             self.advance();
         }
 
+        let mut is_float = false;
         if self.peek() == '.' && Scanner::is_decimal_digit(self.peek_next()) {
+            is_float = true;
             self.advance();
         }
 
@@ -215,12 +228,28 @@ impl Scanner {
             self.advance();
         }
 
-        let val: f64 = String::from_utf8(self.source[self.start..self.current].to_vec())
-            .unwrap()
-            .parse()
-            .unwrap();
+        let val = String::from_utf8(self.source[self.start..self.current].to_vec()).unwrap();
+
+        if self.peek() == 'r' && !Scanner::is_alphanumeric(self.peek_next()) {
+            self.advance();
+            self.add_token_literal(TokenType::Number, Some(Literal::Rational(val)));
+            return;
+        }
+
+        self.add_token_literal(
+            TokenType::Number,
+            Some(Scanner::number_literal(val, is_float)),
+        )
+    }
 
-        self.add_token_literal(TokenType::Number, Some(Literal::Number(val)))
+    fn number_literal(val: String, is_float: bool) -> Literal {
+        if is_float {
+            return Literal::Number(val.parse().unwrap());
+        }
+        match val.parse::<i64>() {
+            Ok(int) => Literal::Integer(int),
+            Err(_) => Literal::BigInteger(val),
+        }
     }
 
     fn string(&mut self) {
@@ -276,11 +305,17 @@ impl Scanner {
             return false;
         }
 
-        self.current += 1;
-        self.col += 1;
+        self.step();
         true
     }
 
+    fn step(&mut self) {
+        if self.cluster_starts[self.current] {
+            self.col += 1;
+        }
+        self.current += 1;
+    }
+
     fn add_token(&mut self, token_type: TokenType) {
         self.add_token_literal(token_type, None)
     }
@@ -5,6 +5,11 @@ pub enum Literal {
     Identifier(String),
     Str(String),
     Number(f64),
+    Integer(i64),
+    /// Decimal digits of an integer literal that does not fit in an i64
+    BigInteger(String),
+    /// Decimal text of an exact rational literal such as `0.1r`
+    Rational(String),
 }
 
 #[derive(Clone)]
@@ -13,6 +18,8 @@ pub struct Token {
     pub lexeme: String,
     pub literal: Option<Literal>,
     pub line: usize,
+    /// Where the token's last character is on its line, counting from zero. The Arabic dialect
+    /// counts graphemes, the english one bytes.
     pub col: i64,
 }
 
@@ -48,6 +55,9 @@ pub enum TokenType {
     Semicolon,
     Slash,
     Star,
+    Pipe,
+    Ampersand,
+    Colon,
 
     // One or two character tokens.
     Bang,
@@ -3,12 +3,18 @@
 use firnas_tokenizer::token::Literal;
 use firnas_tokenizer::token::Token;
 use firnas_tokenizer::token::TokenType;
+use firnas_tokenizer::tokenizer::canonicalize;
 use firnas_tokenizer::tokenizer::scan_tokens;
+use firnas_tokenizer::tokenizer::scan_tokens_with;
+use firnas_tokenizer::tokenizer::DigitSet;
+use firnas_tokenizer::tokenizer::IdentifierSpelling;
+use firnas_tokenizer::tokenizer::KeywordProfile;
+use firnas_tokenizer::tokenizer::ScanOptions;
 
 #[test]
 fn it_should_tokenize_single_char_token() {
     assert_eq!(
-        scan_tokens("( ) [ ] { } , . - + ; \\ *".to_owned()).unwrap(),
+        scan_tokens("( ) [ ] { } , . - + ; \\ * | & :".to_owned()).unwrap(),
         vec![
             Token {
                 ty: TokenType::LeftRoundBracket,
@@ -101,6 +107,27 @@ fn it_should_tokenize_single_char_token() {
                 line: 1,
                 col: 0
             },
+            Token {
+                ty: TokenType::Pipe,
+                lexeme: String::from("|"),
+                literal: None,
+                line: 1,
+                col: 0
+            },
+            Token {
+                ty: TokenType::Ampersand,
+                lexeme: String::from("&"),
+                literal: None,
+                line: 1,
+                col: 0
+            },
+            Token {
+                ty: TokenType::Colon,
+                lexeme: String::from(":"),
+                literal: None,
+                line: 1,
+                col: 0
+            },
             Token {
                 ty: TokenType::Eof,
                 lexeme: String::new(),
@@ -185,6 +212,7 @@ fn it_should_tokenize_one_or_two_char() {
 }
 
 #[test]
+#[allow(clippy::approx_constant)]
 fn it_should_tokenize_literals() {
     assert_eq!(
         scan_tokens("فرناس \"شئ\" ٣٫١٤".to_owned()).unwrap(),
@@ -221,6 +249,66 @@ fn it_should_tokenize_literals() {
     );
 }
 
+#[test]
+fn it_should_tokenize_integer_and_float_literals() {
+    assert_eq!(
+        scan_tokens("٣ ٣٫٠".to_owned()).unwrap(),
+        vec![
+            Token {
+                ty: TokenType::Number,
+                lexeme: String::from("٣"),
+                literal: Some(Literal::Integer(3)),
+                line: 1,
+                col: 0
+            },
+            Token {
+                ty: TokenType::Number,
+                lexeme: String::from("٣٫٠"),
+                literal: Some(Literal::Number(3.0)),
+                line: 1,
+                col: 0
+            },
+            Token {
+                ty: TokenType::Eof,
+                lexeme: String::from(""),
+                literal: None,
+                line: 1,
+                col: 0
+            }
+        ]
+    );
+}
+
+#[test]
+fn it_should_tokenize_big_integer_and_rational_literals() {
+    assert_eq!(
+        scan_tokens("٩٢٢٣٣٧٢٠٣٦٨٥٤٧٧٥٨٠٧٠ ٠٫٢٥ن".to_owned()).unwrap(),
+        vec![
+            Token {
+                ty: TokenType::Number,
+                lexeme: String::from("٩٢٢٣٣٧٢٠٣٦٨٥٤٧٧٥٨٠٧٠"),
+                literal: Some(Literal::BigInteger(String::from("92233720368547758070"))),
+                line: 1,
+                col: 0
+            },
+            Token {
+                ty: TokenType::Number,
+                lexeme: String::from("٠٫٢٥ن"),
+                literal: Some(Literal::Rational(String::from("0.25"))),
+                line: 1,
+                col: 0
+            },
+            Token {
+                ty: TokenType::Eof,
+                lexeme: String::from(""),
+                literal: None,
+                line: 1,
+                col: 0
+            }
+        ]
+    );
+}
+
 #[test]
 fn it_should_tokenize_keywords() {
     assert_eq!(
@@ -351,3 +439,290 @@ fn it_should_tokenize_keywords() {
         ]
     );
 }
+
+fn literals(tokens: &[Token]) -> Vec<(TokenType, Option<Literal>)> {
+    tokens
+        .iter()
+        .map(|token| (token.ty, token.literal.clone()))
+        .collect()
+}
+
+#[test]
+fn it_should_normalize_keyword_spellings() {
+    assert_eq!(
+        literals(&scan_tokens("أو او إذا_كان أساس خطأ دالة داله".to_owned()).unwrap()),
+        vec![
+            (TokenType::Or, None),
+            (TokenType::Or, None),
+            (TokenType::If, None),
+            (TokenType::Super, None),
+            (TokenType::False, None),
+            (TokenType::Fun, None),
+            (TokenType::Fun, None),
+            (TokenType::Eof, None),
+        ]
+    );
+}
+
+#[test]
+fn it_should_normalize_identifier_spellings() {
+    let identifier = |name: &str| {
+        (
+            TokenType::Identifier,
+            Some(Literal::Identifier(String::from(name))),
+        )
+    };
+    assert_eq!(
+        literals(&scan_tokens("مدرسة مدرسه أحمد احمد مستشفى".to_owned()).unwrap()),
+        vec![
+            identifier("مدرسه"),
+            identifier("مدرسه"),
+            identifier("احمد"),
+            identifier("احمد"),
+            identifier("مستشفي"),
+            (TokenType::Eof, None),
+        ]
+    );
+}
+
+fn warn_on_spelling() -> ScanOptions {
+    ScanOptions {
+        spelling: IdentifierSpelling::Warn,
+        ..ScanOptions::default()
+    }
+}
+
+#[test]
+fn it_should_warn_on_near_duplicate_identifiers() {
+    let scanned = scan_tokens_with(
+        "دع مدرسة = ١؛\nاطبع_سطر(مدرسه)؛\nاطبع_سطر(مدرسة)؛".to_owned(),
+        warn_on_spelling(),
+    )
+    .unwrap();
+
+    let names: Vec<_> = scanned
+        .tokens
+        .iter()
+        .filter_map(|token| match &token.literal {
+            Some(Literal::Identifier(name)) => Some(name.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        names,
+        vec!["مدرسة", "اطبع_سطر", "مدرسه", "اطبع_سطر", "مدرسة"]
+    );
+
+    assert_eq!(scanned.warnings.len(), 1);
+    assert_eq!(
+        scanned.warnings[0].what,
+        "'مدرسه' is spelled like 'مدرسة' but is a different name"
+    );
+    assert_eq!(scanned.warnings[0].line, 2);
+}
+
+#[test]
+fn it_should_ignore_tashkeel_and_tatweel_in_identifiers() {
+    let identifier = |name: &str| {
+        (
+            TokenType::Identifier,
+            Some(Literal::Identifier(String::from(name))),
+        )
+    };
+    assert_eq!(
+        literals(&scan_tokens("دَعْ عَدَدٌ مـحـمـد مُحَمَّد_٢".to_owned()).unwrap()),
+        vec![
+            (TokenType::Var, None),
+            identifier("عدد"),
+            identifier("محمد"),
+            identifier("محمد_٢"),
+            (TokenType::Eof, None),
+        ]
+    );
+
+    let scanned = scan_tokens_with("عَدَد عدد".to_owned(), warn_on_spelling()).unwrap();
+    assert_eq!(
+        literals(&scanned.tokens),
+        vec![identifier("عدد"), identifier("عدد"), (TokenType::Eof, None)]
+    );
+    assert!(scanned.warnings.is_empty());
+}
+
+#[test]
+fn it_should_accept_extended_letters_in_identifiers() {
+    let scanned = scan_tokens("پدر چای ژاله گل کتاب".to_owned()).unwrap();
+    assert_eq!(
+        scanned
+            .iter()
+            .map(|token| token.lexeme.as_str())
+            .collect::<Vec<_>>(),
+        vec!["پدر", "چای", "ژاله", "گل", "کتاب", ""]
+    );
+    assert_eq!(
+        scanned[4].literal,
+        Some(Literal::Identifier(String::from("كتاب")))
+    );
+}
+
+#[test]
+fn it_should_not_start_identifiers_with_tatweel() {
+    assert!(scan_tokens("ـاسم".to_owned()).is_err());
+}
+
+#[test]
+fn it_should_tokenize_numbers_in_every_digit_set() {
+    assert_eq!(
+        literals(&scan_tokens("١٢ ۱۲ 12 ۳٫۵ 3.5 ٢٫٥".to_owned()).unwrap()),
+        vec![
+            (TokenType::Number, Some(Literal::Integer(12))),
+            (TokenType::Number, Some(Literal::Integer(12))),
+            (TokenType::Number, Some(Literal::Integer(12))),
+            (TokenType::Number, Some(Literal::Number(3.5))),
+            (TokenType::Number, Some(Literal::Number(3.5))),
+            (TokenType::Number, Some(Literal::Number(2.5))),
+            (TokenType::Eof, None),
+        ]
+    );
+}
+
+#[test]
+fn it_should_tokenize_thousands_separators() {
+    assert_eq!(
+        literals(&scan_tokens("١٬٠٠٠٬٠٠٠ ۱٬۲۳۴٫۵".to_owned()).unwrap()),
+        vec![
+            (TokenType::Number, Some(Literal::Integer(1_000_000))),
+            (TokenType::Number, Some(Literal::Number(1234.5))),
+            (TokenType::Eof, None),
+        ]
+    );
+    // a trailing separator isn't part of the number
+    assert!(scan_tokens("١٬".to_owned()).is_err());
+}
+
+#[test]
+fn it_should_only_accept_the_configured_digits() {
+    let options = ScanOptions {
+        digits: vec![DigitSet::ArabicIndic],
+        ..ScanOptions::default()
+    };
+    assert!(scan_tokens_with("١٢".to_owned(), options.clone()).is_ok());
+    assert!(scan_tokens_with("۱۲".to_owned(), options.clone()).is_err());
+    assert!(scan_tokens_with("12".to_owned(), options).is_err());
+}
+
+#[test]
+fn it_should_count_columns_from_zero_on_every_line() {
+    let tokens = scan_tokens("دع س\nدع صاد".to_owned()).unwrap();
+    let positions: Vec<_> = tokens.iter().map(|token| (token.line, token.col)).collect();
+    assert_eq!(positions, vec![(1, 1), (1, 3), (2, 1), (2, 5), (2, 5)]);
+}
+
+fn teaching_keywords() -> ScanOptions {
+    ScanOptions {
+        keywords: KeywordProfile::builtin("تعليمي").unwrap(),
+        ..ScanOptions::default()
+    }
+}
+
+#[test]
+fn it_should_tokenize_keyword_synonyms() {
+    let types = |tokens: Vec<Token>| tokens.iter().map(|token| token.ty).collect::<Vec<_>>();
+    let code = "متغير إذا وإلا اذا دع";
+    assert_eq!(
+        types(
+            scan_tokens_with(code.to_owned(), teaching_keywords())
+                .unwrap()
+                .tokens
+        ),
+        vec![
+            TokenType::Var,
+            TokenType::If,
+            TokenType::Else,
+            TokenType::If,
+            TokenType::Var,
+            TokenType::Eof,
+        ]
+    );
+    assert_eq!(
+        types(scan_tokens(code.to_owned()).unwrap()),
+        vec![
+            TokenType::Identifier,
+            TokenType::Identifier,
+            TokenType::Identifier,
+            TokenType::Identifier,
+            TokenType::Var,
+            TokenType::Eof,
+        ]
+    );
+}
+
+#[test]
+fn it_should_canonicalize_keyword_synonyms() {
+    assert_eq!(
+        canonicalize(
+            "متغير س = ١؛ \\\\ إذا\nإذا (س) { اطبع_سطر(\"وإلا\")؛ } وإلا { أساس؛ }".to_owned(),
+            teaching_keywords()
+        )
+        .unwrap(),
+        "دع س = ١؛ \\\\ إذا\nاذا_كان (س) { اطبع_سطر(\"وإلا\")؛ } غير_ذلك { أساس؛ }"
+    );
+}
+
+#[test]
+fn it_should_parse_keyword_profiles() {
+    let profile = KeywordProfile::parse("# تجربة\n\nمتغير = دع\nاذا = إذا_كان\n").unwrap();
+    assert_eq!(
+        profile,
+        KeywordProfile::default()
+            .with_synonym("متغير", TokenType::Var)
+            .with_synonym("اذا", TokenType::If)
+    );
+}
+
+#[test]
+fn it_should_reject_invalid_keyword_synonyms() {
+    let error = |text: &str| KeywordProfile::parse(text).unwrap_err();
+    assert_eq!(
+        error("متغير دع").what,
+        "Expected 'synonym = keyword', found 'متغير دع'"
+    );
+    assert_eq!(error("متغير = متغير").what, "'متغير' is not a keyword");
+    assert_eq!(error("صنف = دع").what, "'صنف' is a keyword already");
+    assert_eq!(error("ب ت = دع").what, "'ب ت' can't be a keyword");
+    assert_eq!(error("if = اذا_كان").what, "'if' can't be a keyword");
+    assert_eq!(error("_متغير = دع").what, "'_متغير' can't be a keyword");
+    assert!(KeywordProfile::parse("مـتـغـيِّر_عام = دع").is_ok());
+    let duplicate = error("متغير = دع\n\nمتغير = صنف");
+    assert_eq!(duplicate.what, "'متغير' is a synonym of 'دع' already");
+    assert_eq!(duplicate.line, 3);
+}
+
+#[test]
+fn it_should_know_the_builtin_keyword_profiles() {
+    for name in KeywordProfile::BUILTIN {
+        assert!(KeywordProfile::builtin(name).is_some());
+    }
+    assert_eq!(
+        KeywordProfile::builtin("teaching"),
+        KeywordProfile::builtin("تعليمي")
+    );
+    assert!(KeywordProfile::builtin("قديم").is_none());
+}
+
+#[test]
+fn it_should_accept_urdu_and_kurdish_identifiers() {
+    let scanned = scan_tokens("لڑکا ٹوپی ڈاک میں ہے گوڵ دۆست ڕێگا ڤین ٱسم".to_owned()).unwrap();
+    assert_eq!(
+        scanned
+            .iter()
+            .map(|token| token.lexeme.as_str())
+            .collect::<Vec<_>>(),
+        vec![
+            "لڑکا", "ٹوپی", "ڈاک", "میں", "ہے", "گوڵ", "دۆست", "ڕێگا", "ڤین", "ٱسم", ""
+        ]
+    );
+    assert_eq!(
+        scanned[9].literal,
+        Some(Literal::Identifier(String::from("اسم")))
+    );
+}
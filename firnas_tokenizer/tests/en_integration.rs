@@ -8,7 +8,7 @@ use firnas_tokenizer::tokenizer;
 #[test]
 fn it_should_tokenize_single_char_token() {
     assert_eq!(
-        tokenizer::scan_tokens("( ) [ ] { } , . - + ; / *".to_owned()).unwrap(),
+        tokenizer::scan_tokens("( ) [ ] { } , . - + ; / * | & :".to_owned()).unwrap(),
         vec![
             Token {
                 ty: TokenType::LeftRoundBracket,
@@ -101,6 +101,27 @@ fn it_should_tokenize_single_char_token() {
                 line: 1,
                 col: 0
             },
+            Token {
+                ty: TokenType::Pipe,
+                lexeme: String::from("|"),
+                literal: None,
+                line: 1,
+                col: 0
+            },
+            Token {
+                ty: TokenType::Ampersand,
+                lexeme: String::from("&"),
+                literal: None,
+                line: 1,
+                col: 0
+            },
+            Token {
+                ty: TokenType::Colon,
+                lexeme: String::from(":"),
+                literal: None,
+                line: 1,
+                col: 0
+            },
             Token {
                 ty: TokenType::Eof,
                 lexeme: String::new(),
@@ -185,6 +206,7 @@ fn it_should_tokenize_one_or_two_char() {
 }
 
 #[test]
+#[allow(clippy::approx_constant)]
 fn it_should_tokenize_literals() {
     assert_eq!(
         tokenizer::scan_tokens("firnas \"str\" 3.14".to_owned()).unwrap(),
@@ -221,6 +243,66 @@ fn it_should_tokenize_literals() {
     );
 }
 
+#[test]
+fn it_should_tokenize_integer_and_float_literals() {
+    assert_eq!(
+        tokenizer::scan_tokens("3 3.0".to_owned()).unwrap(),
+        vec![
+            Token {
+                ty: TokenType::Number,
+                lexeme: String::from("3"),
+                literal: Some(Literal::Integer(3)),
+                line: 1,
+                col: 0
+            },
+            Token {
+                ty: TokenType::Number,
+                lexeme: String::from("3.0"),
+                literal: Some(Literal::Number(3.0)),
+                line: 1,
+                col: 0
+            },
+            Token {
+                ty: TokenType::Eof,
+                lexeme: String::from(""),
+                literal: None,
+                line: 1,
+                col: 0
+            }
+        ]
+    );
+}
+
+#[test]
+fn it_should_tokenize_big_integer_and_rational_literals() {
+    assert_eq!(
+        tokenizer::scan_tokens("92233720368547758070 0.25r".to_owned()).unwrap(),
+        vec![
+            Token {
+                ty: TokenType::Number,
+                lexeme: String::from("92233720368547758070"),
+                literal: Some(Literal::BigInteger(String::from("92233720368547758070"))),
+                line: 1,
+                col: 0
+            },
+            Token {
+                ty: TokenType::Number,
+                lexeme: String::from("0.25r"),
+                literal: Some(Literal::Rational(String::from("0.25"))),
+                line: 1,
+                col: 0
+            },
+            Token {
+                ty: TokenType::Eof,
+                lexeme: String::from(""),
+                literal: None,
+                line: 1,
+                col: 0
+            }
+        ]
+    );
+}
+
 #[test]
 fn it_should_tokenize_keywords() {
     assert_eq!(
@@ -352,3 +434,10 @@ fn it_should_tokenize_keywords() {
         ]
     );
 }
+
+#[test]
+fn it_should_count_columns_in_grapheme_clusters() {
+    let tokens = tokenizer::scan_tokens("var t = \"سَلام\" x;".to_owned()).unwrap();
+    let x = tokens.iter().find(|token| token.lexeme == "x").unwrap();
+    assert_eq!(x.col, 15);
+}
@@ -1,13 +1,22 @@
 use crate::value;
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 
 enum GcData {
     String(String),
+    BigInt(BigInt),
+    Rational(BigRational),
     Closure(value::Closure),
     Class(value::Class),
     Instance(value::Instance),
     BoundMethod(value::BoundMethod),
     List(Vec<value::Value>),
+    Tuple(Vec<value::Value>),
+    Set(BTreeMap<value::HashKey, value::Value>),
+    Dict(BTreeMap<value::HashKey, (value::Value, value::Value)>),
+    Regex(regex::Regex),
 }
 
 impl GcData {
@@ -18,6 +27,27 @@ impl GcData {
         }
     }
 
+    fn as_bigint(&self) -> Option<&BigInt> {
+        match self {
+            GcData::BigInt(num) => Some(num),
+            _ => None,
+        }
+    }
+
+    fn as_rational(&self) -> Option<&BigRational> {
+        match self {
+            GcData::Rational(num) => Some(num),
+            _ => None,
+        }
+    }
+
+    fn as_regex(&self) -> Option<&regex::Regex> {
+        match self {
+            GcData::Regex(re) => Some(re),
+            _ => None,
+        }
+    }
+
     fn as_list(&self) -> Option<&Vec<value::Value>> {
         match self {
             GcData::List(elements) => Some(elements),
@@ -25,6 +55,43 @@ impl GcData {
         }
     }
 
+    fn as_tuple(&self) -> Option<&Vec<value::Value>> {
+        match self {
+            GcData::Tuple(elements) => Some(elements),
+            _ => None,
+        }
+    }
+
+    fn as_set(&self) -> Option<&BTreeMap<value::HashKey, value::Value>> {
+        match self {
+            GcData::Set(elements) => Some(elements),
+            _ => None,
+        }
+    }
+
+    fn as_set_mut(&mut self) -> Option<&mut BTreeMap<value::HashKey, value::Value>> {
+        match self {
+            GcData::Set(elements) => Some(elements),
+            _ => None,
+        }
+    }
+
+    fn as_dict(&self) -> Option<&BTreeMap<value::HashKey, (value::Value, value::Value)>> {
+        match self {
+            GcData::Dict(entries) => Some(entries),
+            _ => None,
+        }
+    }
+
+    fn as_dict_mut(
+        &mut self,
+    ) -> Option<&mut BTreeMap<value::HashKey, (value::Value, value::Value)>> {
+        match self {
+            GcData::Dict(entries) => Some(entries),
+            _ => None,
+        }
+    }
+
     fn as_list_mut(&mut self) -> Option<&mut Vec<value::Value>> {
         match self {
             GcData::List(elements) => Some(elements),
@@ -132,6 +199,27 @@ impl Heap {
         id
     }
 
+    pub fn manage_bigint(&mut self, num: BigInt) -> HeapId {
+        self.bytes_allocated += (num.bits() / 8) as usize;
+        let id = self.generate_id();
+        self.values.insert(id, GCVal::from(GcData::BigInt(num)));
+        id
+    }
+
+    pub fn manage_regex(&mut self, re: regex::Regex) -> HeapId {
+        self.bytes_allocated += re.as_str().len();
+        let id = self.generate_id();
+        self.values.insert(id, GCVal::from(GcData::Regex(re)));
+        id
+    }
+
+    pub fn manage_rational(&mut self, num: BigRational) -> HeapId {
+        self.bytes_allocated += ((num.numer().bits() + num.denom().bits()) / 8) as usize;
+        let id = self.generate_id();
+        self.values.insert(id, GCVal::from(GcData::Rational(num)));
+        id
+    }
+
     pub fn manage_list(&mut self, elements: Vec<value::Value>) -> HeapId {
         self.bytes_allocated += elements.len();
         let id = self.generate_id();
@@ -139,6 +227,30 @@ impl Heap {
         id
     }
 
+    pub fn manage_tuple(&mut self, elements: Vec<value::Value>) -> HeapId {
+        self.bytes_allocated += elements.len();
+        let id = self.generate_id();
+        self.values.insert(id, GCVal::from(GcData::Tuple(elements)));
+        id
+    }
+
+    pub fn manage_set(&mut self, elements: BTreeMap<value::HashKey, value::Value>) -> HeapId {
+        self.bytes_allocated += elements.len();
+        let id = self.generate_id();
+        self.values.insert(id, GCVal::from(GcData::Set(elements)));
+        id
+    }
+
+    pub fn manage_dict(
+        &mut self,
+        entries: BTreeMap<value::HashKey, (value::Value, value::Value)>,
+    ) -> HeapId {
+        self.bytes_allocated += entries.len();
+        let id = self.generate_id();
+        self.values.insert(id, GCVal::from(GcData::Dict(entries)));
+        id
+    }
+
     pub fn manage_closure(&mut self, c: value::Closure) -> HeapId {
         self.bytes_allocated += c.function.chunk.code.len();
         self.bytes_allocated += c.function.chunk.constants.len();
@@ -183,6 +295,18 @@ impl Heap {
         self.values.get(&id).unwrap().data.as_str().unwrap()
     }
 
+    pub fn get_bigint(&self, id: HeapId) -> &BigInt {
+        self.values.get(&id).unwrap().data.as_bigint().unwrap()
+    }
+
+    pub fn get_rational(&self, id: HeapId) -> &BigRational {
+        self.values.get(&id).unwrap().data.as_rational().unwrap()
+    }
+
+    pub fn get_regex(&self, id: HeapId) -> &regex::Regex {
+        self.values.get(&id).unwrap().data.as_regex().unwrap()
+    }
+
     pub fn get_closure(&self, id: HeapId) -> &value::Closure {
         self.values.get(&id).unwrap().data.as_closure().unwrap()
     }
@@ -209,6 +333,40 @@ impl Heap {
             .unwrap()
     }
 
+    pub fn get_tuple_elements(&self, id: HeapId) -> &Vec<value::Value> {
+        self.values.get(&id).unwrap().data.as_tuple().unwrap()
+    }
+
+    pub fn get_set_elements(&self, id: HeapId) -> &BTreeMap<value::HashKey, value::Value> {
+        self.values.get(&id).unwrap().data.as_set().unwrap()
+    }
+
+    pub fn get_set_elements_mut(
+        &mut self,
+        id: HeapId,
+    ) -> &mut BTreeMap<value::HashKey, value::Value> {
+        self.values.get_mut(&id).unwrap().data.as_set_mut().unwrap()
+    }
+
+    pub fn get_dict_entries(
+        &self,
+        id: HeapId,
+    ) -> &BTreeMap<value::HashKey, (value::Value, value::Value)> {
+        self.values.get(&id).unwrap().data.as_dict().unwrap()
+    }
+
+    pub fn get_dict_entries_mut(
+        &mut self,
+        id: HeapId,
+    ) -> &mut BTreeMap<value::HashKey, (value::Value, value::Value)> {
+        self.values
+            .get_mut(&id)
+            .unwrap()
+            .data
+            .as_dict_mut()
+            .unwrap()
+    }
+
     pub fn get_class(&self, id: HeapId) -> &value::Class {
         self.values.get(&id).unwrap().data.as_class().unwrap()
     }
@@ -252,11 +410,17 @@ impl Heap {
     pub fn children(&self, id: HeapId) -> Vec<HeapId> {
         match &self.values.get(&id).unwrap().data {
             GcData::String(_) => Vec::new(),
+            GcData::BigInt(_) => Vec::new(),
+            GcData::Rational(_) => Vec::new(),
+            GcData::Regex(_) => Vec::new(),
             GcData::Closure(closure) => self.closure_children(closure),
             GcData::Class(class) => self.class_children(class),
             GcData::Instance(instance) => self.instance_children(instance),
             GcData::BoundMethod(method) => self.bound_method_children(method),
             GcData::List(elements) => self.list_children(elements),
+            GcData::Tuple(elements) => self.list_children(elements),
+            GcData::Set(elements) => self.set_children(elements),
+            GcData::Dict(entries) => self.dict_children(entries),
         }
     }
 
@@ -265,7 +429,12 @@ impl Heap {
     }
 
     pub fn class_children(&self, class: &value::Class) -> Vec<HeapId> {
-        class.methods.values().copied().collect()
+        class
+            .methods
+            .values()
+            .copied()
+            .chain(class.superclass)
+            .collect()
     }
 
     pub fn instance_children(&self, instance: &value::Instance) -> Vec<HeapId> {
@@ -292,6 +461,21 @@ impl Heap {
         res
     }
 
+    pub fn set_children(&self, elements: &BTreeMap<value::HashKey, value::Value>) -> Vec<HeapId> {
+        elements.values().filter_map(Heap::extract_id).collect()
+    }
+
+    pub fn dict_children(
+        &self,
+        entries: &BTreeMap<value::HashKey, (value::Value, value::Value)>,
+    ) -> Vec<HeapId> {
+        entries
+            .values()
+            .flat_map(|(key, val)| [Heap::extract_id(key), Heap::extract_id(val)])
+            .flatten()
+            .collect()
+    }
+
     pub fn closure_children(&self, closure: &value::Closure) -> Vec<HeapId> {
         let res: Vec<HeapId> = closure
             .upvalues
@@ -307,6 +491,9 @@ impl Heap {
     pub fn extract_id(val: &value::Value) -> Option<HeapId> {
         match val {
             value::Value::Number(_) => None,
+            value::Value::Integer(_) => None,
+            value::Value::BigInt(id) => Some(*id),
+            value::Value::Rational(id) => Some(*id),
             value::Value::Bool(_) => None,
             value::Value::String(id) => Some(*id),
             value::Value::Function(id) => Some(*id),
@@ -316,6 +503,10 @@ impl Heap {
             value::Value::NativeFunction(_) => None,
             value::Value::Nil => None,
             value::Value::List(id) => Some(*id),
+            value::Value::Tuple(id) => Some(*id),
+            value::Value::Set(id) => Some(*id),
+            value::Value::Dict(id) => Some(*id),
+            value::Value::Regex(id) => Some(*id),
         }
     }
 
@@ -1,4 +1,5 @@
 pub mod gc;
+pub mod numeric;
 pub mod value;
 pub mod virtual_machine;
 
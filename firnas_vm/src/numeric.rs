@@ -0,0 +1,290 @@
+use crate::gc;
+use crate::value;
+use firnas_compiler::diagnostic::Language;
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_rational::BigRational;
+use num_traits::FromPrimitive;
+use num_traits::One;
+use num_traits::ToPrimitive;
+use num_traits::Zero;
+use std::cmp::Ordering;
+
+#[derive(Debug, Copy, Clone)]
+pub enum Binop {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl Binop {
+    pub fn name(self, language: Language) -> &'static str {
+        match (self, language) {
+            (Binop::Add, Language::English) => "Add",
+            (Binop::Sub, Language::English) => "Sub",
+            (Binop::Mul, Language::English) => "Mul",
+            (Binop::Div, Language::English) => "Div",
+            (Binop::Add, Language::Arabic) => "الجمع",
+            (Binop::Sub, Language::Arabic) => "الطرح",
+            (Binop::Mul, Language::Arabic) => "الضرب",
+            (Binop::Div, Language::Arabic) => "القسمة",
+        }
+    }
+}
+
+/*
+The numeric tower, from narrowest to widest: Integer, BigInt, Rational, Float.
+Binary operations widen both operands to the wider of the two kinds. Integer results
+that overflow an i64 are promoted to BigInt, and BigInt results that fit are narrowed back.
+ */
+#[derive(Debug, Clone)]
+pub enum Numeric {
+    Integer(i64),
+    BigInt(BigInt),
+    Rational(BigRational),
+    Float(f64),
+}
+
+impl Numeric {
+    pub fn from_value(heap: &gc::Heap, val: &value::Value) -> Option<Numeric> {
+        match val {
+            value::Value::Integer(num) => Some(Numeric::Integer(*num)),
+            value::Value::Number(num) => Some(Numeric::Float(*num)),
+            value::Value::BigInt(id) => Some(Numeric::BigInt(heap.get_bigint(*id).clone())),
+            value::Value::Rational(id) => Some(Numeric::Rational(heap.get_rational(*id).clone())),
+            _ => None,
+        }
+    }
+
+    pub fn into_value(self, heap: &mut gc::Heap) -> value::Value {
+        match self {
+            Numeric::Integer(num) => value::Value::Integer(num),
+            Numeric::Float(num) => value::Value::Number(num),
+            Numeric::BigInt(num) => match num.to_i64() {
+                Some(num) => value::Value::Integer(num),
+                None => value::Value::BigInt(heap.manage_bigint(num)),
+            },
+            Numeric::Rational(num) => value::Value::Rational(heap.manage_rational(num)),
+        }
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        match self {
+            Numeric::Integer(num) => *num as f64,
+            Numeric::BigInt(num) => num.to_f64().unwrap_or(f64::NAN),
+            Numeric::Rational(num) => num.to_f64().unwrap_or(f64::NAN),
+            Numeric::Float(num) => *num,
+        }
+    }
+
+    pub fn to_bigint(&self) -> Option<BigInt> {
+        match self {
+            Numeric::Integer(num) => Some(BigInt::from(*num)),
+            Numeric::BigInt(num) => Some(num.clone()),
+            Numeric::Rational(num) if num.is_integer() => Some(num.to_integer()),
+            _ => None,
+        }
+    }
+
+    pub fn to_rational(&self) -> Option<BigRational> {
+        match self {
+            Numeric::Integer(num) => Some(BigRational::from_integer(BigInt::from(*num))),
+            Numeric::BigInt(num) => Some(BigRational::from_integer(num.clone())),
+            Numeric::Rational(num) => Some(num.clone()),
+            Numeric::Float(num) => BigRational::from_float(*num),
+        }
+    }
+
+    /// Rounds towards zero, keeping the result an integer kind.
+    pub fn truncate(&self) -> Option<Numeric> {
+        match self {
+            Numeric::Integer(_) | Numeric::BigInt(_) => Some(self.clone()),
+            Numeric::Rational(num) => Some(Numeric::BigInt(num.trunc().to_integer())),
+            Numeric::Float(num) => BigInt::from_f64(num.trunc()).map(Numeric::BigInt),
+        }
+    }
+
+    /// Rounds towards negative infinity, keeping the result an integer kind.
+    pub fn floor(&self) -> Option<Numeric> {
+        match self {
+            Numeric::Rational(num) => Some(Numeric::BigInt(num.floor().to_integer())),
+            Numeric::Float(num) => BigInt::from_f64(num.floor()).map(Numeric::BigInt),
+            _ => self.truncate(),
+        }
+    }
+
+    /// Rounds towards positive infinity, keeping the result an integer kind.
+    pub fn ceil(&self) -> Option<Numeric> {
+        match self {
+            Numeric::Rational(num) => Some(Numeric::BigInt(num.ceil().to_integer())),
+            Numeric::Float(num) => BigInt::from_f64(num.ceil()).map(Numeric::BigInt),
+            _ => self.truncate(),
+        }
+    }
+
+    /// Rounds half away from zero, keeping the result an integer kind.
+    pub fn round(&self) -> Option<Numeric> {
+        match self {
+            Numeric::Rational(num) => Some(Numeric::BigInt(num.round().to_integer())),
+            Numeric::Float(num) => BigInt::from_f64(num.round()).map(Numeric::BigInt),
+            _ => self.truncate(),
+        }
+    }
+
+    pub fn abs(self) -> Numeric {
+        match self.compare(&Numeric::Integer(0)) {
+            Some(Ordering::Less) => self.negate(),
+            _ => self,
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        match self {
+            Numeric::Integer(num) => *num == 0,
+            Numeric::BigInt(num) => num.is_zero(),
+            Numeric::Rational(num) => num.is_zero(),
+            Numeric::Float(num) => *num == 0.0,
+        }
+    }
+
+    pub fn negate(self) -> Numeric {
+        match self {
+            Numeric::Integer(num) => match num.checked_neg() {
+                Some(negated) => Numeric::Integer(negated),
+                None => Numeric::BigInt(-BigInt::from(num)),
+            },
+            Numeric::BigInt(num) => Numeric::BigInt(-num),
+            Numeric::Rational(num) => Numeric::Rational(-num),
+            Numeric::Float(num) => Numeric::Float(-num),
+        }
+    }
+
+    pub fn apply(self, right: Numeric, binop: Binop) -> Result<Numeric, String> {
+        match (&self, &right) {
+            (Numeric::Integer(left), Numeric::Integer(right)) => {
+                if let Binop::Div = binop {
+                    // only exact quotients stay integers, `7 / 2` is `3.5`
+                    if *right == 0 || left.wrapping_rem(*right) != 0 {
+                        return Ok(Numeric::Float(*left as f64 / *right as f64));
+                    }
+                }
+                if let Some(res) = Numeric::apply_integer_binop(*left, *right, binop) {
+                    return Ok(Numeric::Integer(res));
+                }
+                Ok(Numeric::apply_bigint_binop(
+                    BigInt::from(*left),
+                    BigInt::from(*right),
+                    binop,
+                ))
+            }
+            (Numeric::Float(_), _) | (_, Numeric::Float(_)) => Ok(Numeric::Float(
+                Numeric::apply_float_binop(self.to_f64(), right.to_f64(), binop),
+            )),
+            (Numeric::Rational(_), _) | (_, Numeric::Rational(_)) => {
+                let left = self.to_rational().unwrap();
+                let right = right.to_rational().unwrap();
+                Numeric::apply_rational_binop(left, right, binop).map(Numeric::Rational)
+            }
+            _ => Ok(Numeric::apply_bigint_binop(
+                self.to_bigint().unwrap(),
+                right.to_bigint().unwrap(),
+                binop,
+            )),
+        }
+    }
+
+    /// `None` when either side is NaN. A float is compared to the other kinds by its exact
+    /// value, so `0.1` is not equal to `1/10`, which it only approximates.
+    pub fn compare(&self, other: &Numeric) -> Option<Ordering> {
+        match (self, other) {
+            (Numeric::Integer(left), Numeric::Integer(right)) => Some(left.cmp(right)),
+            (Numeric::Float(left), Numeric::Float(right)) => left.partial_cmp(right),
+            (Numeric::Float(_), _) | (_, Numeric::Float(_)) => {
+                match (self.to_rational(), other.to_rational()) {
+                    (Some(left), Some(right)) => Some(left.cmp(&right)),
+                    // NaN and the infinities
+                    _ => self.to_f64().partial_cmp(&other.to_f64()),
+                }
+            }
+            _ => Some(
+                self.to_rational()
+                    .unwrap()
+                    .cmp(&other.to_rational().unwrap()),
+            ),
+        }
+    }
+
+    /// Exact, like `compare`, so that equal numbers have equal hash keys.
+    pub fn equals(&self, other: &Numeric) -> bool {
+        self.compare(other) == Some(Ordering::Equal)
+    }
+
+    fn apply_integer_binop(left: i64, right: i64, binop: Binop) -> Option<i64> {
+        match binop {
+            Binop::Add => left.checked_add(right),
+            Binop::Sub => left.checked_sub(right),
+            Binop::Mul => left.checked_mul(right),
+            Binop::Div => left.checked_div(right),
+        }
+    }
+
+    fn apply_bigint_binop(left: BigInt, right: BigInt, binop: Binop) -> Numeric {
+        match binop {
+            Binop::Add => Numeric::BigInt(left + right),
+            Binop::Sub => Numeric::BigInt(left - right),
+            Binop::Mul => Numeric::BigInt(left * right),
+            Binop::Div => {
+                if right.is_zero() || !left.is_multiple_of(&right) {
+                    let left = Numeric::BigInt(left).to_f64();
+                    let right = Numeric::BigInt(right).to_f64();
+                    Numeric::Float(left / right)
+                } else {
+                    Numeric::BigInt(left / right)
+                }
+            }
+        }
+    }
+
+    fn apply_rational_binop(
+        left: BigRational,
+        right: BigRational,
+        binop: Binop,
+    ) -> Result<BigRational, String> {
+        match binop {
+            Binop::Add => Ok(left + right),
+            Binop::Sub => Ok(left - right),
+            Binop::Mul => Ok(left * right),
+            Binop::Div if right.is_zero() => Err("Division of a rational by zero".to_string()),
+            Binop::Div => Ok(left / right),
+        }
+    }
+
+    fn apply_float_binop(left: f64, right: f64, binop: Binop) -> f64 {
+        match binop {
+            Binop::Add => left + right,
+            Binop::Sub => left - right,
+            Binop::Mul => left * right,
+            Binop::Div => left / right,
+        }
+    }
+}
+
+/// Parses the decimal text of a rational literal, e.g. `0.25` into `1/4`.
+pub fn parse_rational(text: &str) -> Option<BigRational> {
+    let (whole, fraction) = match text.split_once('.') {
+        Some((whole, fraction)) => (whole, fraction),
+        None => (text, ""),
+    };
+    let numer: BigInt = format!("{whole}{fraction}").parse().ok()?;
+    let denom = num_traits::pow(BigInt::from(10), fraction.len());
+    Some(BigRational::new(numer, denom))
+}
+
+pub fn format_rational(num: &BigRational) -> String {
+    if num.denom().is_one() {
+        num.numer().to_string()
+    } else {
+        format!("{}/{}", num.numer(), num.denom())
+    }
+}
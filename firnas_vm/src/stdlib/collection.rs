@@ -1,111 +1,708 @@
+use super::number::expect_integer;
+use super::number::expect_number;
+use super::string;
+use super::StdFunc;
+use crate::gc;
 use crate::value;
 use crate::virtual_machine;
+use std::cmp::Ordering;
+
+/*
+Natives here that take a function call back into firnas code through
+`VirtualMachine::call_from_native`. A collection can run on any step of such a call, so values
+that are only held in rust while it runs are first put in a list that is pushed on the stack
+with `root`, and popped with `unroot` once the callbacks are done.
+ */
+
+pub fn std_collection_len() -> StdFunc {
+    StdFunc::native("len", "طول", 1, len)
+}
+
+pub fn std_collection_for_each() -> StdFunc {
+    StdFunc::native("forEach", "لكل", 2, for_each)
+}
+
+pub fn std_collection_map() -> StdFunc {
+    StdFunc::native("map", "طبق", 2, map)
+}
+
+pub fn std_collection_filter() -> StdFunc {
+    StdFunc::native("filter", "رشح", 2, filter)
+}
+
+pub fn std_collection_reduce() -> StdFunc {
+    StdFunc::native("reduce", "اختزل", 3, reduce)
+}
+
+pub fn std_collection_sort() -> StdFunc {
+    StdFunc::native("sort", "رتب", 1, sort).with_optional(1)
+}
+
+/// Chooses how strings are ordered: `"arabic"` (`"عربي"`, the default) sorts them by letter
+/// as a dictionary does, `"codepoint"` (`"رموز"`) by code point.
+pub fn std_collection_use_collation() -> StdFunc {
+    StdFunc::native("useCollation", "استخدم_ترتيب", 1, use_collation)
+}
+
+pub fn std_collection_reverse() -> StdFunc {
+    StdFunc::native("reverse", "اعكس", 1, reverse)
+}
+
+pub fn std_collection_zip() -> StdFunc {
+    StdFunc::native("zip", "زاوج", 2, zip)
+}
+
+pub fn std_collection_enumerate() -> StdFunc {
+    StdFunc::native("enumerate", "مع_الفهرس", 1, enumerate)
+}
+
+pub fn std_collection_any() -> StdFunc {
+    StdFunc::native("any", "أي", 2, any)
+}
+
+pub fn std_collection_all() -> StdFunc {
+    StdFunc::native("all", "كل", 2, all)
+}
+
+pub fn std_collection_find() -> StdFunc {
+    StdFunc::native("find", "ابحث", 2, find)
+}
+
+pub fn std_collection_index_of() -> StdFunc {
+    StdFunc::native("indexOf", "موقع", 2, index_of)
+}
+
+pub fn std_collection_push() -> StdFunc {
+    StdFunc::native("push", "ادفع", 2, push)
+}
+
+pub fn std_collection_pop() -> StdFunc {
+    StdFunc::native("pop", "اسحب", 1, pop)
+}
+
+pub fn std_collection_insert() -> StdFunc {
+    StdFunc::native("insert", "أدرج", 3, insert)
+}
+
+pub fn std_collection_range() -> StdFunc {
+    StdFunc::native("range", "مدى", 2, range)
+}
+
+pub fn std_collection_add() -> StdFunc {
+    StdFunc::native("add", "أضف", 2, add)
+}
+
+pub fn std_collection_remove() -> StdFunc {
+    StdFunc::native("remove", "احذف", 2, remove)
+}
+
+pub fn std_collection_contains() -> StdFunc {
+    StdFunc::native("contains", "يحتوي", 2, contains)
+}
+
+pub fn std_collection_keys() -> StdFunc {
+    StdFunc::native("keys", "مفاتيح", 1, keys)
+}
+
+pub fn std_collection_values() -> StdFunc {
+    StdFunc::native("values", "قيم", 1, values)
+}
+
+fn root(interp: &mut virtual_machine::VirtualMachine, elements: Vec<value::Value>) -> gc::HeapId {
+    let id = interp.heap.manage_list(elements);
+    interp.stack.push(value::Value::List(id));
+    id
+}
+
+fn unroot(interp: &mut virtual_machine::VirtualMachine) {
+    interp.stack.pop();
+}
+
+/// The elements a native iterates over: the keys of a dictionary and the characters of a string.
+fn iterable_elements(
+    interp: &mut virtual_machine::VirtualMachine,
+    val: &value::Value,
+    func_name: &str,
+) -> Result<Vec<value::Value>, String> {
+    match val {
+        value::Value::List(id) => Ok(interp.heap.get_list_elements(*id).clone()),
+        value::Value::Tuple(id) => Ok(interp.heap.get_tuple_elements(*id).clone()),
+        value::Value::Set(id) => Ok(interp
+            .heap
+            .get_set_elements(*id)
+            .values()
+            .cloned()
+            .collect()),
+        value::Value::Dict(id) => Ok(interp
+            .heap
+            .get_dict_entries(*id)
+            .values()
+            .map(|(key, _)| key.clone())
+            .collect()),
+        value::Value::String(id) => Ok(string::graphemes(&interp.heap.get_str(*id).clone())
+            .into_iter()
+            .map(|grapheme| value::Value::String(interp.heap.manage_str(grapheme.to_string())))
+            .collect()),
+        val => Err(format!(
+            "Can't call {} on value of type {}",
+            func_name,
+            value::type_of(val).name(interp.language)
+        )),
+    }
+}
+
+fn expect_list(
+    interp: &virtual_machine::VirtualMachine,
+    val: &value::Value,
+    func_name: &str,
+) -> Result<gc::HeapId, String> {
+    match val {
+        value::Value::List(id) => Ok(*id),
+        val => Err(format!(
+            "Can't call {} on value of type {}",
+            func_name,
+            value::type_of(val).name(interp.language)
+        )),
+    }
+}
 
 pub fn len(
     interp: &mut virtual_machine::VirtualMachine,
     args: &[value::Value],
 ) -> Result<value::Value, String> {
     match &args[0] {
-        value::Value::String(id) => Ok(value::Value::Number(interp.heap.get_str(*id).len() as f64)),
-        value::Value::List(id) => Ok(value::Value::Number(
-            interp.heap.get_list_elements(*id).len() as f64,
+        value::Value::String(id) => Ok(value::Value::Integer(
+            string::graphemes(interp.heap.get_str(*id)).len() as i64,
+        )),
+        value::Value::List(id) => Ok(value::Value::Integer(
+            interp.heap.get_list_elements(*id).len() as i64,
+        )),
+        value::Value::Tuple(id) => Ok(value::Value::Integer(
+            interp.heap.get_tuple_elements(*id).len() as i64,
+        )),
+        value::Value::Set(id) => Ok(value::Value::Integer(
+            interp.heap.get_set_elements(*id).len() as i64,
+        )),
+        value::Value::Dict(id) => Ok(value::Value::Integer(
+            interp.heap.get_dict_entries(*id).len() as i64,
         )),
         val => Err(format!(
-            "Object of type {:?} has no len.",
-            value::type_of(val)
+            "Object of type {} has no len",
+            value::type_of(val).name(interp.language)
         )),
     }
 }
 
-pub fn for_each(
+fn for_each(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let elements = iterable_elements(interp, &args[0], "forEach")?;
+    root(interp, elements.clone());
+    for element in elements {
+        interp.call_from_native(&args[1], &[element])?;
+    }
+    unroot(interp);
+    Ok(value::Value::Nil)
+}
+
+fn map(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let elements = iterable_elements(interp, &args[0], "map")?;
+    let res_id = root(interp, Vec::new());
+    root(interp, elements.clone());
+    for element in elements {
+        let mapped = interp.call_from_native(&args[1], &[element])?;
+        interp.heap.get_list_elements_mut(res_id).push(mapped);
+    }
+    unroot(interp);
+    unroot(interp);
+    Ok(value::Value::List(res_id))
+}
+
+fn filter(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let elements = iterable_elements(interp, &args[0], "filter")?;
+    let res_id = root(interp, Vec::new());
+    root(interp, elements.clone());
+    for element in elements {
+        let keep = interp.call_from_native(&args[1], std::slice::from_ref(&element))?;
+        if !interp.is_falsey(&keep) {
+            interp.heap.get_list_elements_mut(res_id).push(element);
+        }
+    }
+    unroot(interp);
+    unroot(interp);
+    Ok(value::Value::List(res_id))
+}
+
+/// Folds the elements from the left, starting from `initial`.
+fn reduce(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let elements = iterable_elements(interp, &args[0], "reduce")?;
+    root(interp, elements.clone());
+    let mut acc = args[2].clone();
+    for element in elements {
+        // the accumulator is an argument of the call, so it's on the stack while it runs
+        acc = interp.call_from_native(&args[1], &[acc, element])?;
+    }
+    unroot(interp);
+    Ok(acc)
+}
+
+/*
+A stable merge sort that, unlike `sort_by`, lets the comparison fail, since it may call a firnas
+function or meet values that can't be ordered.
+ */
+fn merge_sort<F>(
+    interp: &mut virtual_machine::VirtualMachine,
+    elements: &mut Vec<value::Value>,
+    compare: &mut F,
+) -> Result<(), String>
+where
+    F: FnMut(
+        &mut virtual_machine::VirtualMachine,
+        &value::Value,
+        &value::Value,
+    ) -> Result<Ordering, String>,
+{
+    if elements.len() <= 1 {
+        return Ok(());
+    }
+
+    let mut right = elements.split_off(elements.len() / 2);
+    let mut left = std::mem::take(elements);
+    merge_sort(interp, &mut left, compare)?;
+    merge_sort(interp, &mut right, compare)?;
+
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+    while let (Some(l), Some(r)) = (left.peek(), right.peek()) {
+        // ties go to the left to keep the sort stable
+        if compare(interp, r, l)? == Ordering::Less {
+            elements.extend(right.next());
+        } else {
+            elements.extend(left.next());
+        }
+    }
+    elements.extend(left);
+    elements.extend(right);
+    Ok(())
+}
+
+fn sort_list<F>(
+    interp: &mut virtual_machine::VirtualMachine,
+    id: gc::HeapId,
+    mut compare: F,
+) -> Result<(), String>
+where
+    F: FnMut(
+        &mut virtual_machine::VirtualMachine,
+        &value::Value,
+        &value::Value,
+    ) -> Result<Ordering, String>,
+{
+    let mut elements = interp.heap.get_list_elements(id).clone();
+    root(interp, elements.clone());
+    merge_sort(interp, &mut elements, &mut compare)?;
+    unroot(interp);
+    *interp.heap.get_list_elements_mut(id) = elements;
+    Ok(())
+}
+
+/// Sorts a list in place: numbers and tuples in their natural order, strings as `<` orders
+/// them. `args[1]` is nil or a comparator that returns a negative number, zero or a positive
+/// number.
+fn sort(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let id = expect_list(interp, &args[0], "sort")?;
+    if !matches!(args[1], value::Value::Nil) {
+        let comparator = args[1].clone();
+        sort_list(interp, id, |interp, left, right| {
+            let res = interp.call_from_native(&comparator, &[left.clone(), right.clone()])?;
+            let res = expect_number(interp, &res)?.to_f64();
+            Ok(res.partial_cmp(&0.0).unwrap_or(Ordering::Equal))
+        })?;
+        return Ok(value::Value::Nil);
+    }
+    sort_list(interp, id, |interp, left, right| {
+        match interp.compare_values(left, right) {
+            Some(Some(ordering)) => Ok(ordering),
+            Some(None) => Err("Can't sort a list containing nan".to_string()),
+            None => Err(format!(
+                "Can't compare values of type {} and {}",
+                value::type_of(left).name(interp.language),
+                value::type_of(right).name(interp.language)
+            )),
+        }
+    })?;
+    Ok(value::Value::Nil)
+}
+
+fn use_collation(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    use arabic_utils::arabic_collation::Collation;
+
+    interp.collation = match super::string::expect_string(interp, &args[0])?.as_str() {
+        "arabic" | "عربي" => Collation::Arabic,
+        "codepoint" | "رموز" => Collation::CodePoint,
+        other => {
+            return Err(format!(
+                "Unknown collation '{}', expected {}",
+                other,
+                super::localized("'arabic' or 'codepoint'", "'عربي' أو 'رموز'")
+            ))
+        }
+    };
+    Ok(value::Value::Nil)
+}
+
+fn reverse(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let id = expect_list(interp, &args[0], "reverse")?;
+    interp.heap.get_list_elements_mut(id).reverse();
+    Ok(value::Value::Nil)
+}
+
+/// Pairs up the elements of two collections as tuples, stopping at the shorter one.
+fn zip(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let left = iterable_elements(interp, &args[0], "zip")?;
+    let right = iterable_elements(interp, &args[1], "zip")?;
+    let pairs = left
+        .into_iter()
+        .zip(right)
+        .map(|(l, r)| value::Value::Tuple(interp.heap.manage_tuple(vec![l, r])))
+        .collect();
+    Ok(value::Value::List(interp.heap.manage_list(pairs)))
+}
+
+fn enumerate(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let elements = iterable_elements(interp, &args[0], "enumerate")?;
+    let pairs = elements
+        .into_iter()
+        .enumerate()
+        .map(|(index, element)| {
+            value::Value::Tuple(
+                interp
+                    .heap
+                    .manage_tuple(vec![value::Value::Integer(index as i64), element]),
+            )
+        })
+        .collect();
+    Ok(value::Value::List(interp.heap.manage_list(pairs)))
+}
+
+/// The first element the predicate holds for.
+fn find_matching(
+    interp: &mut virtual_machine::VirtualMachine,
+    elements: Vec<value::Value>,
+    predicate: &value::Value,
+) -> Result<Option<value::Value>, String> {
+    root(interp, elements.clone());
+    for element in elements {
+        let res = interp.call_from_native(predicate, std::slice::from_ref(&element))?;
+        if !interp.is_falsey(&res) {
+            unroot(interp);
+            return Ok(Some(element));
+        }
+    }
+    unroot(interp);
+    Ok(None)
+}
+
+fn any(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let elements = iterable_elements(interp, &args[0], "any")?;
+    let found = find_matching(interp, elements, &args[1])?;
+    Ok(value::Value::Bool(found.is_some()))
+}
+
+fn all(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let elements = iterable_elements(interp, &args[0], "all")?;
+    root(interp, elements.clone());
+    for element in elements {
+        let res = interp.call_from_native(&args[1], &[element])?;
+        if interp.is_falsey(&res) {
+            unroot(interp);
+            return Ok(value::Value::Bool(false));
+        }
+    }
+    unroot(interp);
+    Ok(value::Value::Bool(true))
+}
+
+/// The first element of a collection, or grapheme of a string, that the predicate holds for,
+/// or nil.
+fn find(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let elements = iterable_elements(interp, &args[0], "find")?;
+    let found = find_matching(interp, elements, &args[1])?;
+    Ok(found.unwrap_or(value::Value::Nil))
+}
+
+/// The index of the first element equal to the value, or of the first occurrence of a
+/// substring, or -1.
+fn index_of(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let elements = match &args[0] {
+        value::Value::String(id) => {
+            let needle = string::expect_string(interp, &args[1])?;
+            let index = string::grapheme_position(interp.heap.get_str(*id), &needle)
+                .map_or(-1, |index| index as i64);
+            return Ok(value::Value::Integer(index));
+        }
+        value::Value::List(id) => interp.heap.get_list_elements(*id),
+        value::Value::Tuple(id) => interp.heap.get_tuple_elements(*id),
+        val => {
+            return Err(format!(
+                "Can't call indexOf on value of type {}",
+                value::type_of(val).name(interp.language)
+            ))
+        }
+    };
+    let index = elements
+        .iter()
+        .position(|element| interp.values_equal(element, &args[1]))
+        .map_or(-1, |index| index as i64);
+    Ok(value::Value::Integer(index))
+}
+
+fn push(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let id = expect_list(interp, &args[0], "push")?;
+    interp.heap.get_list_elements_mut(id).push(args[1].clone());
+    Ok(value::Value::Nil)
+}
+
+fn pop(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let id = expect_list(interp, &args[0], "pop")?;
+    interp
+        .heap
+        .get_list_elements_mut(id)
+        .pop()
+        .ok_or_else(|| "Can't pop from an empty list".to_string())
+}
+
+/// Inserts before the index; negative indices count from the end and out of range ones
+/// are clamped, as in slices.
+fn insert(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let id = expect_list(interp, &args[0], "insert")?;
+    let index: i64 = expect_integer(interp, &args[1])?;
+    let elements = interp.heap.get_list_elements_mut(id);
+    let len = elements.len() as i64;
+    let index = if index < 0 { index + len } else { index };
+    elements.insert(index.clamp(0, len) as usize, args[2].clone());
+    Ok(value::Value::Nil)
+}
+
+/// The longest list `range` makes, so a mistyped bound fails instead of exhausting memory.
+const MAX_RANGE_LEN: i64 = 100_000_000;
+
+/// The integers from `start` up to but not including `end`.
+fn range(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let start: i64 = expect_integer(interp, &args[0])?;
+    let end: i64 = expect_integer(interp, &args[1])?;
+    if i128::from(end) - i128::from(start) > i128::from(MAX_RANGE_LEN) {
+        return Err(format!(
+            "Range from {} to {} is too long, it can have at most {} elements",
+            start, end, MAX_RANGE_LEN
+        ));
+    }
+    let elements = (start..end).map(value::Value::Integer).collect();
+    Ok(value::Value::List(interp.heap.manage_list(elements)))
+}
+
+fn add(
     interp: &mut virtual_machine::VirtualMachine,
     args: &[value::Value],
 ) -> Result<value::Value, String> {
     match &args[0] {
-        value::Value::List(id) => {
-            let list_elements = interp.heap.get_list_elements(*id).clone();
-            let callable = args[1].clone();
-            for element in list_elements.iter() {
-                interp.stack.push(callable.clone());
-                interp.stack.push(element.clone());
-
-                // stash the current frame number if we're going to call a pure firnas function ...
-                let frame_idx = interp.frames.len();
-
-                if let Err(virtual_machine::VmError::Runtime(err)) =
-                    interp.call_value(callable.clone(), 1)
-                {
-                    return Err(err);
-                }
-
-                // If we're calling a pure firnas function, `interp.call_value` doesn't actually
-                // call the value, it just sets up a call frame. We loop the interpreter
-                // until it his an error or returns to the call frame with `frame_idx`.
-                // Unfortunately, this doesn't play well with our current debugger
-                // implementation, which manually calls `interpreter.step()`
-                loop {
-                    if interp.frames.len() == frame_idx {
-                        break;
-                    }
-
-                    if let Err(virtual_machine::VmError::Runtime(err)) = interp.step() {
-                        return Err(err);
-                    }
-                }
-            }
+        value::Value::Set(id) => {
+            let key = hash_key(interp, &args[1])?;
+            interp
+                .heap
+                .get_set_elements_mut(*id)
+                .insert(key, args[1].clone());
             Ok(value::Value::Nil)
         }
         val => Err(format!(
-            "Can't call forEach on value of type {:?}.",
-            value::type_of(val)
+            "Can't call add on value of type {}",
+            value::type_of(val).name(interp.language)
         )),
     }
 }
 
-pub fn map(
+/// Removes a set element, a dictionary key or the first equal element of a list, and
+/// says whether there was one.
+fn remove(
     interp: &mut virtual_machine::VirtualMachine,
     args: &[value::Value],
 ) -> Result<value::Value, String> {
-    match &args[1] {
+    match &args[0] {
         value::Value::List(id) => {
-            let list_elements = interp.heap.get_list_elements(*id).clone();
-            let callable = args[0].clone();
-            let mut res_elements = Vec::new();
-            for element in list_elements.iter() {
-                interp.stack.push(callable.clone());
-                interp.stack.push(element.clone());
-
-                //stash the current frame number if we're going to call a pure firnas function ...
-                let frame_idx = interp.frames.len();
-
-                if let Err(virtual_machine::VmError::Runtime(err)) =
-                    interp.call_value(callable.clone(), 1)
-                {
-                    return Err(err);
-                }
-
-                // If we're calling a pure firnas function, `interp.call_value` doesn't actually
-                // call the value, it just sets up a call frame. We loop the interpreter
-                // until it his an error or returns to the call frame with `frame_idx`.
-                // Unfortunately, this doesn't play well with our current debugger
-                // implementation, which manually calls `interpreter.step()`
-                loop {
-                    if interp.frames.len() == frame_idx {
-                        break;
-                    }
-
-                    if let Err(virtual_machine::VmError::Runtime(err)) = interp.step() {
-                        return Err(err);
-                    }
-                }
-
-                res_elements.push(interp.pop_stack());
+            let position = interp
+                .heap
+                .get_list_elements(*id)
+                .iter()
+                .position(|element| interp.values_equal(element, &args[1]));
+            if let Some(index) = position {
+                interp.heap.get_list_elements_mut(*id).remove(index);
             }
-            Ok(value::Value::List(interp.heap.manage_list(res_elements)))
+            Ok(value::Value::Bool(position.is_some()))
+        }
+        value::Value::Set(id) => {
+            let key = hash_key(interp, &args[1])?;
+            let removed = interp.heap.get_set_elements_mut(*id).remove(&key);
+            Ok(value::Value::Bool(removed.is_some()))
+        }
+        value::Value::Dict(id) => {
+            let key = hash_key(interp, &args[1])?;
+            let removed = interp.heap.get_dict_entries_mut(*id).remove(&key);
+            Ok(value::Value::Bool(removed.is_some()))
         }
         val => Err(format!(
-            "Can't call forEach on value of type {:?}.",
-            value::type_of(val)
+            "Can't call remove on value of type {}",
+            value::type_of(val).name(interp.language)
         )),
     }
 }
+
+fn contains(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    match &args[0] {
+        value::Value::List(id) => Ok(value::Value::Bool(
+            interp
+                .heap
+                .get_list_elements(*id)
+                .iter()
+                .any(|element| interp.values_equal(element, &args[1])),
+        )),
+        value::Value::Tuple(id) => Ok(value::Value::Bool(
+            interp
+                .heap
+                .get_tuple_elements(*id)
+                .iter()
+                .any(|element| interp.values_equal(element, &args[1])),
+        )),
+        value::Value::String(id) => {
+            let needle = string::expect_string(interp, &args[1])?;
+            Ok(value::Value::Bool(
+                string::grapheme_position(interp.heap.get_str(*id), &needle).is_some(),
+            ))
+        }
+        value::Value::Set(id) => {
+            // an unhashable value can't be in a set in the first place
+            let contained = match interp.hash_key(&args[1]) {
+                Some(key) => interp.heap.get_set_elements(*id).contains_key(&key),
+                None => false,
+            };
+            Ok(value::Value::Bool(contained))
+        }
+        value::Value::Dict(id) => {
+            let contained = match interp.hash_key(&args[1]) {
+                Some(key) => interp.heap.get_dict_entries(*id).contains_key(&key),
+                None => false,
+            };
+            Ok(value::Value::Bool(contained))
+        }
+        val => Err(format!(
+            "Can't call contains on value of type {}",
+            value::type_of(val).name(interp.language)
+        )),
+    }
+}
+
+/// The keys of a dictionary as a list, in the dictionary's order.
+fn keys(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    match &args[0] {
+        value::Value::Dict(id) => {
+            let keys = interp
+                .heap
+                .get_dict_entries(*id)
+                .values()
+                .map(|(key, _)| key.clone())
+                .collect();
+            Ok(value::Value::List(interp.heap.manage_list(keys)))
+        }
+        val => Err(format!(
+            "Can't call keys on value of type {}",
+            value::type_of(val).name(interp.language)
+        )),
+    }
+}
+
+fn values(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    match &args[0] {
+        value::Value::Dict(id) => {
+            let values = interp
+                .heap
+                .get_dict_entries(*id)
+                .values()
+                .map(|(_, val)| val.clone())
+                .collect();
+            Ok(value::Value::List(interp.heap.manage_list(values)))
+        }
+        val => Err(format!(
+            "Can't call values on value of type {}",
+            value::type_of(val).name(interp.language)
+        )),
+    }
+}
+
+fn hash_key(
+    interp: &virtual_machine::VirtualMachine,
+    val: &value::Value,
+) -> Result<value::HashKey, String> {
+    interp.hash_key(val).ok_or_else(|| {
+        format!(
+            "Unhashable value of type {}",
+            value::type_of(val).name(interp.language)
+        )
+    })
+}
@@ -13,8 +13,8 @@ pub(crate) fn dis_builtin(
             Ok(value::Value::Nil)
         }
         _ => Err(format!(
-            "Invalid call: expected firnas function, got {:?}.",
-            value::type_of(&args[0])
+            "Invalid call: expected firnas function, got {}",
+            value::type_of(&args[0]).name(interp.language)
         )),
     }
 }
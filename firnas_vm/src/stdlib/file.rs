@@ -0,0 +1,165 @@
+use super::string::expect_string;
+use super::StdFunc;
+use crate::value;
+use crate::virtual_machine;
+use std::fs;
+use std::io::Write;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+pub fn std_file_read() -> StdFunc {
+    StdFunc::native("readFile", "اقرأ_ملف", 1, read_file)
+}
+
+pub fn std_file_write() -> StdFunc {
+    StdFunc::native("writeFile", "اكتب_ملف", 2, write_file)
+}
+
+pub fn std_file_append() -> StdFunc {
+    StdFunc::native("appendFile", "ألحق_بملف", 2, append_file)
+}
+
+pub fn std_file_list_dir() -> StdFunc {
+    StdFunc::native("listDir", "محتويات_مجلد", 1, list_dir)
+}
+
+pub fn std_file_exists() -> StdFunc {
+    StdFunc::native("exists", "موجود", 1, exists)
+}
+
+pub fn std_file_delete() -> StdFunc {
+    StdFunc::native("deleteFile", "احذف_ملف", 1, delete_file)
+}
+
+/// What the file functions are allowed to touch.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum FileAccess {
+    /// Every file function fails, e.g. in the playground.
+    Denied,
+    /// Only relative paths inside the given directory, without `..` and without symlinks
+    /// leading out of it.
+    Within(PathBuf),
+    #[default]
+    Unrestricted,
+}
+
+fn resolve_path(
+    interp: &virtual_machine::VirtualMachine,
+    val: &value::Value,
+) -> Result<PathBuf, String> {
+    let path = PathBuf::from(expect_string(interp, val)?);
+
+    match &interp.file_access {
+        FileAccess::Denied => Err("File access is disabled".to_string()),
+        FileAccess::Unrestricted => Ok(path),
+        FileAccess::Within(root) => {
+            let escapes = path
+                .components()
+                .any(|component| !matches!(component, Component::Normal(_) | Component::CurDir));
+            let outside = || format!("Path '{}' is outside the allowed directory", path.display());
+            if escapes {
+                return Err(outside());
+            }
+            let root = fs::canonicalize(root).map_err(|err| io_error(root, err))?;
+            let resolved = resolve_links(&root.join(&path)).ok_or_else(outside)?;
+            if resolved.starts_with(&root) {
+                Ok(resolved)
+            } else {
+                Err(outside())
+            }
+        }
+    }
+}
+
+/// `path` with every symlink in it followed. Only its existing part is canonicalized, so a
+/// file that is about to be written resolves through its directory. `None` when a link in it
+/// is dangling, since writing through it would create the link's target.
+fn resolve_links(path: &Path) -> Option<PathBuf> {
+    let mut existing = path;
+    let mut rest = Vec::new();
+    while existing.symlink_metadata().is_err() {
+        rest.push(existing.file_name()?);
+        existing = existing.parent()?;
+    }
+    let mut resolved = fs::canonicalize(existing).ok()?;
+    resolved.extend(rest.into_iter().rev());
+    Some(resolved)
+}
+
+fn io_error(path: &Path, err: std::io::Error) -> String {
+    format!("{}: {}", path.display(), err)
+}
+
+fn read_file(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let path = resolve_path(interp, &args[0])?;
+    let content = fs::read_to_string(&path).map_err(|err| io_error(&path, err))?;
+    Ok(value::Value::String(interp.heap.manage_str(content)))
+}
+
+fn write_file(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let path = resolve_path(interp, &args[0])?;
+    let content = expect_string(interp, &args[1])?;
+    fs::write(&path, content).map_err(|err| io_error(&path, err))?;
+    Ok(value::Value::Nil)
+}
+
+fn append_file(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let path = resolve_path(interp, &args[0])?;
+    let content = expect_string(interp, &args[1])?;
+    fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| file.write_all(content.as_bytes()))
+        .map_err(|err| io_error(&path, err))?;
+    Ok(value::Value::Nil)
+}
+
+/// The names of the entries in a directory, sorted.
+fn list_dir(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let path = resolve_path(interp, &args[0])?;
+    let mut names = fs::read_dir(&path)
+        .and_then(|entries| {
+            entries
+                .map(|entry| entry.map(|entry| entry.file_name().to_string_lossy().into_owned()))
+                .collect::<Result<Vec<String>, _>>()
+        })
+        .map_err(|err| io_error(&path, err))?;
+    names.sort();
+
+    let elements = names
+        .into_iter()
+        .map(|name| value::Value::String(interp.heap.manage_str(name)))
+        .collect::<Vec<value::Value>>();
+    Ok(value::Value::List(interp.heap.manage_list(elements)))
+}
+
+fn exists(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let path = resolve_path(interp, &args[0])?;
+    Ok(value::Value::Bool(path.exists()))
+}
+
+fn delete_file(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let path = resolve_path(interp, &args[0])?;
+    fs::remove_file(&path).map_err(|err| io_error(&path, err))?;
+    Ok(value::Value::Nil)
+}
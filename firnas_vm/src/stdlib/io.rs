@@ -2,22 +2,137 @@ use super::StdFunc;
 use crate::value;
 use crate::value::NativeFunction;
 use crate::virtual_machine;
+use firnas_compiler::diagnostic;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::io::BufRead;
+use std::io::Read;
+use std::io::Write;
 
+/// Text reaches `print` and `println` with the implementation's output mode already applied.
 pub trait StdIO {
     fn print(&self, content: &str);
     fn println(&self, content: &str);
+    fn mode(&self) -> OutputMode {
+        OutputMode::Plain
+    }
+    /// The next line of input without its line ending, or `None` at the end of input.
+    fn read_line(&self) -> Option<String>;
+    /// Everything left in the input.
+    fn read_all(&self) -> String;
+}
+
+/// How text is written to the terminal.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputMode {
+    #[default]
+    Plain,
+    /// English words and western numbers in lines holding Arabic are wrapped in directional
+    /// isolates, so terminals don't reorder them with the Arabic around them.
+    IsolateLtr,
+}
+
+impl OutputMode {
+    pub fn apply(self, content: &str) -> String {
+        match self {
+            OutputMode::Plain => content.to_string(),
+            OutputMode::IsolateLtr => arabic_utils::arabic_bidi::isolate_ltr_runs(content),
+        }
+    }
 }
 
-pub struct DefaultStdIO;
+#[derive(Default)]
+pub struct DefaultStdIO {
+    pub mode: OutputMode,
+}
 
 impl StdIO for DefaultStdIO {
     fn print(&self, content: &str) {
         print!("{content}");
+        // a prompt printed before reading input has no newline to flush it
+        let _ = std::io::stdout().flush();
     }
 
     fn println(&self, content: &str) {
         println!("{content}");
     }
+
+    fn mode(&self) -> OutputMode {
+        self.mode
+    }
+
+    fn read_line(&self) -> Option<String> {
+        let mut line = String::new();
+        match std::io::stdin().lock().read_line(&mut line) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(trim_line_ending(line)),
+        }
+    }
+
+    fn read_all(&self) -> String {
+        let mut content = String::new();
+        let _ = std::io::stdin().lock().read_to_string(&mut content);
+        content
+    }
+}
+
+/// Prints like `DefaultStdIO` but reads from a fixed script, for tests and embedders.
+pub struct ScriptedStdIO {
+    input: RefCell<VecDeque<String>>,
+    pub mode: OutputMode,
+}
+
+impl ScriptedStdIO {
+    pub fn new(lines: &[&str]) -> ScriptedStdIO {
+        ScriptedStdIO {
+            input: RefCell::new(lines.iter().map(|line| line.to_string()).collect()),
+            mode: OutputMode::default(),
+        }
+    }
+}
+
+impl StdIO for ScriptedStdIO {
+    fn print(&self, content: &str) {
+        print!("{content}");
+    }
+
+    fn println(&self, content: &str) {
+        println!("{content}");
+    }
+
+    fn mode(&self) -> OutputMode {
+        self.mode
+    }
+
+    fn read_line(&self) -> Option<String> {
+        self.input.borrow_mut().pop_front()
+    }
+
+    fn read_all(&self) -> String {
+        self.input
+            .borrow_mut()
+            .drain(..)
+            .map(|line| format!("{line}\n"))
+            .collect()
+    }
+}
+
+fn trim_line_ending(mut line: String) -> String {
+    if line.ends_with('\n') {
+        line.pop();
+        if line.ends_with('\r') {
+            line.pop();
+        }
+    }
+    line
+}
+
+pub fn std_io_input() -> StdFunc {
+    StdFunc::native("input", "ادخل", 1, input)
+}
+
+pub fn std_io_read_all() -> StdFunc {
+    StdFunc::native("readAll", "اقرأ_الكل", 0, read_all)
 }
 
 pub fn std_io_print_line() -> StdFunc {
@@ -31,6 +146,7 @@ pub fn std_io_print_line() -> StdFunc {
         name: name.clone(),
         func: value::Value::NativeFunction(NativeFunction {
             arity: 1,
+            optional: 0,
             name,
             func: print_line,
         }),
@@ -49,6 +165,7 @@ pub fn std_io_print() -> StdFunc {
         name: name.clone(),
         func: value::Value::NativeFunction(NativeFunction {
             arity: 1,
+            optional: 0,
             name,
             func: print,
         }),
@@ -59,59 +176,9 @@ fn print_line(
     vm: &mut virtual_machine::VirtualMachine,
     args: &[value::Value],
 ) -> Result<value::Value, String> {
-    match &args[0] {
-        value::Value::String(id) => {
-            let output = vm.heap.get_str(*id).clone();
-            vm.push_output(output.clone());
-            vm.std_io.println(&output);
-        }
-        value::Value::Number(num) => {
-            let output: String = make_number(*num);
-            vm.push_output(output.clone());
-            vm.std_io.println(&output);
-        }
-        value::Value::Bool(b) => {
-            let output = make_bool(*b);
-            vm.push_output(output.clone());
-            vm.std_io.println(&output);
-        }
-        value::Value::Nil => {
-            let output = make_nil();
-            vm.push_output(output.clone());
-            vm.std_io.println(&output);
-        }
-        value::Value::Function(id) => {
-            let output = format!("<fn '{}'>", vm.heap.get_closure(*id).clone().function.name);
-            vm.push_output(output.clone());
-            vm.std_io.println(&output);
-        }
-        value::Value::Instance(id) => {
-            let instance = vm.heap.get_instance(*id);
-            let class_name = &vm.heap.get_class(instance.class_id).name;
-            let output = format!("<{} instance>", class_name);
-            vm.push_output(output.clone());
-            vm.std_io.println(&output);
-        }
-        value::Value::BoundMethod(id) => {
-            let bound_method = vm.heap.get_bound_method(*id);
-            let instance = vm.heap.get_instance(bound_method.instance_id);
-            let class_name = &vm.heap.get_class(instance.class_id).name;
-            let output = format!("<bound method of {} instance>", class_name);
-            vm.push_output(output.clone());
-            vm.std_io.println(&output);
-        }
-        value::Value::Class(id) => {
-            let output = format!("<class '{}'>", vm.heap.get_class(*id).name);
-            vm.push_output(output.clone());
-            vm.std_io.println(&output);
-        }
-        value::Value::NativeFunction(func) => {
-            let output = format!("<native fn {}>", func.name);
-            vm.push_output(output.clone());
-            vm.std_io.println(&output);
-        }
-        value::Value::List(_) => todo!(),
-    };
+    let output = vm.std_io.mode().apply(&vm.format_val(&args[0]));
+    vm.push_output(output.clone());
+    vm.std_io.println(&output);
     Ok(value::Value::Nil)
 }
 
@@ -119,67 +186,53 @@ fn print(
     vm: &mut virtual_machine::VirtualMachine,
     args: &[value::Value],
 ) -> Result<value::Value, String> {
-    match &args[0] {
-        value::Value::String(id) => {
-            let output = vm.heap.get_str(*id).clone();
-            vm.push_output(output.clone());
-            vm.std_io.print(&output);
-        }
-        value::Value::Number(num) => {
-            let output: String = make_number(*num);
-            vm.push_output(output.clone());
-            vm.std_io.print(&output);
-        }
-        value::Value::Bool(b) => {
-            let output = make_bool(*b);
-            vm.push_output(output.clone());
-            vm.std_io.print(&output);
-        }
-        value::Value::Nil => {
-            let output = make_nil();
-            vm.push_output(output.clone());
-            vm.std_io.print(&output);
-        }
-        value::Value::Function(id) => {
-            let output = format!("<fn '{}'>", vm.heap.get_closure(*id).clone().function.name);
-            vm.push_output(output.clone());
-            vm.std_io.print(&output);
-        }
-        value::Value::Instance(id) => {
-            let instance = vm.heap.get_instance(*id);
-            let class_name = &vm.heap.get_class(instance.class_id).name;
-            let output = format!("<{} instance>", class_name);
-            vm.push_output(output.clone());
-            vm.std_io.print(&output);
-        }
-        value::Value::BoundMethod(id) => {
-            let bound_method = vm.heap.get_bound_method(*id);
-            let instance = vm.heap.get_instance(bound_method.instance_id);
-            let class_name = &vm.heap.get_class(instance.class_id).name;
-            let output = format!("<bound method of {} instance>", class_name);
-            vm.push_output(output.clone());
-            vm.std_io.print(&output);
-        }
-        value::Value::Class(id) => {
-            let output = format!("<class '{}'>", vm.heap.get_class(*id).name);
-            vm.push_output(output.clone());
-            vm.std_io.print(&output);
-        }
-        value::Value::NativeFunction(func) => {
-            let output = format!("<native fn {}>", func.name);
-            vm.push_output(output.clone());
-            vm.std_io.print(&output);
+    let output = vm.std_io.mode().apply(&vm.format_val(&args[0]));
+    vm.push_output(output.clone());
+    vm.std_io.print(&output);
+    Ok(value::Value::Nil)
+}
+
+/// Prints the prompt, if any, and returns the next line of input, or nil at the end of input.
+fn input(
+    vm: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let prompt = match &args[0] {
+        value::Value::String(id) => vm.heap.get_str(*id).clone(),
+        value::Value::Nil => String::new(),
+        val => {
+            return Err(diagnostic::EXPECTED_STRING_ARGUMENT
+                .render(vm.language, &[&value::type_of(val).name(vm.language)]))
         }
-        value::Value::List(_) => todo!(),
     };
-    Ok(value::Value::Nil)
+    if !prompt.is_empty() {
+        let prompt = vm.std_io.mode().apply(&prompt);
+        vm.push_output(prompt.clone());
+        vm.std_io.print(&prompt);
+    }
+
+    match vm.std_io.read_line() {
+        Some(line) => Ok(value::Value::String(vm.heap.manage_str(line))),
+        None => Ok(value::Value::Nil),
+    }
+}
+
+fn read_all(
+    vm: &mut virtual_machine::VirtualMachine,
+    _args: &[value::Value],
+) -> Result<value::Value, String> {
+    let content = vm.std_io.read_all();
+    Ok(value::Value::String(vm.heap.manage_str(content)))
 }
 
-fn make_number(num: f64) -> String {
+#[cfg_attr(not(feature = "ar"), allow(unused_variables))]
+pub(crate) fn make_number(vm: &virtual_machine::VirtualMachine, num: f64) -> String {
     #[cfg(feature = "ar")]
     {
         use arabic_utils::arabic_number::ArabicNumber;
-        num.to_arabic_decimal().unwrap()
+        use arabic_utils::arabic_number::NumberFormat;
+        num.to_arabic_decimal_with(NumberFormat::default().with_digits(vm.digits))
+            .unwrap()
     }
     #[cfg(not(feature = "ar"))]
     {
@@ -187,7 +240,34 @@ fn make_number(num: f64) -> String {
     }
 }
 
-fn make_bool(b: bool) -> String {
+#[cfg_attr(not(feature = "ar"), allow(unused_variables))]
+pub(crate) fn make_integer(vm: &virtual_machine::VirtualMachine, num: i64) -> String {
+    #[cfg(feature = "ar")]
+    {
+        use arabic_utils::arabic_number::ArabicNumber;
+        use arabic_utils::arabic_number::NumberFormat;
+        num.to_arabic_decimal_with(NumberFormat::default().with_digits(vm.digits))
+            .unwrap()
+    }
+    #[cfg(not(feature = "ar"))]
+    {
+        format!("{num}")
+    }
+}
+
+#[cfg_attr(not(feature = "ar"), allow(unused_variables))]
+pub(crate) fn make_digits(vm: &virtual_machine::VirtualMachine, digits: String) -> String {
+    #[cfg(feature = "ar")]
+    {
+        vm.digits.localize(&digits)
+    }
+    #[cfg(not(feature = "ar"))]
+    {
+        digits
+    }
+}
+
+pub(crate) fn make_bool(b: bool) -> String {
     let (t, f) = if cfg!(feature = "ar") {
         (String::from("صح"), String::from("خطا"))
     } else {
@@ -199,7 +279,7 @@ fn make_bool(b: bool) -> String {
     }
 }
 
-fn make_nil() -> String {
+pub(crate) fn make_nil() -> String {
     if cfg!(feature = "ar") {
         String::from("عدم")
     } else {
@@ -0,0 +1,526 @@
+use super::localized;
+use super::string::expect_string;
+use super::StdFunc;
+use super::StdModule;
+use crate::gc;
+use crate::numeric::Numeric;
+use crate::value;
+use crate::virtual_machine;
+use arabic_utils::arabic_str::ArabicStr;
+use std::collections::BTreeMap;
+
+/// How deep arrays and objects may nest, so that deep input fails instead of overflowing the
+/// stack.
+const MAX_DEPTH: usize = 512;
+
+/// The widest indent `stringify` takes, as in JavaScript's `JSON.stringify`.
+const MAX_INDENT: i64 = 10;
+
+pub fn std_json() -> StdModule {
+    StdModule::new(
+        "json",
+        "جيسون",
+        vec![
+            StdFunc::native("parse", "حلل", 1, parse),
+            StdFunc::native("stringify", "الى_نص", 1, stringify).with_optional(1),
+        ],
+    )
+}
+
+/// Objects become dictionaries, arrays become lists and every number becomes a float.
+fn parse(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let text = expect_string(interp, &args[0])?;
+    let mut parser = Parser {
+        chars: text.chars().collect(),
+        pos: 0,
+        depth: 0,
+        interp,
+    };
+
+    parser.skip_whitespace();
+    let res = parser.parse_value()?;
+    parser.skip_whitespace();
+    if parser.pos < parser.chars.len() {
+        return Err(parser.error("unexpected trailing characters"));
+    }
+    Ok(res)
+}
+
+/// Dictionaries keep their entries sorted by key, so objects are written with their keys in
+/// order rather than in the order they were parsed or added in. `args[1]` is nil or a
+/// dictionary of options; `indent` puts every nested item on its own line indented by that
+/// many spaces, from 0 to 10.
+fn stringify(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let indent = stringify_indent(interp, &args[1])?;
+    let mut writer = Writer {
+        interp,
+        indent,
+        visiting: Vec::new(),
+        out: String::new(),
+    };
+    writer.write_value(&args[0], 0)?;
+    let out = writer.out;
+    Ok(value::Value::String(interp.heap.manage_str(out)))
+}
+
+fn stringify_indent(
+    interp: &virtual_machine::VirtualMachine,
+    options: &value::Value,
+) -> Result<Option<usize>, String> {
+    let id = match options {
+        value::Value::Nil => return Ok(None),
+        value::Value::Dict(id) => *id,
+        val => {
+            return Err(format!(
+                "Invalid call: expected a dictionary of options, got {}",
+                value::type_of(val).name(interp.language)
+            ))
+        }
+    };
+
+    let indent_option = localized("indent", "إزاحة").normalize_arabic();
+    let mut indent = None;
+    for (key, val) in interp.heap.get_dict_entries(id).values() {
+        let key = match key {
+            value::Value::String(key_id) => interp.heap.get_str(*key_id),
+            _ => {
+                return Err(format!(
+                    "Invalid call: option names must be strings, got {}",
+                    value::type_of(key).name(interp.language)
+                ))
+            }
+        };
+        if key.normalize_arabic() != indent_option {
+            return Err(format!("Invalid call: unknown option '{}'", key));
+        }
+        indent = match val {
+            value::Value::Integer(indent) if (0..=MAX_INDENT).contains(indent) => {
+                Some(*indent as usize)
+            }
+            value::Value::Integer(indent) => {
+                return Err(format!(
+                    "Invalid call: indent must be from 0 to {}, got {}",
+                    MAX_INDENT, indent
+                ))
+            }
+            val => {
+                return Err(format!(
+                    "Invalid call: expected an integer indent, got {}",
+                    value::type_of(val).name(interp.language)
+                ))
+            }
+        };
+    }
+    Ok(indent)
+}
+
+struct Parser<'a> {
+    chars: Vec<char>,
+    pos: usize,
+    /// The arrays and objects the parser is inside of.
+    depth: usize,
+    interp: &'a mut virtual_machine::VirtualMachine,
+}
+
+impl Parser<'_> {
+    fn error(&self, what: &str) -> String {
+        format!("Invalid JSON at position {}: {}", self.pos, what)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(' ' | '\t' | '\n' | '\r') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        if self.peek() == Some(expected) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", expected)))
+        }
+    }
+
+    fn expect_word(&mut self, word: &str, val: value::Value) -> Result<value::Value, String> {
+        let end = self.pos + word.chars().count();
+        if end <= self.chars.len() && self.chars[self.pos..end].iter().copied().eq(word.chars()) {
+            self.pos = end;
+            Ok(val)
+        } else {
+            Err(self.error("unexpected character"))
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<value::Value, String> {
+        match self.peek() {
+            Some(c @ ('{' | '[')) => {
+                if self.depth == MAX_DEPTH {
+                    return Err(self.error("nested too deeply"));
+                }
+                self.depth += 1;
+                let res = if c == '{' {
+                    self.parse_object()
+                } else {
+                    self.parse_array()
+                };
+                self.depth -= 1;
+                res
+            }
+            Some('"') => {
+                let s = self.parse_string()?;
+                Ok(value::Value::String(self.interp.heap.manage_str(s)))
+            }
+            Some('t') => self.expect_word("true", value::Value::Bool(true)),
+            Some('f') => self.expect_word("false", value::Value::Bool(false)),
+            Some('n') => self.expect_word("null", value::Value::Nil),
+            Some('-' | '0'..='9') => self.parse_number(),
+            Some(_) => Err(self.error("unexpected character")),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<value::Value, String> {
+        self.expect('{')?;
+        let mut entries = BTreeMap::new();
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.pos += 1;
+            return Ok(value::Value::Dict(self.interp.heap.manage_dict(entries)));
+        }
+
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some('"') {
+                return Err(self.error("expected a string key"));
+            }
+            let key = self.parse_string()?;
+            self.skip_whitespace();
+            self.expect(':')?;
+            self.skip_whitespace();
+            let val = self.parse_value()?;
+            let key_val = value::Value::String(self.interp.heap.manage_str(key.clone()));
+            entries.insert(value::HashKey::String(key), (key_val, val));
+
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some('}') => {
+                    self.pos += 1;
+                    break;
+                }
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+        Ok(value::Value::Dict(self.interp.heap.manage_dict(entries)))
+    }
+
+    fn parse_array(&mut self) -> Result<value::Value, String> {
+        self.expect('[')?;
+        let mut elements = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.pos += 1;
+            return Ok(value::Value::List(self.interp.heap.manage_list(elements)));
+        }
+
+        loop {
+            self.skip_whitespace();
+            elements.push(self.parse_value()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some(']') => {
+                    self.pos += 1;
+                    break;
+                }
+                _ => return Err(self.error("expected ',' or ']'")),
+            }
+        }
+        Ok(value::Value::List(self.interp.heap.manage_list(elements)))
+    }
+
+    fn parse_string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut res = String::new();
+        loop {
+            match self.peek() {
+                Some('"') => {
+                    self.pos += 1;
+                    return Ok(res);
+                }
+                Some('\\') => {
+                    self.pos += 1;
+                    let escaped = match self.peek() {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('/') => '/',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('u') => {
+                            self.pos += 1;
+                            res.push(self.parse_unicode_escape()?);
+                            continue;
+                        }
+                        _ => return Err(self.error("invalid escape")),
+                    };
+                    self.pos += 1;
+                    res.push(escaped);
+                }
+                Some(c) if c < ' ' => return Err(self.error("control character in string")),
+                Some(c) => {
+                    self.pos += 1;
+                    res.push(c);
+                }
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+
+    /// The four hex digits after `\u`, combining a surrogate pair into one character.
+    fn parse_unicode_escape(&mut self) -> Result<char, String> {
+        let high = self.parse_hex4()?;
+        if !(0xD800..0xDC00).contains(&high) {
+            return char::from_u32(high).ok_or_else(|| self.error("invalid unicode escape"));
+        }
+
+        if self.peek() != Some('\\') || self.chars.get(self.pos + 1) != Some(&'u') {
+            return Err(self.error("unpaired surrogate"));
+        }
+        self.pos += 2;
+        let low = self.parse_hex4()?;
+        if !(0xDC00..0xE000).contains(&low) {
+            return Err(self.error("unpaired surrogate"));
+        }
+        char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00))
+            .ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    fn parse_hex4(&mut self) -> Result<u32, String> {
+        if self.pos + 4 > self.chars.len() {
+            return Err(self.error("invalid unicode escape"));
+        }
+        let digits: String = self.chars[self.pos..self.pos + 4].iter().collect();
+        let code =
+            u32::from_str_radix(&digits, 16).map_err(|_| self.error("invalid unicode escape"))?;
+        self.pos += 4;
+        Ok(code)
+    }
+
+    fn parse_number(&mut self) -> Result<value::Value, String> {
+        let start = self.pos;
+        while let Some('-' | '+' | '.' | 'e' | 'E' | '0'..='9') = self.peek() {
+            self.pos += 1;
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+        if !is_json_number(&text) {
+            self.pos = start;
+            return Err(self.error("invalid number"));
+        }
+        text.parse::<f64>()
+            .map(value::Value::Number)
+            .map_err(|_| self.error("invalid number"))
+    }
+}
+
+/// Rust accepts more than JSON does, e.g. `+1`, `01` and `1.`.
+fn is_json_number(text: &str) -> bool {
+    let digits = text.strip_prefix('-').unwrap_or(text);
+    let (mantissa, exponent) = match digits.find(['e', 'E']) {
+        Some(idx) => (&digits[..idx], Some(&digits[idx + 1..])),
+        None => (digits, None),
+    };
+    let (whole, fraction) = match mantissa.split_once('.') {
+        Some((whole, fraction)) => (whole, Some(fraction)),
+        None => (mantissa, None),
+    };
+
+    let all_digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    let whole_ok = whole == "0" || (all_digits(whole) && !whole.starts_with('0'));
+    let fraction_ok = fraction.is_none_or(all_digits);
+    let exponent_ok = exponent
+        .is_none_or(|exponent| all_digits(exponent.strip_prefix(['+', '-']).unwrap_or(exponent)));
+    whole_ok && fraction_ok && exponent_ok
+}
+
+struct Writer<'a> {
+    interp: &'a virtual_machine::VirtualMachine,
+    indent: Option<usize>,
+    /// The containers between the root and the value being written.
+    visiting: Vec<gc::HeapId>,
+    out: String,
+}
+
+impl Writer<'_> {
+    fn write_value(&mut self, val: &value::Value, depth: usize) -> Result<(), String> {
+        match val {
+            value::Value::Nil => self.out.push_str("null"),
+            value::Value::Bool(b) => self.out.push_str(&b.to_string()),
+            value::Value::String(id) => {
+                let s = self.interp.heap.get_str(*id).clone();
+                self.write_string(&s);
+            }
+            value::Value::Number(_)
+            | value::Value::Integer(_)
+            | value::Value::BigInt(_)
+            | value::Value::Rational(_) => {
+                let num = Numeric::from_value(&self.interp.heap, val).unwrap();
+                self.write_number(num)?;
+            }
+            value::Value::List(id) => {
+                let elements = self.interp.heap.get_list_elements(*id).clone();
+                self.write_array(*id, &elements, depth)?;
+            }
+            value::Value::Tuple(id) => {
+                let elements = self.interp.heap.get_tuple_elements(*id).clone();
+                self.write_array(*id, &elements, depth)?;
+            }
+            value::Value::Set(id) => {
+                let elements = self
+                    .interp
+                    .heap
+                    .get_set_elements(*id)
+                    .values()
+                    .cloned()
+                    .collect::<Vec<value::Value>>();
+                self.write_array(*id, &elements, depth)?;
+            }
+            value::Value::Dict(id) => self.write_object(*id, depth)?,
+            _ => {
+                return Err(format!(
+                    "Can't convert value of type {} to JSON",
+                    value::type_of(val).name(self.interp.language)
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    fn write_number(&mut self, num: Numeric) -> Result<(), String> {
+        match num {
+            Numeric::Integer(num) => self.out.push_str(&num.to_string()),
+            Numeric::BigInt(num) => self.out.push_str(&num.to_string()),
+            num => {
+                let num = num.to_f64();
+                if !num.is_finite() {
+                    return Err(format!("Can't convert {} to JSON", num));
+                }
+                self.out.push_str(&num.to_string());
+            }
+        }
+        Ok(())
+    }
+
+    fn write_string(&mut self, s: &str) {
+        self.out.push('"');
+        for c in s.chars() {
+            match c {
+                '"' => self.out.push_str("\\\""),
+                '\\' => self.out.push_str("\\\\"),
+                '\n' => self.out.push_str("\\n"),
+                '\r' => self.out.push_str("\\r"),
+                '\t' => self.out.push_str("\\t"),
+                '\u{8}' => self.out.push_str("\\b"),
+                '\u{c}' => self.out.push_str("\\f"),
+                c if c < ' ' => self.out.push_str(&format!("\\u{:04x}", c as u32)),
+                c => self.out.push(c),
+            }
+        }
+        self.out.push('"');
+    }
+
+    fn enter(&mut self, id: gc::HeapId) -> Result<(), String> {
+        if self.visiting.contains(&id) {
+            return Err("Can't convert a value that contains itself to JSON".to_string());
+        }
+        if self.visiting.len() == MAX_DEPTH {
+            return Err("Can't convert a value nested too deeply to JSON".to_string());
+        }
+        self.visiting.push(id);
+        Ok(())
+    }
+
+    fn write_array(
+        &mut self,
+        id: gc::HeapId,
+        elements: &[value::Value],
+        depth: usize,
+    ) -> Result<(), String> {
+        self.enter(id)?;
+        self.out.push('[');
+        for (i, element) in elements.iter().enumerate() {
+            if i > 0 {
+                self.out.push(',');
+            }
+            self.write_newline(depth + 1);
+            self.write_value(element, depth + 1)?;
+        }
+        if !elements.is_empty() {
+            self.write_newline(depth);
+        }
+        self.out.push(']');
+        self.visiting.pop();
+        Ok(())
+    }
+
+    fn write_object(&mut self, id: gc::HeapId, depth: usize) -> Result<(), String> {
+        self.enter(id)?;
+        let entries = self
+            .interp
+            .heap
+            .get_dict_entries(id)
+            .values()
+            .cloned()
+            .collect::<Vec<(value::Value, value::Value)>>();
+
+        self.out.push('{');
+        for (i, (key, val)) in entries.iter().enumerate() {
+            let key = match key {
+                value::Value::String(key_id) => self.interp.heap.get_str(*key_id).clone(),
+                _ => {
+                    return Err(format!(
+                        "JSON object keys must be strings, got {}",
+                        value::type_of(key).name(self.interp.language)
+                    ))
+                }
+            };
+            if i > 0 {
+                self.out.push(',');
+            }
+            self.write_newline(depth + 1);
+            self.write_string(&key);
+            self.out.push(':');
+            if self.indent.is_some() {
+                self.out.push(' ');
+            }
+            self.write_value(val, depth + 1)?;
+        }
+        if !entries.is_empty() {
+            self.write_newline(depth);
+        }
+        self.out.push('}');
+        self.visiting.pop();
+        Ok(())
+    }
+
+    /// Starts a new line at `depth` when pretty printing, and does nothing otherwise.
+    fn write_newline(&mut self, depth: usize) {
+        if let Some(indent) = self.indent {
+            self.out.push('\n');
+            self.out.push_str(&" ".repeat(indent * depth));
+        }
+    }
+}
@@ -1,7 +1,18 @@
+use super::number::expect_integer;
+use super::number::expect_number;
 use super::StdFunc;
+use crate::numeric;
+use crate::numeric::Numeric;
 use crate::value;
 use crate::value::NativeFunction;
 use crate::virtual_machine;
+use firnas_compiler::diagnostic;
+use num_bigint::BigInt;
+use num_bigint::Sign;
+use num_integer::Integer;
+use num_traits::ToPrimitive;
+use num_traits::Zero;
+use std::cmp::Ordering;
 
 pub fn std_math_exp() -> StdFunc {
     let name = if cfg!(feature = "ar") {
@@ -14,6 +25,7 @@ pub fn std_math_exp() -> StdFunc {
         name: name.clone(),
         func: value::Value::NativeFunction(NativeFunction {
             arity: 1,
+            optional: 0,
             name,
             func: exp,
         }),
@@ -31,6 +43,7 @@ pub fn std_math_sqrt() -> StdFunc {
         name: name.clone(),
         func: value::Value::NativeFunction(NativeFunction {
             arity: 1,
+            optional: 0,
             name,
             func: sqrt,
         }),
@@ -38,27 +51,419 @@ pub fn std_math_sqrt() -> StdFunc {
 }
 
 fn exp(
-    _interp: &mut virtual_machine::VirtualMachine,
+    interp: &mut virtual_machine::VirtualMachine,
     args: &[value::Value],
 ) -> Result<value::Value, String> {
-    match args[0] {
-        value::Value::Number(num) => Ok(value::Value::Number(num.exp())),
-        _ => Err(format!(
-            "Invalid call: expected number, got {:?}.",
-            value::type_of(&args[0])
+    match numeric::Numeric::from_value(&interp.heap, &args[0]) {
+        Some(num) => Ok(value::Value::Number(num.to_f64().exp())),
+        _ => Err(diagnostic::EXPECTED_NUMBER_ARGUMENT.render(
+            interp.language,
+            &[&value::type_of(&args[0]).name(interp.language)],
         )),
     }
 }
 
 fn sqrt(
-    _interp: &mut virtual_machine::VirtualMachine,
+    interp: &mut virtual_machine::VirtualMachine,
     args: &[value::Value],
 ) -> Result<value::Value, String> {
-    match args[0] {
-        value::Value::Number(num) => Ok(value::Value::Number(num.sqrt())),
-        _ => Err(format!(
-            "Invalid call: expected number, got {:?}.",
-            value::type_of(&args[0])
+    match numeric::Numeric::from_value(&interp.heap, &args[0]) {
+        Some(num) => Ok(value::Value::Number(num.to_f64().sqrt())),
+        _ => Err(diagnostic::EXPECTED_NUMBER_ARGUMENT.render(
+            interp.language,
+            &[&value::type_of(&args[0]).name(interp.language)],
         )),
     }
 }
+
+pub fn std_math_sin() -> StdFunc {
+    StdFunc::native("sin", "جا", 1, sin)
+}
+
+pub fn std_math_cos() -> StdFunc {
+    StdFunc::native("cos", "جتا", 1, cos)
+}
+
+pub fn std_math_tan() -> StdFunc {
+    StdFunc::native("tan", "ظا", 1, tan)
+}
+
+pub fn std_math_asin() -> StdFunc {
+    StdFunc::native("asin", "قوس_جا", 1, asin)
+}
+
+pub fn std_math_acos() -> StdFunc {
+    StdFunc::native("acos", "قوس_جتا", 1, acos)
+}
+
+pub fn std_math_atan() -> StdFunc {
+    StdFunc::native("atan", "قوس_ظا", 1, atan)
+}
+
+pub fn std_math_atan2() -> StdFunc {
+    StdFunc::native("atan2", "قوس_ظا٢", 2, atan2)
+}
+
+pub fn std_math_log() -> StdFunc {
+    StdFunc::native("log", "لوغاريتم", 1, log)
+}
+
+pub fn std_math_log10() -> StdFunc {
+    StdFunc::native("log10", "لوغاريتم١٠", 1, log10)
+}
+
+pub fn std_math_log2() -> StdFunc {
+    StdFunc::native("log2", "لوغاريتم٢", 1, log2)
+}
+
+pub fn std_math_pow() -> StdFunc {
+    StdFunc::native("pow", "أس", 2, pow)
+}
+
+pub fn std_math_abs() -> StdFunc {
+    StdFunc::native("abs", "مطلق", 1, abs)
+}
+
+pub fn std_math_floor() -> StdFunc {
+    StdFunc::native("floor", "أرضية", 1, floor)
+}
+
+pub fn std_math_ceil() -> StdFunc {
+    StdFunc::native("ceil", "سقف", 1, ceil)
+}
+
+pub fn std_math_round() -> StdFunc {
+    StdFunc::native("round", "تقريب", 1, round)
+}
+
+pub fn std_math_trunc() -> StdFunc {
+    StdFunc::native("trunc", "بتر", 1, trunc)
+}
+
+pub fn std_math_min() -> StdFunc {
+    StdFunc::native("min", "أصغر", 2, min)
+}
+
+pub fn std_math_max() -> StdFunc {
+    StdFunc::native("max", "أكبر", 2, max)
+}
+
+pub fn std_math_hypot() -> StdFunc {
+    StdFunc::native("hypot", "وتر", 2, hypot)
+}
+
+pub fn std_math_gcd() -> StdFunc {
+    StdFunc::native("gcd", "ق_م_أ", 2, gcd)
+}
+
+pub fn std_math_lcm() -> StdFunc {
+    StdFunc::native("lcm", "م_م_أ", 2, lcm)
+}
+
+pub fn std_math_is_prime() -> StdFunc {
+    StdFunc::native("isPrime", "أولي", 1, is_prime)
+}
+
+pub fn std_math_pi() -> StdFunc {
+    StdFunc::value("PI", "ط", value::Value::Number(std::f64::consts::PI))
+}
+
+pub fn std_math_e() -> StdFunc {
+    StdFunc::value("E", "ه", value::Value::Number(std::f64::consts::E))
+}
+
+pub fn std_math_inf() -> StdFunc {
+    StdFunc::value("INF", "لانهاية", value::Value::Number(f64::INFINITY))
+}
+
+pub fn std_math_nan() -> StdFunc {
+    StdFunc::value("NAN", "ليس_رقما", value::Value::Number(f64::NAN))
+}
+
+fn float_arg(interp: &virtual_machine::VirtualMachine, val: &value::Value) -> Result<f64, String> {
+    Ok(expect_number(interp, val)?.to_f64())
+}
+
+fn sin(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    Ok(value::Value::Number(float_arg(interp, &args[0])?.sin()))
+}
+
+fn cos(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    Ok(value::Value::Number(float_arg(interp, &args[0])?.cos()))
+}
+
+fn tan(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    Ok(value::Value::Number(float_arg(interp, &args[0])?.tan()))
+}
+
+fn asin(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    Ok(value::Value::Number(float_arg(interp, &args[0])?.asin()))
+}
+
+fn acos(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    Ok(value::Value::Number(float_arg(interp, &args[0])?.acos()))
+}
+
+fn atan(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    Ok(value::Value::Number(float_arg(interp, &args[0])?.atan()))
+}
+
+fn atan2(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let y = float_arg(interp, &args[0])?;
+    let x = float_arg(interp, &args[1])?;
+    Ok(value::Value::Number(y.atan2(x)))
+}
+
+fn log(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    Ok(value::Value::Number(float_arg(interp, &args[0])?.ln()))
+}
+
+fn log10(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    Ok(value::Value::Number(float_arg(interp, &args[0])?.log10()))
+}
+
+fn log2(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    Ok(value::Value::Number(float_arg(interp, &args[0])?.log2()))
+}
+
+/// How many bits an exact power may have, about 300,000 decimal digits.
+const MAX_EXACT_POWER_BITS: u64 = 1 << 20;
+
+/// Fails if `exponent` copies of a number with `bits` bits would multiply out past
+/// `MAX_EXACT_POWER_BITS`. Counting one bit less than the number has keeps 1 and -1 at 0.
+fn check_exact_power(bits: u64, exponent: i32) -> Result<(), String> {
+    if bits.saturating_sub(1) * u64::from(exponent.unsigned_abs()) > MAX_EXACT_POWER_BITS {
+        return Err(
+            "The exact power is too big, use a float base for an approximate one".to_string(),
+        );
+    }
+    Ok(())
+}
+
+/*
+Exact when raising an integer to a non-negative integer power, or a rational to any
+integer power, as long as the result isn't too big. Everything else goes through floats.
+ */
+fn pow(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let base = expect_number(interp, &args[0])?;
+    let exponent = expect_number(interp, &args[1])?;
+
+    let exact_exponent = match &exponent {
+        Numeric::Float(_) => None,
+        _ => exponent.to_bigint().and_then(|exponent| exponent.to_i32()),
+    };
+
+    let res = match (&base, exact_exponent) {
+        (Numeric::Integer(_) | Numeric::BigInt(_), Some(exponent)) if exponent >= 0 => {
+            let base = base.to_bigint().unwrap();
+            check_exact_power(base.bits(), exponent)?;
+            Numeric::BigInt(num_traits::pow(base, exponent as usize))
+        }
+        (Numeric::Rational(num), Some(exponent)) => {
+            if num.is_zero() && exponent < 0 {
+                return Err("Can't raise zero to a negative power".to_string());
+            }
+            check_exact_power(num.numer().bits().max(num.denom().bits()), exponent)?;
+            Numeric::Rational(num.pow(exponent))
+        }
+        _ => Numeric::Float(base.to_f64().powf(exponent.to_f64())),
+    };
+    Ok(res.into_value(&mut interp.heap))
+}
+
+fn abs(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let num = expect_number(interp, &args[0])?;
+    Ok(num.abs().into_value(&mut interp.heap))
+}
+
+fn rounded(
+    interp: &mut virtual_machine::VirtualMachine,
+    num: Option<Numeric>,
+) -> Result<value::Value, String> {
+    match num {
+        Some(num) => Ok(num.into_value(&mut interp.heap)),
+        None => Err("Can't round NaN or infinity to an integer".to_string()),
+    }
+}
+
+fn floor(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let num = expect_number(interp, &args[0])?.floor();
+    rounded(interp, num)
+}
+
+fn ceil(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let num = expect_number(interp, &args[0])?.ceil();
+    rounded(interp, num)
+}
+
+fn round(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let num = expect_number(interp, &args[0])?.round();
+    rounded(interp, num)
+}
+
+fn trunc(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let num = expect_number(interp, &args[0])?.truncate();
+    rounded(interp, num)
+}
+
+fn min(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let left = expect_number(interp, &args[0])?;
+    let right = expect_number(interp, &args[1])?;
+    match left.compare(&right) {
+        Some(Ordering::Greater) => Ok(args[1].clone()),
+        Some(_) => Ok(args[0].clone()),
+        None => Ok(value::Value::Number(f64::NAN)),
+    }
+}
+
+fn max(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let left = expect_number(interp, &args[0])?;
+    let right = expect_number(interp, &args[1])?;
+    match left.compare(&right) {
+        Some(Ordering::Less) => Ok(args[1].clone()),
+        Some(_) => Ok(args[0].clone()),
+        None => Ok(value::Value::Number(f64::NAN)),
+    }
+}
+
+fn hypot(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let x = float_arg(interp, &args[0])?;
+    let y = float_arg(interp, &args[1])?;
+    Ok(value::Value::Number(x.hypot(y)))
+}
+
+fn gcd(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let left: BigInt = expect_integer(interp, &args[0])?;
+    let right: BigInt = expect_integer(interp, &args[1])?;
+    Ok(Numeric::BigInt(left.gcd(&right)).into_value(&mut interp.heap))
+}
+
+fn lcm(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let left: BigInt = expect_integer(interp, &args[0])?;
+    let right: BigInt = expect_integer(interp, &args[1])?;
+    Ok(Numeric::BigInt(left.lcm(&right)).into_value(&mut interp.heap))
+}
+
+fn is_prime(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let num: BigInt = expect_integer(interp, &args[0])?;
+    if num.sign() == Sign::Minus {
+        return Ok(value::Value::Bool(false));
+    }
+    match num.to_u64() {
+        Some(num) => Ok(value::Value::Bool(is_prime_u64(num))),
+        None => Err("isPrime only supports integers below 2^64".to_string()),
+    }
+}
+
+/*
+Miller-Rabin with the first twelve primes as witnesses, which is deterministic for every
+64-bit integer.
+ */
+fn is_prime_u64(num: u64) -> bool {
+    const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+    if num < 2 {
+        return false;
+    }
+    for witness in WITNESSES {
+        if num.is_multiple_of(witness) {
+            return num == witness;
+        }
+    }
+
+    let mul_mod = |a: u64, b: u64| (u128::from(a) * u128::from(b) % u128::from(num)) as u64;
+    let pow_mod = |mut base: u64, mut exponent: u64| {
+        let mut res = 1;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                res = mul_mod(res, base);
+            }
+            base = mul_mod(base, base);
+            exponent >>= 1;
+        }
+        res
+    };
+
+    let trailing_zeros = (num - 1).trailing_zeros();
+    let odd_part = (num - 1) >> trailing_zeros;
+    'witnesses: for witness in WITNESSES {
+        let mut x = pow_mod(witness, odd_part);
+        if x == 1 || x == num - 1 {
+            continue;
+        }
+        for _ in 1..trailing_zeros {
+            x = mul_mod(x, x);
+            if x == num - 1 {
+                continue 'witnesses;
+            }
+        }
+        return false;
+    }
+    true
+}
@@ -1,12 +1,126 @@
 use crate::value;
+use crate::virtual_machine;
+use std::collections::HashMap;
 
 pub(crate) mod collection;
 pub(crate) mod debug;
+pub mod file;
 pub mod io;
+pub(crate) mod json;
 pub(crate) mod math;
+pub(crate) mod number;
+pub(crate) mod pattern;
+pub mod random;
+pub(crate) mod reflect;
+pub(crate) mod string;
 pub(crate) mod time;
 
+/// `en`, or `ar` under the `ar` feature.
+pub(crate) fn localized<'a>(en: &'a str, ar: &'a str) -> &'a str {
+    if cfg!(feature = "ar") {
+        ar
+    } else {
+        en
+    }
+}
+
+/// `name` spelled the way the tokenizer reads identifiers by default.
+pub(crate) fn normalized(name: &str) -> String {
+    #[cfg(feature = "ar")]
+    {
+        use arabic_utils::arabic_str::ArabicStr;
+        name.normalize_arabic()
+    }
+    #[cfg(not(feature = "ar"))]
+    name.to_string()
+}
+
+/// The entry for `name` spelled as written, or else spelled normalized. Names defined on the
+/// rust side are stored normalized, so they're found whether or not the tokenizer normalized
+/// the name they're looked up by.
+pub(crate) fn lookup<'a, V>(map: &'a HashMap<String, V>, name: &str) -> Option<&'a V> {
+    map.get(name).or_else(|| map.get(&normalized(name)))
+}
+
 pub struct StdFunc {
     pub name: String,
     pub func: value::Value,
 }
+
+impl StdFunc {
+    /// A global value named `en_name`, or `ar_name` under the `ar` feature.
+    pub(crate) fn value(en_name: &str, ar_name: &str, value: value::Value) -> StdFunc {
+        let name = if cfg!(feature = "ar") {
+            String::from(ar_name)
+        } else {
+            String::from(en_name)
+        };
+
+        StdFunc { name, func: value }
+    }
+
+    /// A native function named `en_name`, or `ar_name` under the `ar` feature.
+    pub(crate) fn native(
+        en_name: &str,
+        ar_name: &str,
+        arity: u8,
+        func: fn(
+            &mut virtual_machine::VirtualMachine,
+            &[value::Value],
+        ) -> Result<value::Value, String>,
+    ) -> StdFunc {
+        let name = if cfg!(feature = "ar") {
+            String::from(ar_name)
+        } else {
+            String::from(en_name)
+        };
+
+        StdFunc {
+            name: name.clone(),
+            func: value::Value::NativeFunction(value::NativeFunction {
+                arity,
+                optional: 0,
+                name,
+                func,
+            }),
+        }
+    }
+
+    /// Lets calls to a native leave out its last `optional` arguments.
+    pub(crate) fn with_optional(mut self, optional: u8) -> StdFunc {
+        if let value::Value::NativeFunction(func) = &mut self.func {
+            func.optional = optional;
+        }
+        self
+    }
+}
+
+/// A global instance whose fields are the module's members, so `json.parse(text)` is an
+/// ordinary method call on it.
+pub struct StdModule {
+    pub name: String,
+    pub members: Vec<StdFunc>,
+}
+
+impl StdModule {
+    /// Native members are renamed to `module.member` so errors say where they come from.
+    pub(crate) fn new(en_name: &str, ar_name: &str, members: Vec<StdFunc>) -> StdModule {
+        let name = if cfg!(feature = "ar") {
+            String::from(ar_name)
+        } else {
+            String::from(en_name)
+        };
+
+        let members = members
+            .into_iter()
+            .map(|mut member| {
+                if let value::Value::NativeFunction(native) = &mut member.func {
+                    native.name = format!("{}.{}", name, member.name);
+                }
+                member
+            })
+            .collect();
+
+        StdModule { name, members }
+    }
+}
@@ -0,0 +1,281 @@
+use super::StdFunc;
+use crate::numeric::Numeric;
+use crate::value;
+use crate::virtual_machine;
+use firnas_compiler::diagnostic;
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::Zero;
+
+pub fn std_number_int() -> StdFunc {
+    StdFunc::native("int", "صحيح", 1, int)
+}
+
+pub fn std_number_float() -> StdFunc {
+    StdFunc::native("float", "عشري", 1, float)
+}
+
+pub fn std_number_rational() -> StdFunc {
+    StdFunc::native("rational", "نسبي", 2, rational)
+}
+
+pub fn std_number_numerator() -> StdFunc {
+    StdFunc::native("numerator", "بسط", 1, numerator)
+}
+
+pub fn std_number_denominator() -> StdFunc {
+    StdFunc::native("denominator", "مقام", 1, denominator)
+}
+
+pub fn std_number_to_number() -> StdFunc {
+    StdFunc::native("toNumber", "الى_رقم", 1, to_number)
+}
+
+/// Spells a number out in Arabic words, e.g. `toArabicWords(1250)` is ألف ومائتان وخمسون.
+pub fn std_number_to_arabic_words() -> StdFunc {
+    StdFunc::native("toArabicWords", "تفقيط", 1, to_arabic_words)
+}
+
+/// Spells an amount out in Arabic words in `"ريال"` (riyals and halalas) or `"دينار"`
+/// (dinars and fils), as it's written on invoices and cheques.
+pub fn std_number_amount_to_arabic_words() -> StdFunc {
+    StdFunc::native(
+        "amountToArabicWords",
+        "تفقيط_مبلغ",
+        2,
+        amount_to_arabic_words,
+    )
+}
+
+/// Chooses the digits the program's numbers are printed with: `"عربية"` (the default),
+/// `"فارسية"` or `"غربية"`.
+#[cfg(feature = "ar")]
+pub fn std_number_use_digits() -> StdFunc {
+    StdFunc::native("useDigits", "استخدم_ارقام", 1, use_digits)
+}
+
+pub(crate) fn expect_number(
+    interp: &virtual_machine::VirtualMachine,
+    val: &value::Value,
+) -> Result<Numeric, String> {
+    Numeric::from_value(&interp.heap, val).ok_or_else(|| {
+        diagnostic::EXPECTED_NUMBER_ARGUMENT.render(
+            interp.language,
+            &[&value::type_of(val).name(interp.language)],
+        )
+    })
+}
+
+/// A whole number as an `i64`, or as a `BigInt` where big integers are fine. Floats and
+/// rationals with no fractional part count as whole numbers.
+pub(crate) fn expect_integer<T: TryFrom<BigInt>>(
+    interp: &virtual_machine::VirtualMachine,
+    val: &value::Value,
+) -> Result<T, String> {
+    expect_number(interp, val)?
+        .to_bigint()
+        .and_then(|num| T::try_from(num).ok())
+        .ok_or_else(|| {
+            diagnostic::EXPECTED_INTEGER_ARGUMENT.render(
+                interp.language,
+                &[&value::type_of(val).name(interp.language)],
+            )
+        })
+}
+
+fn to_arabic_words(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    use arabic_utils::arabic_words;
+
+    let words = match expect_number(interp, &args[0])? {
+        Numeric::Integer(num) => {
+            arabic_words::integer_to_words(num, arabic_words::Gender::Masculine)
+        }
+        Numeric::Float(num) if !num.is_finite() => {
+            return Err(String::from("Can't write NaN or infinities in words"))
+        }
+        Numeric::Float(num) => arabic_words::decimal_to_words(num),
+        Numeric::BigInt(_) => None,
+        Numeric::Rational(_) => {
+            return Err(String::from(
+                "Can't write a rational in words, convert it to a float first",
+            ))
+        }
+    };
+    match words {
+        Some(words) => Ok(value::Value::String(interp.heap.manage_str(words))),
+        None => Err(String::from(
+            "Can't write numbers from a quadrillion on in words",
+        )),
+    }
+}
+
+fn amount_to_arabic_words(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    use arabic_utils::arabic_words;
+
+    let amount = expect_number(interp, &args[0])?.to_f64();
+    let currency = match super::string::expect_string(interp, &args[1])?.as_str() {
+        "ريال" | "riyal" => arabic_words::RIYAL,
+        "دينار" | "dinar" => arabic_words::DINAR,
+        other => {
+            return Err(format!(
+                "Unknown currency '{}', expected 'ريال' or 'دينار'",
+                other
+            ))
+        }
+    };
+    match arabic_words::amount_to_words(amount, &currency) {
+        Some(words) => Ok(value::Value::String(interp.heap.manage_str(words))),
+        None => Err(String::from(
+            "Can't write NaN, infinities or amounts from a quadrillion on in words",
+        )),
+    }
+}
+
+#[cfg(feature = "ar")]
+fn use_digits(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    use arabic_utils::arabic_digits::DigitSet;
+
+    let name = super::string::expect_string(interp, &args[0])?;
+    interp.digits = match name.as_str() {
+        "عربية" => DigitSet::ArabicIndic,
+        "فارسية" => DigitSet::EasternArabicIndic,
+        "غربية" => DigitSet::Western,
+        _ => {
+            return Err(format!(
+                "Unknown digits '{}', expected 'عربية', 'فارسية' or 'غربية'",
+                name
+            ))
+        }
+    };
+    Ok(value::Value::Nil)
+}
+
+fn int(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    match expect_number(interp, &args[0])?.truncate() {
+        Some(num) => Ok(num.into_value(&mut interp.heap)),
+        None => Err("Can't convert NaN or infinity to an integer".to_string()),
+    }
+}
+
+fn float(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let num = expect_number(interp, &args[0])?;
+    Ok(value::Value::Number(num.to_f64()))
+}
+
+fn rational(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let numer = expect_number(interp, &args[0])?.to_rational();
+    let denom = expect_number(interp, &args[1])?.to_rational();
+
+    match (numer, denom) {
+        (_, Some(denom)) if denom.is_zero() => Err("Rational with zero denominator".to_string()),
+        (Some(numer), Some(denom)) => {
+            Ok(Numeric::Rational(numer / denom).into_value(&mut interp.heap))
+        }
+        _ => Err("Can't make a rational out of NaN or infinity".to_string()),
+    }
+}
+
+fn numerator(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let num = as_rational(interp, &args[0])?;
+    Ok(Numeric::BigInt(num.numer().clone()).into_value(&mut interp.heap))
+}
+
+fn denominator(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let num = as_rational(interp, &args[0])?;
+    Ok(Numeric::BigInt(num.denom().clone()).into_value(&mut interp.heap))
+}
+
+fn as_rational(
+    interp: &virtual_machine::VirtualMachine,
+    val: &value::Value,
+) -> Result<BigRational, String> {
+    match expect_number(interp, val)? {
+        Numeric::Float(_) => Err(format!(
+            "Invalid call: expected integer or rational, got {}",
+            value::type_of(val).name(interp.language)
+        )),
+        num => Ok(num.to_rational().unwrap()),
+    }
+}
+
+/// Parses a string into an integer or a float, or returns nil if it isn't a number.
+fn to_number(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let text = match &args[0] {
+        value::Value::String(id) => interp.heap.get_str(*id).clone(),
+        val => {
+            return match Numeric::from_value(&interp.heap, val) {
+                Some(_) => Ok(val.clone()),
+                None => Err(diagnostic::EXPECTED_STRING_ARGUMENT.render(
+                    interp.language,
+                    &[&value::type_of(val).name(interp.language)],
+                )),
+            }
+        }
+    };
+
+    match parse_number(&text) {
+        Some(num) => Ok(num.into_value(&mut interp.heap)),
+        None => Ok(value::Value::Nil),
+    }
+}
+
+fn parse_number(text: &str) -> Option<Numeric> {
+    let text = text.trim();
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    };
+    let digits = western_digits(digits)?;
+
+    let num = if digits.contains('.') {
+        Numeric::Float(digits.parse().ok()?)
+    } else {
+        Numeric::BigInt(digits.parse::<BigInt>().ok()?)
+    };
+    Some(if negative { num.negate() } else { num })
+}
+
+/// Checks that `digits` is a plain decimal number and converts any Arabic-Indic digits.
+fn western_digits(digits: &str) -> Option<String> {
+    let is_decimal = |s: &str| {
+        !s.is_empty()
+            && !s.starts_with('.')
+            && !s.ends_with('.')
+            && s.matches('.').count() <= 1
+            && s.chars().all(|c| c.is_ascii_digit() || c == '.')
+    };
+
+    if is_decimal(digits) {
+        return Some(digits.to_string());
+    }
+
+    use arabic_utils::arabic_str::ArabicStr;
+    digits.parse_arabic_decimal()
+}
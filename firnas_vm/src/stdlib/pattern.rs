@@ -0,0 +1,155 @@
+use super::string::expect_string;
+use super::StdFunc;
+use super::StdModule;
+use crate::value;
+use crate::virtual_machine;
+use regex::Regex;
+
+/*
+Patterns are Unicode aware, so `\w` matches Arabic letters together with their diacritics.
+Every function takes either a compiled pattern or the pattern's source as a string.
+ */
+
+pub fn std_regex() -> StdModule {
+    StdModule::new(
+        "regex",
+        "نمط",
+        vec![
+            StdFunc::native("compile", "ترجم", 1, compile),
+            StdFunc::native("test", "يطابق", 2, test),
+            StdFunc::native("find", "ابحث", 2, find),
+            StdFunc::native("findAll", "ابحث_الكل", 2, find_all),
+            StdFunc::native("captures", "التقط", 2, captures),
+            StdFunc::native("replace", "استبدل", 3, replace),
+            StdFunc::native("split", "قسم", 2, split),
+        ],
+    )
+}
+
+fn compile_pattern(pattern: &str) -> Result<Regex, String> {
+    Regex::new(pattern).map_err(|err| match err {
+        regex::Error::Syntax(what) => {
+            // the last line says what went wrong, the lines above point at where
+            let reason = what.lines().last().unwrap_or_default().trim().to_string();
+            format!("Invalid regular expression '{}': {}", pattern, reason)
+        }
+        err => format!("Invalid regular expression '{}': {}", pattern, err),
+    })
+}
+
+fn expect_regex(
+    interp: &virtual_machine::VirtualMachine,
+    val: &value::Value,
+) -> Result<Regex, String> {
+    match val {
+        value::Value::Regex(id) => Ok(interp.heap.get_regex(*id).clone()),
+        value::Value::String(id) => compile_pattern(interp.heap.get_str(*id)),
+        _ => Err(format!(
+            "Invalid call: expected regex or string, got {}",
+            value::type_of(val).name(interp.language)
+        )),
+    }
+}
+
+fn make_string(interp: &mut virtual_machine::VirtualMachine, s: &str) -> value::Value {
+    value::Value::String(interp.heap.manage_str(s.to_string()))
+}
+
+fn make_list(
+    interp: &mut virtual_machine::VirtualMachine,
+    elements: Vec<value::Value>,
+) -> value::Value {
+    value::Value::List(interp.heap.manage_list(elements))
+}
+
+fn compile(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let re = expect_regex(interp, &args[0])?;
+    Ok(value::Value::Regex(interp.heap.manage_regex(re)))
+}
+
+/// Whether the pattern matches anywhere in the text.
+fn test(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let re = expect_regex(interp, &args[0])?;
+    let text = expect_string(interp, &args[1])?;
+    Ok(value::Value::Bool(re.is_match(&text)))
+}
+
+/// The first match, or nil.
+fn find(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let re = expect_regex(interp, &args[0])?;
+    let text = expect_string(interp, &args[1])?;
+    match re.find(&text) {
+        Some(found) => Ok(make_string(interp, found.as_str())),
+        None => Ok(value::Value::Nil),
+    }
+}
+
+fn find_all(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let re = expect_regex(interp, &args[0])?;
+    let text = expect_string(interp, &args[1])?;
+    let elements = re
+        .find_iter(&text)
+        .map(|found| make_string(interp, found.as_str()))
+        .collect();
+    Ok(make_list(interp, elements))
+}
+
+/// The whole first match followed by its groups, with nil for groups that didn't take
+/// part, or nil when there's no match.
+fn captures(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let re = expect_regex(interp, &args[0])?;
+    let text = expect_string(interp, &args[1])?;
+    match re.captures(&text) {
+        Some(groups) => {
+            let elements = groups
+                .iter()
+                .map(|group| match group {
+                    Some(group) => make_string(interp, group.as_str()),
+                    None => value::Value::Nil,
+                })
+                .collect();
+            Ok(make_list(interp, elements))
+        }
+        None => Ok(value::Value::Nil),
+    }
+}
+
+/// Replaces every match; `$1` or `${name}` in the replacement stand for a group.
+fn replace(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let re = expect_regex(interp, &args[0])?;
+    let text = expect_string(interp, &args[1])?;
+    let replacement = expect_string(interp, &args[2])?;
+    let res = re.replace_all(&text, replacement.as_str());
+    Ok(make_string(interp, &res))
+}
+
+fn split(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let re = expect_regex(interp, &args[0])?;
+    let text = expect_string(interp, &args[1])?;
+    let elements = re
+        .split(&text)
+        .map(|part| make_string(interp, part))
+        .collect();
+    Ok(make_list(interp, elements))
+}
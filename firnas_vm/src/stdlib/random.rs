@@ -0,0 +1,175 @@
+use super::number::expect_integer;
+use super::StdFunc;
+use crate::value;
+use crate::virtual_machine;
+use firnas_compiler::diagnostic;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+pub fn std_random_random() -> StdFunc {
+    StdFunc::native("random", "عشوائي", 0, random)
+}
+
+pub fn std_random_randint() -> StdFunc {
+    StdFunc::native("randint", "عدد_عشوائي", 2, randint)
+}
+
+pub fn std_random_choice() -> StdFunc {
+    StdFunc::native("choice", "اختر", 1, choice)
+}
+
+pub fn std_random_shuffle() -> StdFunc {
+    StdFunc::native("shuffle", "اخلط", 1, shuffle)
+}
+
+pub fn std_random_seed() -> StdFunc {
+    StdFunc::native("seed", "بذرة", 1, seed)
+}
+
+/*
+xoshiro256** seeded through splitmix64. It is not suitable for cryptography, but it is
+fast, has no dependencies and gives the same sequence for the same seed on every platform.
+ */
+pub struct Rng {
+    state: [u64; 4],
+}
+
+impl Rng {
+    pub fn from_seed(seed: u64) -> Rng {
+        let mut splitmix_state = seed;
+        let mut splitmix = || {
+            splitmix_state = splitmix_state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = splitmix_state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^ (z >> 31)
+        };
+        Rng {
+            state: [splitmix(), splitmix(), splitmix(), splitmix()],
+        }
+    }
+
+    pub fn from_clock() -> Rng {
+        let since_the_epoch = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        Rng::from_seed(since_the_epoch.as_nanos() as u64)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let res = self.state[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = self.state[1] << 17;
+
+        self.state[2] ^= self.state[0];
+        self.state[3] ^= self.state[1];
+        self.state[1] ^= self.state[2];
+        self.state[0] ^= self.state[3];
+        self.state[2] ^= t;
+        self.state[3] = self.state[3].rotate_left(45);
+
+        res
+    }
+
+    /// Uniform in `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform in `[0, bound)`, rejecting samples that would bias the result.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        let zone = u64::MAX - u64::MAX % bound;
+        loop {
+            let sample = self.next_u64();
+            if sample < zone {
+                return sample % bound;
+            }
+        }
+    }
+}
+
+impl Default for Rng {
+    fn default() -> Rng {
+        Rng::from_clock()
+    }
+}
+
+fn random(
+    interp: &mut virtual_machine::VirtualMachine,
+    _args: &[value::Value],
+) -> Result<value::Value, String> {
+    Ok(value::Value::Number(interp.rng.next_f64()))
+}
+
+/// Uniform in `[low, high]`, both ends included.
+fn randint(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let low: i64 = expect_integer(interp, &args[0])?;
+    let high: i64 = expect_integer(interp, &args[1])?;
+    if low > high {
+        return Err(format!("Empty range from {} to {}", low, high));
+    }
+
+    let span = (i128::from(high) - i128::from(low) + 1) as u128;
+    let offset = if span > u128::from(u64::MAX) {
+        interp.rng.next_u64()
+    } else {
+        interp.rng.below(span as u64)
+    };
+    Ok(value::Value::Integer(
+        (i128::from(low) + i128::from(offset)) as i64,
+    ))
+}
+
+fn choice(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let elements = match &args[0] {
+        value::Value::List(id) => interp.heap.get_list_elements(*id).clone(),
+        value::Value::Tuple(id) => interp.heap.get_tuple_elements(*id).clone(),
+        val => {
+            return Err(diagnostic::EXPECTED_LIST_ARGUMENT.render(
+                interp.language,
+                &[&value::type_of(val).name(interp.language)],
+            ))
+        }
+    };
+    if elements.is_empty() {
+        return Err("Can't choose from an empty list".to_string());
+    }
+
+    let index = interp.rng.below(elements.len() as u64) as usize;
+    Ok(elements[index].clone())
+}
+
+/// Shuffles a list in place with Fisher-Yates.
+fn shuffle(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let id = match &args[0] {
+        value::Value::List(id) => *id,
+        val => {
+            return Err(diagnostic::EXPECTED_LIST_ARGUMENT.render(
+                interp.language,
+                &[&value::type_of(val).name(interp.language)],
+            ))
+        }
+    };
+
+    let len = interp.heap.get_list_elements(id).len();
+    for i in (1..len).rev() {
+        let j = interp.rng.below(i as u64 + 1) as usize;
+        interp.heap.get_list_elements_mut(id).swap(i, j);
+    }
+    Ok(value::Value::Nil)
+}
+
+fn seed(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let seed: i64 = expect_integer(interp, &args[0])?;
+    interp.rng = Rng::from_seed(seed as u64);
+    Ok(value::Value::Nil)
+}
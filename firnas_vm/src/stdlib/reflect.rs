@@ -0,0 +1,270 @@
+use super::localized;
+use super::lookup;
+use super::normalized;
+use super::string::expect_string;
+use super::StdFunc;
+use crate::gc;
+use crate::value;
+use crate::virtual_machine;
+
+pub fn std_reflect_type() -> StdFunc {
+    StdFunc::native("type", "نوع", 1, type_name)
+}
+
+pub fn std_reflect_fields() -> StdFunc {
+    StdFunc::native("fields", "حقول", 1, fields)
+}
+
+pub fn std_reflect_methods() -> StdFunc {
+    StdFunc::native("methods", "طرق", 1, methods)
+}
+
+pub fn std_reflect_has_attr() -> StdFunc {
+    StdFunc::native("hasAttr", "يملك", 2, has_attr)
+}
+
+pub fn std_reflect_get_attr() -> StdFunc {
+    StdFunc::native("getAttr", "اجلب", 2, get_attr)
+}
+
+pub fn std_reflect_set_attr() -> StdFunc {
+    StdFunc::native("setAttr", "عين", 3, set_attr)
+}
+
+pub fn std_reflect_superclass() -> StdFunc {
+    StdFunc::native("superclass", "الأصل", 1, superclass)
+}
+
+pub fn std_reflect_arity() -> StdFunc {
+    StdFunc::native("arity", "عدد_المعاملات", 1, arity)
+}
+
+pub fn std_reflect_name() -> StdFunc {
+    StdFunc::native("name", "اسم", 1, name)
+}
+
+fn make_string(interp: &mut virtual_machine::VirtualMachine, s: &str) -> value::Value {
+    value::Value::String(interp.heap.manage_str(s.to_string()))
+}
+
+fn make_names(
+    interp: &mut virtual_machine::VirtualMachine,
+    mut names: Vec<String>,
+) -> value::Value {
+    // hash map order would change from run to run
+    names.sort();
+    let elements = names.iter().map(|name| make_string(interp, name)).collect();
+    value::Value::List(interp.heap.manage_list(elements))
+}
+
+fn expect_instance(
+    interp: &virtual_machine::VirtualMachine,
+    val: &value::Value,
+) -> Result<gc::HeapId, String> {
+    match val {
+        value::Value::Instance(id) => Ok(*id),
+        _ => Err(format!(
+            "Invalid call: expected instance, got {}",
+            value::type_of(val).name(interp.language)
+        )),
+    }
+}
+
+/// The class itself, or the class of an instance.
+fn expect_class(
+    interp: &virtual_machine::VirtualMachine,
+    val: &value::Value,
+) -> Result<gc::HeapId, String> {
+    match val {
+        value::Value::Class(id) => Ok(*id),
+        value::Value::Instance(id) => Ok(interp.heap.get_instance(*id).class_id),
+        _ => Err(format!(
+            "Invalid call: expected class, got {}",
+            value::type_of(val).name(interp.language)
+        )),
+    }
+}
+
+/// The value's type in the dialect of the build. Unlike `Type::name`, which
+/// error messages use, it doesn't change with the language errors are reported in.
+fn type_name(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let name = match value::type_of(&args[0]) {
+        value::Type::Number => localized("Number", "عشري"),
+        value::Type::Integer => localized("Integer", "صحيح"),
+        value::Type::BigInt => localized("BigInt", "صحيح_كبير"),
+        value::Type::Rational => localized("Rational", "نسبي"),
+        value::Type::Bool => localized("Bool", "منطقي"),
+        value::Type::String => localized("String", "نص"),
+        value::Type::Function => localized("Function", "دالة"),
+        value::Type::NativeFunction => localized("NativeFunction", "دالة_مدمجة"),
+        value::Type::Class => localized("Class", "صنف"),
+        value::Type::BoundMethod => localized("BoundMethod", "طريقة"),
+        value::Type::Instance => localized("Instance", "كائن"),
+        value::Type::Nil => localized("Nil", "عدم"),
+        value::Type::List => localized("List", "قائمة"),
+        value::Type::Tuple => localized("Tuple", "صف"),
+        value::Type::Set => localized("Set", "مجموعة"),
+        value::Type::Dict => localized("Dict", "قاموس"),
+        value::Type::Regex => localized("Regex", "نمط"),
+    };
+    Ok(make_string(interp, name))
+}
+
+fn fields(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let id = expect_instance(interp, &args[0])?;
+    let names = interp
+        .heap
+        .get_instance(id)
+        .fields
+        .keys()
+        .cloned()
+        .collect();
+    Ok(make_names(interp, names))
+}
+
+/// The names of a class's methods, inherited ones included.
+fn methods(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let id = expect_class(interp, &args[0])?;
+    let names = interp.heap.get_class(id).methods.keys().cloned().collect();
+    Ok(make_names(interp, names))
+}
+
+fn has_attr(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let id = expect_instance(interp, &args[0])?;
+    let attr = expect_string(interp, &args[1])?;
+    let instance = interp.heap.get_instance(id);
+    Ok(value::Value::Bool(
+        lookup(&instance.fields, &attr).is_some()
+            || lookup(&interp.heap.get_class(instance.class_id).methods, &attr).is_some(),
+    ))
+}
+
+/// A field, or a method bound to the instance, like `instance.attr`.
+fn get_attr(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let id = expect_instance(interp, &args[0])?;
+    let attr = expect_string(interp, &args[1])?;
+    let instance = interp.heap.get_instance(id);
+    if let Some(val) = lookup(&instance.fields, &attr) {
+        return Ok(val.clone());
+    }
+    match lookup(&interp.heap.get_class(instance.class_id).methods, &attr) {
+        Some(closure_id) => {
+            let method = value::BoundMethod {
+                instance_id: id,
+                closure_id: *closure_id,
+            };
+            Ok(value::Value::BoundMethod(
+                interp.heap.manage_bound_method(method),
+            ))
+        }
+        None => Err(format!(
+            "{} has no attribute {}",
+            interp.format_val(&args[0]),
+            attr
+        )),
+    }
+}
+
+fn set_attr(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let id = expect_instance(interp, &args[0])?;
+    let attr = expect_string(interp, &args[1])?;
+    if interp.is_read_only(id) {
+        return Err(format!(
+            "Can't set attribute {} of {}, its attributes are read-only",
+            attr,
+            interp.format_val(&args[0])
+        ));
+    }
+    // a field that is already there keeps its spelling, a new one is spelled like
+    // `instance.attr = val` would spell it
+    let fields = &mut interp.heap.get_instance_mut(id).fields;
+    let key = if fields.contains_key(&attr) {
+        attr
+    } else {
+        normalized(&attr)
+    };
+    fields.insert(key, args[2].clone());
+    Ok(value::Value::Nil)
+}
+
+/// The class it inherits from, or nil.
+fn superclass(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let id = expect_class(interp, &args[0])?;
+    Ok(match interp.heap.get_class(id).superclass {
+        Some(superclass_id) => value::Value::Class(superclass_id),
+        None => value::Value::Nil,
+    })
+}
+
+/// The number of arguments a call takes; for a class, the arguments of its initializer.
+fn arity(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let arity = match &args[0] {
+        value::Value::Function(id) => interp.heap.get_closure(*id).function.arity,
+        value::Value::NativeFunction(func) => func.arity,
+        value::Value::BoundMethod(id) => {
+            let closure_id = interp.heap.get_bound_method(*id).closure_id;
+            interp.heap.get_closure(closure_id).function.arity
+        }
+        value::Value::Class(id) => {
+            let methods = &interp.heap.get_class(*id).methods;
+            let init_name = localized("init", "تهيئة");
+            match lookup(methods, init_name) {
+                Some(closure_id) => interp.heap.get_closure(*closure_id).function.arity,
+                None => 0,
+            }
+        }
+        val => {
+            return Err(format!(
+                "Invalid call: expected function, got {}",
+                value::type_of(val).name(interp.language)
+            ))
+        }
+    };
+    Ok(value::Value::Integer(i64::from(arity)))
+}
+
+fn name(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let name = match &args[0] {
+        value::Value::Function(id) => interp.heap.get_closure(*id).function.name.clone(),
+        value::Value::NativeFunction(func) => func.name.clone(),
+        value::Value::BoundMethod(id) => {
+            let closure_id = interp.heap.get_bound_method(*id).closure_id;
+            interp.heap.get_closure(closure_id).function.name.clone()
+        }
+        value::Value::Class(id) => interp.heap.get_class(*id).name.clone(),
+        val => {
+            return Err(format!(
+                "Invalid call: expected function or class, got {}",
+                value::type_of(val).name(interp.language)
+            ))
+        }
+    };
+    Ok(make_string(interp, &name))
+}
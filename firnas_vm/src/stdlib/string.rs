@@ -0,0 +1,254 @@
+use super::StdFunc;
+use crate::value;
+use crate::virtual_machine;
+use firnas_compiler::diagnostic;
+use unicode_segmentation::UnicodeSegmentation;
+
+/*
+Every index and length in this module counts graphemes rather than bytes or chars,
+so a letter and the diacritics on it are always treated as one character.
+ */
+
+/// The value as it's printed, so that `"total: " + str(3)` concatenates two strings.
+pub fn std_string_str() -> StdFunc {
+    StdFunc::native("str", "نص", 1, str)
+}
+
+pub fn std_string_substring() -> StdFunc {
+    StdFunc::native("substring", "اقتطع", 3, substring)
+}
+
+pub fn std_string_find_substring() -> StdFunc {
+    StdFunc::native("findSubstring", "ابحث_عن_نص", 2, find_substring)
+}
+
+pub fn std_string_replace() -> StdFunc {
+    StdFunc::native("replace", "استبدل", 3, replace)
+}
+
+pub fn std_string_split() -> StdFunc {
+    StdFunc::native("split", "قسم", 2, split)
+}
+
+pub fn std_string_join() -> StdFunc {
+    StdFunc::native("join", "ادمج", 2, join)
+}
+
+pub fn std_string_trim() -> StdFunc {
+    StdFunc::native("trim", "شذب", 1, trim)
+}
+
+pub fn std_string_upper() -> StdFunc {
+    StdFunc::native("upper", "كبير", 1, upper)
+}
+
+pub fn std_string_lower() -> StdFunc {
+    StdFunc::native("lower", "صغير", 1, lower)
+}
+
+pub fn std_string_starts_with() -> StdFunc {
+    StdFunc::native("startsWith", "يبدأ_ب", 2, starts_with)
+}
+
+pub fn std_string_ends_with() -> StdFunc {
+    StdFunc::native("endsWith", "ينتهي_ب", 2, ends_with)
+}
+
+pub fn std_string_chars() -> StdFunc {
+    StdFunc::native("chars", "حروف", 1, chars)
+}
+
+pub(crate) fn graphemes(s: &str) -> Vec<&str> {
+    s.graphemes(true).collect()
+}
+
+/// The grapheme index `needle` first occurs at in `s`. Only whole graphemes match, so a
+/// letter is not found inside the same letter with diacritics.
+pub(crate) fn grapheme_position(s: &str, needle: &str) -> Option<usize> {
+    let haystack = graphemes(s);
+    let needle = graphemes(needle);
+    if needle.is_empty() {
+        return Some(0);
+    }
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle.as_slice())
+}
+
+pub(crate) fn expect_string(
+    interp: &virtual_machine::VirtualMachine,
+    val: &value::Value,
+) -> Result<String, String> {
+    match val {
+        value::Value::String(id) => Ok(interp.heap.get_str(*id).clone()),
+        _ => Err(diagnostic::EXPECTED_STRING_ARGUMENT.render(
+            interp.language,
+            &[&value::type_of(val).name(interp.language)],
+        )),
+    }
+}
+
+fn expect_index(
+    interp: &virtual_machine::VirtualMachine,
+    val: &value::Value,
+) -> Result<i64, String> {
+    match val {
+        value::Value::Integer(index) => Ok(*index),
+        value::Value::Number(index) if index.fract() == 0.0 => Ok(*index as i64),
+        _ => Err(diagnostic::EXPECTED_INTEGER_ARGUMENT.render(
+            interp.language,
+            &[&value::type_of(val).name(interp.language)],
+        )),
+    }
+}
+
+fn make_string(interp: &mut virtual_machine::VirtualMachine, s: String) -> value::Value {
+    value::Value::String(interp.heap.manage_str(s))
+}
+
+fn str(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let s = interp.format_val(&args[0]);
+    Ok(make_string(interp, s))
+}
+
+fn substring(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let s = expect_string(interp, &args[0])?;
+    let graphemes = graphemes(&s);
+    let len = graphemes.len() as i64;
+    // negative indices count from the end, as in slices
+    let clamp = |index: i64| {
+        let index = if index < 0 { index + len } else { index };
+        index.clamp(0, len) as usize
+    };
+    let start = clamp(expect_index(interp, &args[1])?);
+    let end = clamp(expect_index(interp, &args[2])?).max(start);
+    Ok(make_string(interp, graphemes[start..end].concat()))
+}
+
+/// The index of the first occurrence of a substring, or -1.
+fn find_substring(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let s = expect_string(interp, &args[0])?;
+    let needle = expect_string(interp, &args[1])?;
+    let index = grapheme_position(&s, &needle).map_or(-1, |index| index as i64);
+    Ok(value::Value::Integer(index))
+}
+
+fn replace(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let s = expect_string(interp, &args[0])?;
+    let from = expect_string(interp, &args[1])?;
+    let to = expect_string(interp, &args[2])?;
+    if from.is_empty() {
+        return Err("Can't replace an empty string".to_string());
+    }
+    Ok(make_string(interp, s.replace(&from, &to)))
+}
+
+fn split(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let s = expect_string(interp, &args[0])?;
+    let separator = expect_string(interp, &args[1])?;
+    let parts: Vec<String> = if separator.is_empty() {
+        graphemes(&s).into_iter().map(String::from).collect()
+    } else {
+        s.split(&separator).map(String::from).collect()
+    };
+    let elements = parts
+        .into_iter()
+        .map(|part| make_string(interp, part))
+        .collect::<Vec<value::Value>>();
+    Ok(value::Value::List(interp.heap.manage_list(elements)))
+}
+
+fn join(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let elements = match &args[0] {
+        value::Value::List(id) => interp.heap.get_list_elements(*id).clone(),
+        value::Value::Tuple(id) => interp.heap.get_tuple_elements(*id).clone(),
+        val => {
+            return Err(diagnostic::EXPECTED_LIST_ARGUMENT.render(
+                interp.language,
+                &[&value::type_of(val).name(interp.language)],
+            ))
+        }
+    };
+    let separator = expect_string(interp, &args[1])?;
+    let parts = elements
+        .iter()
+        .map(|element| expect_string(interp, element))
+        .collect::<Result<Vec<String>, String>>()?;
+    Ok(make_string(interp, parts.join(&separator)))
+}
+
+fn trim(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let s = expect_string(interp, &args[0])?;
+    Ok(make_string(interp, s.trim().to_string()))
+}
+
+fn upper(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let s = expect_string(interp, &args[0])?;
+    Ok(make_string(interp, s.to_uppercase()))
+}
+
+fn lower(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let s = expect_string(interp, &args[0])?;
+    Ok(make_string(interp, s.to_lowercase()))
+}
+
+fn starts_with(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let s = expect_string(interp, &args[0])?;
+    let prefix = expect_string(interp, &args[1])?;
+    Ok(value::Value::Bool(
+        graphemes(&s).starts_with(&graphemes(&prefix)),
+    ))
+}
+
+fn ends_with(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let s = expect_string(interp, &args[0])?;
+    let suffix = expect_string(interp, &args[1])?;
+    Ok(value::Value::Bool(
+        graphemes(&s).ends_with(&graphemes(&suffix)),
+    ))
+}
+
+fn chars(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let s = expect_string(interp, &args[0])?;
+    let elements = graphemes(&s)
+        .into_iter()
+        .map(|grapheme| make_string(interp, grapheme.to_string()))
+        .collect::<Vec<value::Value>>();
+    Ok(value::Value::List(interp.heap.manage_list(elements)))
+}
@@ -1,7 +1,18 @@
+use super::io::make_digits;
+use super::localized;
+use super::normalized;
+use super::number::expect_integer;
+use super::number::expect_number;
+use super::string::expect_string;
 use super::StdFunc;
+use super::StdModule;
+use crate::gc;
 use crate::value;
 use crate::value::NativeFunction;
 use crate::virtual_machine;
+use firnas_compiler::diagnostic;
+use firnas_compiler::diagnostic::Language;
+use std::collections::HashMap;
 use std::time::SystemTime;
 use std::time::UNIX_EPOCH;
 
@@ -16,6 +27,7 @@ pub fn std_time_clock() -> StdFunc {
         name: name.clone(),
         func: value::Value::NativeFunction(NativeFunction {
             arity: 0,
+            optional: 0,
             name,
             func: clock,
         }),
@@ -29,5 +41,719 @@ fn clock(
     let start = SystemTime::now();
     let since_the_epoch = start.duration_since(UNIX_EPOCH).unwrap();
 
-    Ok(value::Value::Number(since_the_epoch.as_millis() as f64))
+    Ok(value::Value::Integer(since_the_epoch.as_millis() as i64))
+}
+
+/*
+A date is an instance of the VM's `DateTime` class whose fields hold the instant in epoch millis,
+the fixed UTC offset in minutes, and the local calendar fields derived from them. Only the
+millis and the offset are read back, the other fields are there for reading, and the VM
+refuses to set any of them so that they can't disagree.
+ */
+
+const MILLIS_PER_DAY: i64 = 86_400_000;
+const MILLIS_PER_MINUTE: i64 = 60_000;
+
+/// Dates go up to a hundred million days either side of 1970-01-01, as in JavaScript, so that
+/// calendar arithmetic on them can't overflow.
+const MAX_MILLIS: i64 = 100_000_000 * MILLIS_PER_DAY;
+/// Years past this are out of range in either calendar, checked before converting them to days.
+const MAX_YEAR: i64 = 300_000;
+
+/// Julian day number of 1970-01-01.
+const UNIX_EPOCH_JDN: i64 = 2_440_588;
+/// Julian day number of the day before 1 Muharram 1 in the tabular Hijri calendar.
+const HIJRI_EPOCH_JDN: i64 = 1_948_439;
+
+const MONTH_NAMES_EN: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+const MONTH_NAMES_AR: [&str; 12] = [
+    "يناير",
+    "فبراير",
+    "مارس",
+    "أبريل",
+    "مايو",
+    "يونيو",
+    "يوليو",
+    "أغسطس",
+    "سبتمبر",
+    "أكتوبر",
+    "نوفمبر",
+    "ديسمبر",
+];
+const HIJRI_MONTH_NAMES_EN: [&str; 12] = [
+    "Muharram",
+    "Safar",
+    "Rabi al-Awwal",
+    "Rabi al-Thani",
+    "Jumada al-Awwal",
+    "Jumada al-Thani",
+    "Rajab",
+    "Shaban",
+    "Ramadan",
+    "Shawwal",
+    "Dhu al-Qadah",
+    "Dhu al-Hijjah",
+];
+const HIJRI_MONTH_NAMES_AR: [&str; 12] = [
+    "محرم",
+    "صفر",
+    "ربيع الأول",
+    "ربيع الآخر",
+    "جمادى الأولى",
+    "جمادى الآخرة",
+    "رجب",
+    "شعبان",
+    "رمضان",
+    "شوال",
+    "ذو القعدة",
+    "ذو الحجة",
+];
+/// Monday first, as in ISO 8601.
+const WEEKDAY_NAMES_EN: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+const WEEKDAY_NAMES_AR: [&str; 7] = [
+    "الاثنين",
+    "الثلاثاء",
+    "الأربعاء",
+    "الخميس",
+    "الجمعة",
+    "السبت",
+    "الأحد",
+];
+
+pub fn std_date() -> StdModule {
+    StdModule::new(
+        "date",
+        "تاريخ",
+        vec![
+            StdFunc::native("now", "الآن", 0, now),
+            StdFunc::native("make", "أنشئ", 3, make),
+            StdFunc::native("at", "عند", 4, at),
+            StdFunc::native("fromMillis", "من_ميلي", 1, from_millis),
+            StdFunc::native("toMillis", "الى_ميلي", 1, to_millis),
+            StdFunc::native("withOffset", "بفارق", 2, with_offset),
+            StdFunc::native("addDays", "أضف_أياما", 2, add_days),
+            StdFunc::native("addSeconds", "أضف_ثواني", 2, add_seconds),
+            StdFunc::native("addMonths", "أضف_أشهرا", 2, add_months),
+            StdFunc::native("diffSeconds", "فرق_الثواني", 2, diff_seconds),
+            StdFunc::native("format", "نسق", 2, format),
+            StdFunc::native("parse", "حلل", 1, parse),
+            StdFunc::native("toHijri", "الى_هجري", 1, to_hijri),
+            StdFunc::native("fromHijri", "من_هجري", 3, from_hijri),
+            StdFunc::native("formatHijri", "نسق_هجري", 2, format_hijri),
+        ],
+    )
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_from_march = (month + 9) % 12;
+    let day_of_year = (153 * month_from_march + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    let (next_year, next_month) = if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    };
+    days_from_civil(next_year, next_month, 1) - days_from_civil(year, month, 1)
+}
+
+/*
+The tabular (arithmetic) Hijri calendar: 30-year cycles with 11 leap years, months
+alternating between 30 and 29 days. It is the only Hijri calendar the module has. The Umm
+al-Qura calendar used officially in Saudi Arabia sets each month's length from astronomical
+tables, and a tabular date can be a day or two off from it; the module doesn't support it.
+ */
+fn hijri_to_jdn(year: i64, month: i64, day: i64) -> i64 {
+    day + (59 * (month - 1) + 1) / 2
+        + (year - 1) * 354
+        + (3 + 11 * year).div_euclid(30)
+        + HIJRI_EPOCH_JDN
+}
+
+fn jdn_to_hijri(jdn: i64) -> (i64, i64, i64) {
+    let year = (30 * (jdn - HIJRI_EPOCH_JDN - 1) + 10646).div_euclid(10631);
+    let days_into_year = jdn - 29 - hijri_to_jdn(year, 1, 1);
+    // ceil(days_into_year / 29.5)
+    let month = (-(-2 * days_into_year).div_euclid(59) + 1).min(12);
+    let day = jdn - hijri_to_jdn(year, month, 1) + 1;
+    (year, month, day)
+}
+
+fn hijri_month_length(year: i64, month: i64) -> i64 {
+    let (next_year, next_month) = if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    };
+    hijri_to_jdn(next_year, next_month, 1) - hijri_to_jdn(year, month, 1)
+}
+
+fn out_of_range(language: Language) -> String {
+    diagnostic::DATE_OUT_OF_RANGE.render(language, &[])
+}
+
+/// Epoch millis of a time `millis_of_day` into a day, with `days` counted from 1970-01-01.
+fn millis_of(language: Language, days: i64, millis_of_day: i64) -> Result<i64, String> {
+    days.checked_mul(MILLIS_PER_DAY)
+        .and_then(|millis| millis.checked_add(millis_of_day))
+        .filter(|millis| millis.abs() <= MAX_MILLIS)
+        .ok_or_else(|| out_of_range(language))
+}
+
+/// Checked before turning a year into days, so the calendar arithmetic can't overflow.
+fn check_year(language: Language, year: i64) -> Result<(), String> {
+    if year.abs() <= MAX_YEAR {
+        Ok(())
+    } else {
+        Err(out_of_range(language))
+    }
+}
+
+/// The local calendar fields of an instant seen at a fixed offset.
+struct Fields {
+    year: i64,
+    month: i64,
+    day: i64,
+    hour: i64,
+    minute: i64,
+    second: i64,
+    /// 1 for Monday through 7 for Sunday.
+    weekday: i64,
+    days: i64,
+}
+
+impl Fields {
+    fn new(language: Language, millis: i64, offset: i64) -> Result<Fields, String> {
+        let local = offset
+            .checked_mul(MILLIS_PER_MINUTE)
+            .and_then(|offset| millis.checked_add(offset))
+            .ok_or_else(|| out_of_range(language))?;
+        let days = local.div_euclid(MILLIS_PER_DAY);
+        let millis_of_day = local.rem_euclid(MILLIS_PER_DAY);
+        let (year, month, day) = civil_from_days(days);
+        Ok(Fields {
+            year,
+            month,
+            day,
+            hour: millis_of_day / 3_600_000,
+            minute: millis_of_day / MILLIS_PER_MINUTE % 60,
+            second: millis_of_day / 1000 % 60,
+            // 1970-01-01 was a Thursday
+            weekday: (days + 3).rem_euclid(7) + 1,
+            days,
+        })
+    }
+}
+
+/// The class of every date, allocated once by the VM.
+pub(crate) fn make_date_class(heap: &mut gc::Heap) -> gc::HeapId {
+    heap.manage_class(value::Class {
+        name: localized("DateTime", "تاريخ_وقت").to_string(),
+        methods: HashMap::new(),
+        superclass: None,
+    })
+}
+
+/// A date at `millis`, or an error if that's out of range.
+fn make_date(
+    interp: &mut virtual_machine::VirtualMachine,
+    millis: i64,
+    offset: i64,
+) -> Result<value::Value, String> {
+    if millis.abs() > MAX_MILLIS {
+        return Err(out_of_range(interp.language));
+    }
+    let fields = Fields::new(interp.language, millis, offset)?;
+    let class_id = interp.date_class;
+
+    let fields = [
+        (localized("year", "سنة"), fields.year),
+        (localized("month", "شهر"), fields.month),
+        (localized("day", "يوم"), fields.day),
+        (localized("hour", "ساعة"), fields.hour),
+        (localized("minute", "دقيقة"), fields.minute),
+        (localized("second", "ثانية"), fields.second),
+        (localized("weekday", "يوم_الأسبوع"), fields.weekday),
+        (localized("offset", "فارق"), offset),
+        (localized("millis", "ميلي"), millis),
+    ]
+    .into_iter()
+    .map(|(name, val)| (normalized(name), value::Value::Integer(val)))
+    .collect();
+
+    Ok(value::Value::Instance(
+        interp
+            .heap
+            .manage_instance(value::Instance { class_id, fields }),
+    ))
+}
+
+/// The epoch millis and the offset of a date.
+fn expect_date(
+    interp: &virtual_machine::VirtualMachine,
+    val: &value::Value,
+) -> Result<(i64, i64), String> {
+    let err = || {
+        format!(
+            "Invalid call: expected date, got {}",
+            value::type_of(val).name(interp.language)
+        )
+    };
+    let instance = match val {
+        value::Value::Instance(id) => interp.heap.get_instance(*id),
+        _ => return Err(err()),
+    };
+    if instance.class_id != interp.date_class {
+        return Err(err());
+    }
+
+    let field = |name: &str| match instance.fields.get(&normalized(name)) {
+        Some(value::Value::Integer(val)) => Ok(*val),
+        _ => Err(err()),
+    };
+    Ok((
+        field(localized("millis", "ميلي"))?,
+        field(localized("offset", "فارق"))?,
+    ))
+}
+
+fn check_time_of_day(hour: i64, minute: i64, second: i64) -> Result<(), String> {
+    if (0..24).contains(&hour) && (0..60).contains(&minute) && (0..60).contains(&second) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid time {:02}:{:02}:{:02}",
+            hour, minute, second
+        ))
+    }
+}
+
+fn check_offset(offset: i64) -> Result<(), String> {
+    if offset.abs() < 24 * 60 {
+        Ok(())
+    } else {
+        Err(format!("Invalid offset of {} minutes", offset))
+    }
+}
+
+fn now(
+    interp: &mut virtual_machine::VirtualMachine,
+    _args: &[value::Value],
+) -> Result<value::Value, String> {
+    let since_the_epoch = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    make_date(interp, since_the_epoch.as_millis() as i64, 0)
+}
+
+/// Midnight UTC of a Gregorian date.
+fn make(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let year = expect_integer(interp, &args[0])?;
+    let month = expect_integer(interp, &args[1])?;
+    let day = expect_integer(interp, &args[2])?;
+    check_year(interp.language, year)?;
+    if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+        return Err(format!("Invalid date {}-{:02}-{:02}", year, month, day));
+    }
+    make_date(
+        interp,
+        millis_of(interp.language, days_from_civil(year, month, day), 0)?,
+        0,
+    )
+}
+
+/// The same local day at another local time.
+fn at(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let (millis, offset) = expect_date(interp, &args[0])?;
+    let hour = expect_integer(interp, &args[1])?;
+    let minute = expect_integer(interp, &args[2])?;
+    let second = expect_integer(interp, &args[3])?;
+    check_time_of_day(hour, minute, second)?;
+
+    let fields = Fields::new(interp.language, millis, offset)?;
+    let local = millis_of(
+        interp.language,
+        fields.days,
+        ((hour * 60 + minute) * 60 + second) * 1000,
+    )?;
+    make_date(interp, local - offset * MILLIS_PER_MINUTE, offset)
+}
+
+fn from_millis(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let millis = expect_integer(interp, &args[0])?;
+    make_date(interp, millis, 0)
+}
+
+fn to_millis(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let (millis, _) = expect_date(interp, &args[0])?;
+    Ok(value::Value::Integer(millis))
+}
+
+/// The same instant seen from a time zone `args[1]` minutes ahead of UTC.
+fn with_offset(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let (millis, _) = expect_date(interp, &args[0])?;
+    let offset = expect_integer(interp, &args[1])?;
+    check_offset(offset)?;
+    make_date(interp, millis, offset)
+}
+
+fn add_days(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let (millis, offset) = expect_date(interp, &args[0])?;
+    let days: i64 = expect_integer(interp, &args[1])?;
+    let millis = days
+        .checked_mul(MILLIS_PER_DAY)
+        .and_then(|delta| millis.checked_add(delta))
+        .ok_or_else(|| out_of_range(interp.language))?;
+    make_date(interp, millis, offset)
+}
+
+fn add_seconds(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let (millis, offset) = expect_date(interp, &args[0])?;
+    let seconds = expect_number(interp, &args[1])?.to_f64();
+    let millis = millis as f64 + (seconds * 1000.0).round();
+    if !millis.is_finite() || millis.abs() >= i64::MAX as f64 {
+        return Err(out_of_range(interp.language));
+    }
+    make_date(interp, millis as i64, offset)
+}
+
+/// Moves by calendar months, clamping the day to the end of a shorter month.
+fn add_months(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let (millis, offset) = expect_date(interp, &args[0])?;
+    let months = expect_integer(interp, &args[1])?;
+
+    let fields = Fields::new(interp.language, millis, offset)?;
+    let month_index = (fields.year * 12 + fields.month - 1)
+        .checked_add(months)
+        .ok_or_else(|| out_of_range(interp.language))?;
+    let (year, month) = (month_index.div_euclid(12), month_index.rem_euclid(12) + 1);
+    check_year(interp.language, year)?;
+    let day = fields.day.min(days_in_month(year, month));
+
+    let millis_of_day = millis + offset * MILLIS_PER_MINUTE - fields.days * MILLIS_PER_DAY;
+    let local = millis_of(
+        interp.language,
+        days_from_civil(year, month, day),
+        millis_of_day,
+    )?;
+    make_date(interp, local - offset * MILLIS_PER_MINUTE, offset)
+}
+
+/// `args[0] - args[1]` in seconds.
+fn diff_seconds(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let (millis1, _) = expect_date(interp, &args[0])?;
+    let (millis2, _) = expect_date(interp, &args[1])?;
+    let diff = millis1 - millis2;
+    if diff % 1000 == 0 {
+        Ok(value::Value::Integer(diff / 1000))
+    } else {
+        Ok(value::Value::Number(diff as f64 / 1000.0))
+    }
+}
+
+/*
+Patterns are made of `yyyy`, `MMMM` (month name), `MM`, `M`, `dd`, `d`, `HH`, `H`, `mm`,
+`ss`, `EEEE` (weekday name) and `Z` (offset as `+03:00`); anything else is copied as is.
+ */
+fn format_pattern(
+    interp: &virtual_machine::VirtualMachine,
+    pattern: &str,
+    date: (i64, i64, i64),
+    month_names: &[&str; 12],
+    fields: &Fields,
+    offset: i64,
+) -> String {
+    let (year, month, day) = date;
+    let weekday_names = if cfg!(feature = "ar") {
+        &WEEKDAY_NAMES_AR
+    } else {
+        &WEEKDAY_NAMES_EN
+    };
+    let digits =
+        |num: i64, width: usize| make_digits(interp, format!("{:0width$}", num, width = width));
+
+    let tokens: [(&str, &dyn Fn() -> String); 12] = [
+        ("yyyy", &|| digits(year, 4)),
+        ("MMMM", &|| month_names[month as usize - 1].to_string()),
+        ("MM", &|| digits(month, 2)),
+        ("M", &|| digits(month, 1)),
+        ("dd", &|| digits(day, 2)),
+        ("d", &|| digits(day, 1)),
+        ("HH", &|| digits(fields.hour, 2)),
+        ("H", &|| digits(fields.hour, 1)),
+        ("mm", &|| digits(fields.minute, 2)),
+        ("ss", &|| digits(fields.second, 2)),
+        ("EEEE", &|| {
+            weekday_names[fields.weekday as usize - 1].to_string()
+        }),
+        ("Z", &|| {
+            let sign = if offset < 0 { "-" } else { "+" };
+            format!(
+                "{}{}:{}",
+                sign,
+                digits(offset.abs() / 60, 2),
+                digits(offset.abs() % 60, 2)
+            )
+        }),
+    ];
+
+    let mut res = String::new();
+    let mut rest = pattern;
+    'outer: while !rest.is_empty() {
+        for (token, expand) in tokens.iter() {
+            if let Some(after) = rest.strip_prefix(token) {
+                res.push_str(&expand());
+                rest = after;
+                continue 'outer;
+            }
+        }
+        let c = rest.chars().next().unwrap();
+        res.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    res
+}
+
+fn format(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let (millis, offset) = expect_date(interp, &args[0])?;
+    let pattern = expect_string(interp, &args[1])?;
+    let fields = Fields::new(interp.language, millis, offset)?;
+    let month_names = if cfg!(feature = "ar") {
+        &MONTH_NAMES_AR
+    } else {
+        &MONTH_NAMES_EN
+    };
+    let res = format_pattern(
+        interp,
+        &pattern,
+        (fields.year, fields.month, fields.day),
+        month_names,
+        &fields,
+        offset,
+    );
+    Ok(value::Value::String(interp.heap.manage_str(res)))
+}
+
+/// Like `format`, with the date in the tabular Hijri calendar.
+fn format_hijri(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let (millis, offset) = expect_date(interp, &args[0])?;
+    let pattern = expect_string(interp, &args[1])?;
+    let fields = Fields::new(interp.language, millis, offset)?;
+    let month_names = if cfg!(feature = "ar") {
+        &HIJRI_MONTH_NAMES_AR
+    } else {
+        &HIJRI_MONTH_NAMES_EN
+    };
+    let res = format_pattern(
+        interp,
+        &pattern,
+        jdn_to_hijri(fields.days + UNIX_EPOCH_JDN),
+        month_names,
+        &fields,
+        offset,
+    );
+    Ok(value::Value::String(interp.heap.manage_str(res)))
+}
+
+/// The local date in the tabular Hijri calendar as a `(year, month, day)` tuple.
+fn to_hijri(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let (millis, offset) = expect_date(interp, &args[0])?;
+    let (year, month, day) =
+        jdn_to_hijri(Fields::new(interp.language, millis, offset)?.days + UNIX_EPOCH_JDN);
+    let elements = vec![
+        value::Value::Integer(year),
+        value::Value::Integer(month),
+        value::Value::Integer(day),
+    ];
+    Ok(value::Value::Tuple(interp.heap.manage_tuple(elements)))
+}
+
+/// Midnight UTC of a date in the tabular Hijri calendar.
+fn from_hijri(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let year = expect_integer(interp, &args[0])?;
+    let month = expect_integer(interp, &args[1])?;
+    let day = expect_integer(interp, &args[2])?;
+    check_year(interp.language, year)?;
+    if year < 1 || !(1..=12).contains(&month) || day < 1 || day > hijri_month_length(year, month) {
+        return Err(format!(
+            "Invalid Hijri date {}-{:02}-{:02}",
+            year, month, day
+        ));
+    }
+    let days = hijri_to_jdn(year, month, day) - UNIX_EPOCH_JDN;
+    make_date(interp, millis_of(interp.language, days, 0)?, 0)
+}
+
+/// Parses ISO 8601 dates such as `2026-03-05`, `2026-03-05T10:20` and
+/// `2026-03-05T10:20:30+03:00`, with Arabic-Indic digits too under the `ar` feature.
+fn parse(
+    interp: &mut virtual_machine::VirtualMachine,
+    args: &[value::Value],
+) -> Result<value::Value, String> {
+    let text = expect_string(interp, &args[0])?;
+    let (millis, offset) = parse_iso(&western_digits(text.trim())).ok_or_else(|| {
+        format!(
+            "Invalid date '{}', expected a date like 2026-03-05T10:20:30+03:00",
+            text
+        )
+    })?;
+    make_date(interp, millis, offset)
+}
+
+fn parse_iso(text: &str) -> Option<(i64, i64)> {
+    let number = |s: &str, len: usize| -> Option<i64> {
+        if s.len() == len && s.chars().all(|c| c.is_ascii_digit()) {
+            s.parse().ok()
+        } else {
+            None
+        }
+    };
+
+    let date = text.get(..10)?;
+    let rest = &text[10..];
+    let mut parts = date.split('-');
+    let year = number(parts.next()?, 4)?;
+    let month = number(parts.next()?, 2)?;
+    let day = number(parts.next()?, 2)?;
+    if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+        return None;
+    }
+
+    let (time, zone) = match rest.strip_prefix(['T', ' ']) {
+        Some(rest) => {
+            let zone_start = rest.find(['Z', '+', '-']).unwrap_or(rest.len());
+            rest.split_at(zone_start)
+        }
+        None if rest.is_empty() => ("00:00", ""),
+        None => return None,
+    };
+
+    let mut time_parts = time.split(':');
+    let hour = number(time_parts.next()?, 2)?;
+    let minute = number(time_parts.next()?, 2)?;
+    let second = match time_parts.next() {
+        Some(second) => number(second, 2)?,
+        None => 0,
+    };
+    if time_parts.next().is_some() || check_time_of_day(hour, minute, second).is_err() {
+        return None;
+    }
+
+    let offset = match zone {
+        "" | "Z" => 0,
+        _ => {
+            let sign = if zone.starts_with('-') { -1 } else { 1 };
+            let (hours, minutes) = zone[1..].split_once(':')?;
+            let offset = sign * (number(hours, 2)? * 60 + number(minutes, 2)?);
+            check_offset(offset).ok()?;
+            offset
+        }
+    };
+
+    let local = days_from_civil(year, month, day) * MILLIS_PER_DAY
+        + ((hour * 60 + minute) * 60 + second) * 1000;
+    Some((local - offset * MILLIS_PER_MINUTE, offset))
+}
+
+fn western_digits(text: &str) -> String {
+    #[cfg(feature = "ar")]
+    {
+        use arabic_utils::arabic_char::ArabicChar;
+        text.chars()
+            .map(|c| {
+                if c.is_arabic_digit() {
+                    c.actoec().unwrap()
+                } else {
+                    c
+                }
+            })
+            .collect()
+    }
+    #[cfg(not(feature = "ar"))]
+    {
+        text.to_string()
+    }
 }
@@ -1,6 +1,8 @@
 use crate::gc;
 use crate::virtual_machine;
 use firnas_bytecode as bytecode;
+use firnas_compiler::diagnostic::Language;
+use num_rational::BigRational;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
@@ -36,6 +38,9 @@ pub struct Closure {
 #[derive(Clone)]
 pub struct NativeFunction {
     pub arity: u8,
+    /// Trailing arguments after the `arity` required ones that a call may leave out. The
+    /// native is passed nil for those.
+    pub optional: u8,
     pub name: String,
     pub func: fn(&mut virtual_machine::VirtualMachine, &[Value]) -> Result<Value, String>,
 }
@@ -44,6 +49,7 @@ pub struct NativeFunction {
 pub struct Class {
     pub name: String,
     pub methods: HashMap<String, gc::HeapId>,
+    pub superclass: Option<gc::HeapId>,
 }
 
 #[derive(Clone)]
@@ -61,6 +67,9 @@ pub struct BoundMethod {
 #[derive(Clone)]
 pub enum Value {
     Number(f64),
+    Integer(i64),
+    BigInt(gc::HeapId),
+    Rational(gc::HeapId),
     Bool(bool),
     String(gc::HeapId),
     Function(gc::HeapId),
@@ -70,11 +79,18 @@ pub enum Value {
     NativeFunction(NativeFunction),
     Nil,
     List(gc::HeapId),
+    Tuple(gc::HeapId),
+    Set(gc::HeapId),
+    Dict(gc::HeapId),
+    Regex(gc::HeapId),
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum Type {
     Number,
+    Integer,
+    BigInt,
+    Rational,
     Bool,
     String,
     Function,
@@ -84,11 +100,64 @@ pub enum Type {
     Instance,
     Nil,
     List,
+    Tuple,
+    Set,
+    Dict,
+    Regex,
+}
+
+impl Type {
+    /// The name of the type in error messages.
+    pub fn name(self, language: Language) -> &'static str {
+        match language {
+            Language::English => match self {
+                Type::Number => "Number",
+                Type::Integer => "Integer",
+                Type::BigInt => "BigInt",
+                Type::Rational => "Rational",
+                Type::Bool => "Bool",
+                Type::String => "String",
+                Type::Function => "Function",
+                Type::NativeFunction => "NativeFunction",
+                Type::Class => "Class",
+                Type::BoundMethod => "BoundMethod",
+                Type::Instance => "Instance",
+                Type::Nil => "Nil",
+                Type::List => "List",
+                Type::Tuple => "Tuple",
+                Type::Set => "Set",
+                Type::Dict => "Dict",
+                Type::Regex => "Regex",
+            },
+            Language::Arabic => match self {
+                Type::Number => "رقم",
+                Type::Integer => "عدد صحيح",
+                Type::BigInt => "عدد صحيح كبير",
+                Type::Rational => "عدد نسبي",
+                Type::Bool => "منطقي",
+                Type::String => "نص",
+                Type::Function => "دالة",
+                Type::NativeFunction => "دالة مدمجة",
+                Type::Class => "صنف",
+                Type::BoundMethod => "طريقة",
+                Type::Instance => "كائن",
+                Type::Nil => "عدم",
+                Type::List => "قائمة",
+                Type::Tuple => "صف",
+                Type::Set => "مجموعة",
+                Type::Dict => "قاموس",
+                Type::Regex => "نمط",
+            },
+        }
+    }
 }
 
 pub fn type_of(value: &Value) -> Type {
     match value {
         Value::Number(_) => Type::Number,
+        Value::Integer(_) => Type::Integer,
+        Value::BigInt(_) => Type::BigInt,
+        Value::Rational(_) => Type::Rational,
         Value::Bool(_) => Type::Bool,
         Value::String(_) => Type::String,
         Value::Function(_) => Type::Function,
@@ -98,5 +167,24 @@ pub fn type_of(value: &Value) -> Type {
         Value::Instance(_) => Type::Instance,
         Value::Nil => Type::Nil,
         Value::List(_) => Type::List,
+        Value::Tuple(_) => Type::Tuple,
+        Value::Set(_) => Type::Set,
+        Value::Dict(_) => Type::Dict,
+        Value::Regex(_) => Type::Regex,
     }
 }
+
+/*
+The hashable projection of a value, for use as a key in hashed collections.
+Values that compare equal map to the same key, so `1`, `1.0` and `1r` share one.
+Mutable values (lists, instances) have no key.
+ */
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HashKey {
+    Nil,
+    Bool(bool),
+    Number(BigRational),
+    Float(u64),
+    String(String),
+    Tuple(Vec<HashKey>),
+}
@@ -1,27 +1,124 @@
 use crate::gc;
+use crate::numeric;
 use crate::stdlib;
+use crate::stdlib::collection::std_collection_add;
+use crate::stdlib::collection::std_collection_all;
+use crate::stdlib::collection::std_collection_any;
+use crate::stdlib::collection::std_collection_contains;
+use crate::stdlib::collection::std_collection_enumerate;
+use crate::stdlib::collection::std_collection_filter;
+use crate::stdlib::collection::std_collection_find;
+use crate::stdlib::collection::std_collection_for_each;
+use crate::stdlib::collection::std_collection_index_of;
+use crate::stdlib::collection::std_collection_insert;
+use crate::stdlib::collection::std_collection_keys;
+use crate::stdlib::collection::std_collection_len;
+use crate::stdlib::collection::std_collection_map;
+use crate::stdlib::collection::std_collection_pop;
+use crate::stdlib::collection::std_collection_push;
+use crate::stdlib::collection::std_collection_range;
+use crate::stdlib::collection::std_collection_reduce;
+use crate::stdlib::collection::std_collection_remove;
+use crate::stdlib::collection::std_collection_reverse;
+use crate::stdlib::collection::std_collection_sort;
+use crate::stdlib::collection::std_collection_use_collation;
+use crate::stdlib::collection::std_collection_values;
+use crate::stdlib::collection::std_collection_zip;
+use crate::stdlib::file::std_file_append;
+use crate::stdlib::file::std_file_delete;
+use crate::stdlib::file::std_file_exists;
+use crate::stdlib::file::std_file_list_dir;
+use crate::stdlib::file::std_file_read;
+use crate::stdlib::file::std_file_write;
+use crate::stdlib::file::FileAccess;
+use crate::stdlib::io::std_io_input;
 use crate::stdlib::io::std_io_print;
 use crate::stdlib::io::std_io_print_line;
+use crate::stdlib::io::std_io_read_all;
 use crate::stdlib::io::DefaultStdIO;
 use crate::stdlib::io::StdIO;
+use crate::stdlib::json::std_json;
+use crate::stdlib::math::std_math_abs;
+use crate::stdlib::math::std_math_acos;
+use crate::stdlib::math::std_math_asin;
+use crate::stdlib::math::std_math_atan;
+use crate::stdlib::math::std_math_atan2;
+use crate::stdlib::math::std_math_ceil;
+use crate::stdlib::math::std_math_cos;
+use crate::stdlib::math::std_math_e;
 use crate::stdlib::math::std_math_exp;
+use crate::stdlib::math::std_math_floor;
+use crate::stdlib::math::std_math_gcd;
+use crate::stdlib::math::std_math_hypot;
+use crate::stdlib::math::std_math_inf;
+use crate::stdlib::math::std_math_is_prime;
+use crate::stdlib::math::std_math_lcm;
+use crate::stdlib::math::std_math_log;
+use crate::stdlib::math::std_math_log10;
+use crate::stdlib::math::std_math_log2;
+use crate::stdlib::math::std_math_max;
+use crate::stdlib::math::std_math_min;
+use crate::stdlib::math::std_math_nan;
+use crate::stdlib::math::std_math_pi;
+use crate::stdlib::math::std_math_pow;
+use crate::stdlib::math::std_math_round;
+use crate::stdlib::math::std_math_sin;
 use crate::stdlib::math::std_math_sqrt;
+use crate::stdlib::math::std_math_tan;
+use crate::stdlib::math::std_math_trunc;
+use crate::stdlib::number::std_number_amount_to_arabic_words;
+use crate::stdlib::number::std_number_denominator;
+use crate::stdlib::number::std_number_float;
+use crate::stdlib::number::std_number_int;
+use crate::stdlib::number::std_number_numerator;
+use crate::stdlib::number::std_number_rational;
+use crate::stdlib::number::std_number_to_arabic_words;
+use crate::stdlib::number::std_number_to_number;
+#[cfg(feature = "ar")]
+use crate::stdlib::number::std_number_use_digits;
+use crate::stdlib::pattern::std_regex;
+use crate::stdlib::random::std_random_choice;
+use crate::stdlib::random::std_random_randint;
+use crate::stdlib::random::std_random_random;
+use crate::stdlib::random::std_random_seed;
+use crate::stdlib::random::std_random_shuffle;
+use crate::stdlib::reflect::std_reflect_arity;
+use crate::stdlib::reflect::std_reflect_fields;
+use crate::stdlib::reflect::std_reflect_get_attr;
+use crate::stdlib::reflect::std_reflect_has_attr;
+use crate::stdlib::reflect::std_reflect_methods;
+use crate::stdlib::reflect::std_reflect_name;
+use crate::stdlib::reflect::std_reflect_set_attr;
+use crate::stdlib::reflect::std_reflect_superclass;
+use crate::stdlib::reflect::std_reflect_type;
+use crate::stdlib::string::std_string_chars;
+use crate::stdlib::string::std_string_ends_with;
+use crate::stdlib::string::std_string_find_substring;
+use crate::stdlib::string::std_string_join;
+use crate::stdlib::string::std_string_lower;
+use crate::stdlib::string::std_string_replace;
+use crate::stdlib::string::std_string_split;
+use crate::stdlib::string::std_string_starts_with;
+use crate::stdlib::string::std_string_str;
+use crate::stdlib::string::std_string_substring;
+use crate::stdlib::string::std_string_trim;
+use crate::stdlib::string::std_string_upper;
+use crate::stdlib::time::std_date;
 use crate::stdlib::time::std_time_clock;
 use crate::stdlib::StdFunc;
+use crate::stdlib::StdModule;
 use crate::value;
 use firnas_bytecode;
+use firnas_compiler::diagnostic;
+use firnas_compiler::diagnostic::Language;
+use firnas_compiler::diagnostic::Message;
+use num_traits::Zero;
 use std::cell::RefCell;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::fmt;
 use std::rc::Rc;
-
-#[derive(Debug)]
-enum Binop {
-    Add,
-    Sub,
-    Mul,
-    Div,
-}
+use unicode_segmentation::UnicodeSegmentation;
 
 pub struct VirtualMachine {
     pub frames: Vec<CallFrame>,
@@ -32,30 +129,44 @@ pub struct VirtualMachine {
     pub heap: gc::Heap,
     gray_stack: Vec<gc::HeapId>,
     pub std_io: Box<dyn StdIO>,
+    pub rng: stdlib::random::Rng,
+    pub file_access: FileAccess,
+    /// How `<`, `>` and `sort` order strings, chosen by the program with `useCollation`.
+    pub collation: arabic_utils::arabic_collation::Collation,
+    /// The digits numbers are printed with, chosen by the program with `استخدم_ارقام`.
+    #[cfg(feature = "ar")]
+    pub digits: arabic_utils::arabic_digits::DigitSet,
+    /// The language runtime errors are reported in.
+    pub language: Language,
+    /// The class of the date module's dates.
+    pub(crate) date_class: gc::HeapId,
 }
 
 impl VirtualMachine {
     pub fn new(std_io: Box<dyn StdIO>) -> Self {
+        let mut heap = gc::Heap::default();
+        let date_class = stdlib::time::make_date_class(&mut heap);
         let mut res = VirtualMachine {
             frames: Default::default(),
             stack: Default::default(),
             output: Default::default(),
             globals: Default::default(),
             upvalues: Default::default(),
-            heap: Default::default(),
+            heap,
             gray_stack: Default::default(),
             std_io,
+            rng: Default::default(),
+            file_access: Default::default(),
+            collation: Default::default(),
+            #[cfg(feature = "ar")]
+            digits: Default::default(),
+            language: Default::default(),
+            date_class,
         };
         res.stack.reserve(256);
         res.frames.reserve(64);
 
-        res.add_std_func(std_io_print());
-        res.add_std_func(std_io_print_line());
-
-        res.add_std_func(std_time_clock());
-
-        res.add_std_func(std_math_exp());
-        res.add_std_func(std_math_sqrt());
+        res.add_std_funcs();
         res
     }
 
@@ -68,79 +179,231 @@ impl VirtualMachine {
     }
 
     fn add_std_func(&mut self, std_func: StdFunc) {
-        self.globals.insert(std_func.name, std_func.func);
+        self.globals
+            .insert(stdlib::normalized(&std_func.name), std_func.func);
+    }
+
+    fn add_std_module(&mut self, module: StdModule) {
+        let class_id = self.heap.manage_class(value::Class {
+            name: module.name.clone(),
+            methods: HashMap::new(),
+            superclass: None,
+        });
+        let fields = module
+            .members
+            .into_iter()
+            .map(|member| (stdlib::normalized(&member.name), member.func))
+            .collect();
+        let instance_id = self
+            .heap
+            .manage_instance(value::Instance { class_id, fields });
+        self.globals.insert(
+            stdlib::normalized(&module.name),
+            value::Value::Instance(instance_id),
+        );
+    }
+
+    fn add_std_funcs(&mut self) {
+        self.add_std_func(std_io_print());
+        self.add_std_func(std_io_print_line());
+        self.add_std_func(std_io_input());
+        self.add_std_func(std_io_read_all());
+
+        self.add_std_func(std_time_clock());
+
+        self.add_std_func(std_file_read());
+        self.add_std_func(std_file_write());
+        self.add_std_func(std_file_append());
+        self.add_std_func(std_file_list_dir());
+        self.add_std_func(std_file_exists());
+        self.add_std_func(std_file_delete());
+
+        self.add_std_func(std_math_exp());
+        self.add_std_func(std_math_sqrt());
+        self.add_std_func(std_math_sin());
+        self.add_std_func(std_math_cos());
+        self.add_std_func(std_math_tan());
+        self.add_std_func(std_math_asin());
+        self.add_std_func(std_math_acos());
+        self.add_std_func(std_math_atan());
+        self.add_std_func(std_math_atan2());
+        self.add_std_func(std_math_log());
+        self.add_std_func(std_math_log10());
+        self.add_std_func(std_math_log2());
+        self.add_std_func(std_math_pow());
+        self.add_std_func(std_math_abs());
+        self.add_std_func(std_math_floor());
+        self.add_std_func(std_math_ceil());
+        self.add_std_func(std_math_round());
+        self.add_std_func(std_math_trunc());
+        self.add_std_func(std_math_min());
+        self.add_std_func(std_math_max());
+        self.add_std_func(std_math_hypot());
+        self.add_std_func(std_math_gcd());
+        self.add_std_func(std_math_lcm());
+        self.add_std_func(std_math_is_prime());
+        self.add_std_func(std_math_pi());
+        self.add_std_func(std_math_e());
+        self.add_std_func(std_math_inf());
+        self.add_std_func(std_math_nan());
+
+        self.add_std_func(std_number_int());
+        self.add_std_func(std_number_float());
+        self.add_std_func(std_number_rational());
+        self.add_std_func(std_number_numerator());
+        self.add_std_func(std_number_denominator());
+        self.add_std_func(std_number_to_number());
+        self.add_std_func(std_number_to_arabic_words());
+        self.add_std_func(std_number_amount_to_arabic_words());
+        #[cfg(feature = "ar")]
+        self.add_std_func(std_number_use_digits());
+
+        self.add_std_func(std_random_random());
+        self.add_std_func(std_random_randint());
+        self.add_std_func(std_random_choice());
+        self.add_std_func(std_random_shuffle());
+        self.add_std_func(std_random_seed());
+
+        self.add_std_func(std_collection_len());
+        self.add_std_func(std_collection_for_each());
+        self.add_std_func(std_collection_map());
+        self.add_std_func(std_collection_filter());
+        self.add_std_func(std_collection_reduce());
+        self.add_std_func(std_collection_sort());
+        self.add_std_func(std_collection_use_collation());
+        self.add_std_func(std_collection_reverse());
+        self.add_std_func(std_collection_zip());
+        self.add_std_func(std_collection_enumerate());
+        self.add_std_func(std_collection_any());
+        self.add_std_func(std_collection_all());
+        self.add_std_func(std_collection_find());
+        self.add_std_func(std_collection_index_of());
+        self.add_std_func(std_collection_push());
+        self.add_std_func(std_collection_pop());
+        self.add_std_func(std_collection_insert());
+        self.add_std_func(std_collection_range());
+        self.add_std_func(std_collection_add());
+        self.add_std_func(std_collection_remove());
+        self.add_std_func(std_collection_contains());
+        self.add_std_func(std_collection_keys());
+        self.add_std_func(std_collection_values());
+
+        self.add_std_func(std_reflect_type());
+        self.add_std_func(std_reflect_fields());
+        self.add_std_func(std_reflect_methods());
+        self.add_std_func(std_reflect_has_attr());
+        self.add_std_func(std_reflect_get_attr());
+        self.add_std_func(std_reflect_set_attr());
+        self.add_std_func(std_reflect_superclass());
+        self.add_std_func(std_reflect_arity());
+        self.add_std_func(std_reflect_name());
+
+        self.add_std_func(std_string_str());
+        self.add_std_func(std_string_substring());
+        self.add_std_func(std_string_find_substring());
+        self.add_std_func(std_string_replace());
+        self.add_std_func(std_string_split());
+        self.add_std_func(std_string_join());
+        self.add_std_func(std_string_trim());
+        self.add_std_func(std_string_upper());
+        self.add_std_func(std_string_lower());
+        self.add_std_func(std_string_starts_with());
+        self.add_std_func(std_string_ends_with());
+        self.add_std_func(std_string_chars());
+
+        self.add_std_module(std_json());
+        self.add_std_module(std_date());
+        self.add_std_module(std_regex());
     }
 }
 
 impl Default for VirtualMachine {
     fn default() -> VirtualMachine {
+        let mut heap = gc::Heap::default();
+        let date_class = stdlib::time::make_date_class(&mut heap);
         let mut res = VirtualMachine {
             frames: Default::default(),
             stack: Default::default(),
             output: Default::default(),
             globals: Default::default(),
             upvalues: Default::default(),
-            heap: Default::default(),
+            heap,
             gray_stack: Default::default(),
-            std_io: Box::new(DefaultStdIO),
+            std_io: Box::new(DefaultStdIO::default()),
+            rng: Default::default(),
+            file_access: Default::default(),
+            collation: Default::default(),
+            #[cfg(feature = "ar")]
+            digits: Default::default(),
+            language: Default::default(),
+            date_class,
         };
         res.stack.reserve(256);
         res.frames.reserve(64);
 
-        res.add_std_func(std_io_print());
-        res.add_std_func(std_io_print_line());
-
-        res.add_std_func(std_time_clock());
-
-        res.add_std_func(std_math_exp());
-        res.add_std_func(std_math_sqrt());
+        res.add_std_funcs();
 
         res.globals.insert(
             String::from("dis"),
             value::Value::NativeFunction(value::NativeFunction {
                 arity: 1,
+                optional: 0,
                 name: String::from("dis"),
                 func: stdlib::debug::dis_builtin,
             }),
         );
-        res.globals.insert(
-            String::from("len"),
-            value::Value::NativeFunction(value::NativeFunction {
-                arity: 1,
-                name: String::from("len"),
-                func: stdlib::collection::len,
-            }),
-        );
-        res.globals.insert(
-            String::from("forEach"),
-            value::Value::NativeFunction(value::NativeFunction {
-                arity: 2,
-                name: String::from("forEach"),
-                func: stdlib::collection::for_each,
-            }),
-        );
-        res.globals.insert(
-            String::from("map"),
-            value::Value::NativeFunction(value::NativeFunction {
-                arity: 2,
-                name: String::from("map"),
-                func: stdlib::collection::map,
-            }),
-        );
 
         res
     }
 }
 
+#[derive(Debug, Copy, Clone)]
+struct SliceBounds {
+    start: Option<i64>,
+    end: Option<i64>,
+    step: i64,
+}
+
+#[derive(Debug, Copy, Clone)]
+enum SetBinop {
+    Union,
+    Intersection,
+    Difference,
+}
+
+impl SetBinop {
+    fn name(self, language: Language) -> &'static str {
+        match (self, language) {
+            (SetBinop::Union, Language::English) => "Union",
+            (SetBinop::Intersection, Language::English) => "Intersection",
+            (SetBinop::Difference, Language::English) => "Difference",
+            (SetBinop::Union, Language::Arabic) => "الاتحاد",
+            (SetBinop::Intersection, Language::Arabic) => "التقاطع",
+            (SetBinop::Difference, Language::Arabic) => "الفرق",
+        }
+    }
+}
+
 #[derive(Eq, PartialEq, Debug, Clone)]
 pub enum VmError {
-    Runtime(String),
+    Runtime(RuntimeError),
+}
+
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct RuntimeError {
+    /// The code of the catalogue entry the error was reported with.
+    pub code: &'static str,
+    pub what: String,
+    pub language: Language,
 }
 
 impl fmt::Display for VmError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            VmError::Runtime(err) => write!(f, "Lox runtime error: {}", err),
+            VmError::Runtime(err) => {
+                let message = diagnostic::RUNTIME_ERROR.render(err.language, &[&err.what]);
+                write!(f, "{}", message)
+            }
         }
     }
 }
@@ -216,10 +479,30 @@ impl VirtualMachine {
         }
     }
 
+    /*
+    The one way values are turned into text, shared by printing, string concatenation and error
+    messages. Under the `ar` feature numbers use Arabic digits and collections use the Arabic comma.
+     */
     pub fn format_val(&self, val: &value::Value) -> String {
+        self.format_nested(val, &mut Vec::new())
+    }
+
+    /*
+    `visiting` holds the lists and dictionaries we are inside of, so that one that contains itself
+    is printed as `[...]` or `{...}` rather than recursing forever.
+     */
+    fn format_nested(&self, val: &value::Value, visiting: &mut Vec<gc::HeapId>) -> String {
+        let separator = stdlib::localized(", ", "، ");
         match val {
-            value::Value::Number(num) => num.to_string(),
-            value::Value::Bool(b) => b.to_string(),
+            value::Value::Number(num) => stdlib::io::make_number(self, *num),
+            value::Value::Integer(num) => stdlib::io::make_integer(self, *num),
+            value::Value::BigInt(id) => {
+                stdlib::io::make_digits(self, self.heap.get_bigint(*id).to_string())
+            }
+            value::Value::Rational(id) => {
+                stdlib::io::make_digits(self, numeric::format_rational(self.heap.get_rational(*id)))
+            }
+            value::Value::Bool(b) => stdlib::io::make_bool(*b),
             value::Value::String(str_handle) => self.get_str(*str_handle).clone(),
             value::Value::Function(closure_handle) => {
                 format!("<fn '{}'>", self.get_closure(*closure_handle).function.name)
@@ -233,23 +516,70 @@ impl VirtualMachine {
                 format!("<{} instance>", class_name)
             }
             value::Value::NativeFunction(func) => format!("<native fn {}>", func.name),
+            value::Value::Regex(id) => format!("<regex '{}'>", self.heap.get_regex(*id).as_str()),
             value::Value::BoundMethod(bound_method_id) => {
                 let bound_method = self.get_bound_method(*bound_method_id);
                 let instance = self.get_instance(bound_method.instance_id);
                 let class_name = &self.get_class(instance.class_id).name;
                 format!("<bound method of {} instance>", class_name)
             }
-            value::Value::Nil => "nil".to_string(),
+            value::Value::Nil => stdlib::io::make_nil(),
             value::Value::List(list_id) => {
-                let elements = self.get_list_elements(*list_id);
-                format!(
-                    "[{}]",
-                    elements
-                        .iter()
-                        .map(|element| self.format_val(element))
-                        .collect::<Vec<String>>()
-                        .join(", ")
-                )
+                if visiting.contains(list_id) {
+                    return "[...]".to_string();
+                }
+                visiting.push(*list_id);
+                let formatted = self
+                    .get_list_elements(*list_id)
+                    .iter()
+                    .map(|element| self.format_nested(element, visiting))
+                    .collect::<Vec<String>>();
+                visiting.pop();
+                format!("[{}]", formatted.join(separator))
+            }
+            value::Value::Tuple(tuple_id) => {
+                let formatted = self
+                    .get_tuple_elements(*tuple_id)
+                    .iter()
+                    .map(|element| self.format_nested(element, visiting))
+                    .collect::<Vec<String>>();
+                if formatted.len() == 1 {
+                    format!("({}{})", formatted[0], separator.trim_end())
+                } else {
+                    format!("({})", formatted.join(separator))
+                }
+            }
+            value::Value::Set(set_id) => {
+                // set elements are hashable, so they can't lead back to the set
+                let formatted = self
+                    .get_set_elements(*set_id)
+                    .values()
+                    .map(|element| self.format_nested(element, visiting))
+                    .collect::<Vec<String>>();
+                format!("{{{}}}", formatted.join(separator))
+            }
+            value::Value::Dict(dict_id) => {
+                if visiting.contains(dict_id) {
+                    return "{...}".to_string();
+                }
+                let entries = self.get_dict_entries(*dict_id);
+                if entries.is_empty() {
+                    // `{}` is the empty set
+                    return "{:}".to_string();
+                }
+                visiting.push(*dict_id);
+                let formatted = entries
+                    .values()
+                    .map(|(key, val)| {
+                        format!(
+                            "{}: {}",
+                            self.format_nested(key, visiting),
+                            self.format_nested(val, visiting)
+                        )
+                    })
+                    .collect::<Vec<String>>();
+                visiting.pop();
+                format!("{{{}}}", formatted.join(separator))
             }
         }
     }
@@ -288,8 +618,8 @@ impl VirtualMachine {
                     return Ok(());
                 }
 
-                let num_to_pop = self.stack.len() - self.frame().slots_offset
-                    + usize::from(self.frame().closure.function.arity);
+                // the frame's slots (arguments and locals) plus the callee below them
+                let num_to_pop = self.stack.len() - self.frame().slots_offset + 1;
                 self.frames.pop();
 
                 self.pop_stack_n_times(num_to_pop);
@@ -349,38 +679,39 @@ impl VirtualMachine {
             }
             (firnas_bytecode::Op::Negate, lineno) => {
                 let top_stack = self.peek();
-                let maybe_number = VirtualMachine::extract_number(top_stack);
+                let maybe_number = numeric::Numeric::from_value(&self.heap, top_stack);
 
                 match maybe_number {
-                        Some(to_negate) => {
-                            self.pop_stack();
-                            self.stack.push(value::Value::Number(-to_negate));
-                        }
-                        None => {
-                            return Err(VmError::Runtime(format!(
-                                "invalid operand to unary op negate. Expected number, found {:?} at line {}",
-                                value::type_of(top_stack), lineno.value
-                            )))
-                        }
+                    Some(to_negate) => {
+                        self.pop_stack();
+                        let negated = to_negate.negate().into_value(&mut self.heap);
+                        self.stack.push(negated);
                     }
+                    None => {
+                        return Err(self.runtime_error(
+                            diagnostic::INVALID_NEGATE_OPERAND,
+                            &[
+                                &value::type_of(top_stack).name(self.language),
+                                &lineno.value,
+                            ],
+                        ))
+                    }
+                }
             }
             (firnas_bytecode::Op::Add, lineno) => {
                 let val1 = self.peek_by(0).clone();
                 let val2 = self.peek_by(1).clone();
 
                 match (&val1, &val2) {
-                    (value::Value::Number(_), value::Value::Number(_)) => {
-                        self.numeric_binop(Binop::Add, lineno)?
+                    _ if self.is_number(&val1) && self.is_number(&val2) => {
+                        self.numeric_binop(numeric::Binop::Add, lineno)?
                     }
                     (value::Value::String(s1), value::Value::String(s2)) => {
                         self.pop_stack();
                         self.pop_stack();
+                        let res = format!("{}{}", self.get_str(*s2), self.get_str(*s1));
                         self.stack
-                            .push(value::Value::String(self.heap.manage_str(format!(
-                                "{}{}",
-                                self.get_str(*s2),
-                                self.get_str(*s1)
-                            ))));
+                            .push(value::Value::String(self.heap.manage_str(res)));
                     }
                     (value::Value::List(id1), value::Value::List(id2)) => {
                         self.pop_stack();
@@ -390,46 +721,75 @@ impl VirtualMachine {
                         self.stack
                             .push(value::Value::List(self.heap.manage_list(res)));
                     }
+                    (value::Value::Tuple(id1), value::Value::Tuple(id2)) => {
+                        self.pop_stack();
+                        self.pop_stack();
+                        let mut res = self.get_tuple_elements(*id2).clone();
+                        res.extend(self.get_tuple_elements(*id1).clone());
+                        self.stack
+                            .push(value::Value::Tuple(self.heap.manage_tuple(res)));
+                    }
                     _ => {
-                        return Err(VmError::Runtime(format!(
-                            "invalid operands of type {:?} and {:?} in add expression: \
-                                 both operands must be number or string (line={})",
-                            value::type_of(&val1),
-                            value::type_of(&val2),
-                            lineno.value
-                        )))
+                        return Err(self.runtime_error(
+                            diagnostic::INVALID_ADD_OPERANDS,
+                            &[
+                                &value::type_of(&val1).name(self.language),
+                                &value::type_of(&val2).name(self.language),
+                                &lineno.value,
+                            ],
+                        ))
                     }
                 }
             }
-            (firnas_bytecode::Op::Subtract, lineno) => match self.numeric_binop(Binop::Sub, lineno)
-            {
-                Ok(()) => {}
-                Err(err) => return Err(err),
-            },
-            (firnas_bytecode::Op::Multiply, lineno) => match self.numeric_binop(Binop::Mul, lineno)
-            {
-                Ok(()) => {}
-                Err(err) => return Err(err),
-            },
-            (firnas_bytecode::Op::Divide, lineno) => match self.numeric_binop(Binop::Div, lineno) {
-                Ok(()) => {}
-                Err(err) => return Err(err),
-            },
+            (firnas_bytecode::Op::Subtract, lineno) => {
+                let is_set_difference = matches!(
+                    (self.peek_by(0), self.peek_by(1)),
+                    (value::Value::Set(_), value::Value::Set(_))
+                );
+                if is_set_difference {
+                    self.set_binop(SetBinop::Difference, lineno)?
+                } else {
+                    match self.numeric_binop(numeric::Binop::Sub, lineno) {
+                        Ok(()) => {}
+                        Err(err) => return Err(err),
+                    }
+                }
+            }
+            (firnas_bytecode::Op::Multiply, lineno) => {
+                match self.numeric_binop(numeric::Binop::Mul, lineno) {
+                    Ok(()) => {}
+                    Err(err) => return Err(err),
+                }
+            }
+            (firnas_bytecode::Op::Divide, lineno) => {
+                match self.numeric_binop(numeric::Binop::Div, lineno) {
+                    Ok(()) => {}
+                    Err(err) => return Err(err),
+                }
+            }
+            (firnas_bytecode::Op::Union, lineno) => self.set_binop(SetBinop::Union, lineno)?,
+            (firnas_bytecode::Op::Intersection, lineno) => {
+                self.set_binop(SetBinop::Intersection, lineno)?
+            }
             (firnas_bytecode::Op::Not, lineno) => {
                 let top_stack = self.peek();
                 let maybe_bool = VirtualMachine::extract_bool(top_stack);
 
                 match maybe_bool {
-                        Some(b) => {
-                            self.pop_stack();
-                            self.stack.push(value::Value::Bool(!b));
-                        }
-                        None => {
-                            return Err(VmError::Runtime(format!(
-                                "invalid operand in not expression. Expected boolean, found {:?} at line {}",
-                                value::type_of(top_stack), lineno.value)))
-                        }
+                    Some(b) => {
+                        self.pop_stack();
+                        self.stack.push(value::Value::Bool(!b));
+                    }
+                    None => {
+                        return Err(self.runtime_error(
+                            diagnostic::INVALID_NOT_OPERAND,
+                            &[
+                                &value::type_of(top_stack).name(self.language),
+                                &lineno.value,
+                            ],
+                        ))
                     }
+                }
             }
             (firnas_bytecode::Op::Equal, _) => {
                 let val1 = self.pop_stack();
@@ -441,34 +801,49 @@ impl VirtualMachine {
                 let val1 = self.peek_by(0).clone();
                 let val2 = self.peek_by(1).clone();
 
-                match (&val1, &val2) {
-                        (value::Value::Number(n1), value::Value::Number(n2)) => {
-                            self.pop_stack();
-                            self.pop_stack();
-
-                            self.stack.push(value::Value::Bool(n2 > n1));
-                        }
-                        _ => return Err(VmError::Runtime(format!(
-                            "invalid operands in Greater expression. Expected numbers, found {:?} and {:?} at line {}",
-                            value::type_of(&val1), value::type_of(&val2), lineno.value)))
-
+                match self.compare_values(&val2, &val1) {
+                    Some(ordering) => {
+                        self.pop_stack();
+                        self.pop_stack();
+                        self.stack.push(value::Value::Bool(
+                            ordering == Some(std::cmp::Ordering::Greater),
+                        ));
                     }
+                    None => {
+                        return Err(self.runtime_error(
+                            diagnostic::INVALID_GREATER_OPERANDS,
+                            &[
+                                &value::type_of(&val1).name(self.language),
+                                &value::type_of(&val2).name(self.language),
+                                &lineno.value,
+                            ],
+                        ))
+                    }
+                }
             }
             (firnas_bytecode::Op::Less, lineno) => {
                 let val1 = self.peek_by(0).clone();
                 let val2 = self.peek_by(1).clone();
 
-                match (&val1, &val2) {
-                        (value::Value::Number(n1), value::Value::Number(n2)) => {
-                            self.pop_stack();
-                            self.pop_stack();
-                            self.stack.push(value::Value::Bool(n2 < n1));
-                        }
-                        _ => return Err(VmError::Runtime(format!(
-                            "invalid operands in Less expression. Expected numbers, found {:?} and {:?} at line {}",
-                            value::type_of(&val1), value::type_of(&val2), lineno.value)))
-
+                match self.compare_values(&val2, &val1) {
+                    Some(ordering) => {
+                        self.pop_stack();
+                        self.pop_stack();
+                        self.stack.push(value::Value::Bool(
+                            ordering == Some(std::cmp::Ordering::Less),
+                        ));
+                    }
+                    None => {
+                        return Err(self.runtime_error(
+                            diagnostic::INVALID_LESS_OPERANDS,
+                            &[
+                                &value::type_of(&val1).name(self.language),
+                                &value::type_of(&val2).name(self.language),
+                                &lineno.value,
+                            ],
+                        ))
                     }
+                }
             }
             (firnas_bytecode::Op::Pop, _) => {
                 self.pop_stack();
@@ -486,16 +861,15 @@ impl VirtualMachine {
             }
             (firnas_bytecode::Op::GetGlobal(idx), lineno) => {
                 if let value::Value::String(name_id) = self.read_constant(idx) {
-                    match self.globals.get(self.get_str(name_id)) {
+                    match stdlib::lookup(&self.globals, self.get_str(name_id)) {
                         Some(val) => {
                             self.stack.push(val.clone());
                         }
                         None => {
-                            return Err(VmError::Runtime(format!(
-                                "Undefined variable '{}' at line {}.",
-                                self.get_str(name_id),
-                                lineno.value
-                            )));
+                            return Err(self.runtime_error(
+                                diagnostic::UNDEFINED_VARIABLE,
+                                &[&self.get_str(name_id), &lineno.value],
+                            ));
                         }
                     }
                 } else {
@@ -509,15 +883,20 @@ impl VirtualMachine {
                 if let value::Value::String(name_id) = self.read_constant(idx) {
                     let name_str = self.get_str(name_id).clone();
                     let val = self.peek().clone();
+                    let key = if self.globals.contains_key(&name_str) {
+                        name_str.clone()
+                    } else {
+                        stdlib::normalized(&name_str)
+                    };
                     if let std::collections::hash_map::Entry::Occupied(mut e) =
-                        self.globals.entry(name_str.clone())
+                        self.globals.entry(key)
                     {
                         e.insert(val);
                     } else {
-                        return Err(VmError::Runtime(format!(
-                            "Use of undefined variable {} in setitem expression at line {}.",
-                            name_str, lineno.value
-                        )));
+                        return Err(self.runtime_error(
+                            diagnostic::UNDEFINED_SETITEM_VARIABLE,
+                            &[&name_str, &lineno.value],
+                        ));
                     }
                 } else {
                     panic!(
@@ -578,6 +957,7 @@ impl VirtualMachine {
                         .push(value::Value::Class(self.heap.manage_class(value::Class {
                             name,
                             methods: HashMap::new(),
+                            superclass: None,
                         })));
                 } else {
                     panic!(
@@ -616,11 +996,10 @@ impl VirtualMachine {
                         self.pop_stack();
                         self.stack.push(attr);
                     } else if !self.bind_method(instance_id, class, attr_id)? {
-                        return Err(VmError::Runtime(format!(
-                            "value {} has no attribute {}.",
-                            self.format_val(&maybe_instance),
-                            self.get_str(attr_id)
-                        )));
+                        return Err(self.runtime_error(
+                            diagnostic::NO_ATTRIBUTE,
+                            &[&self.format_val(&maybe_instance), &self.get_str(attr_id)],
+                        ));
                     }
                 } else {
                     panic!(
@@ -662,11 +1041,13 @@ impl VirtualMachine {
                             (*superclass_id, *subclass_id)
                         }
                         (not_a_class, value::Value::Class(_)) => {
-                            return Err(VmError::Runtime(format!(
-                                "Superclass must be a class, found {:?} at lineno={:?}",
-                                value::type_of(not_a_class),
-                                lineno
-                            )));
+                            return Err(self.runtime_error(
+                                diagnostic::SUPERCLASS_NOT_A_CLASS,
+                                &[
+                                    &value::type_of(not_a_class).name(self.language),
+                                    &lineno.value,
+                                ],
+                            ));
                         }
                         _ => panic!("expected classes when interpreting Inherit!"),
                     };
@@ -675,6 +1056,7 @@ impl VirtualMachine {
                     let subclass = self.get_class_mut(subclass_id);
 
                     subclass.methods.extend(superclass_methods);
+                    subclass.superclass = Some(superclass_id);
                 }
                 self.pop_stack(); //subclass
             }
@@ -698,11 +1080,13 @@ impl VirtualMachine {
                 };
 
                 if !self.bind_method(instance_id, superclass, method_id)? {
-                    return Err(VmError::Runtime(format!(
-                        "superclass {} has no attribute {}.",
-                        self.format_val(&maybe_superclass),
-                        self.get_str(method_id)
-                    )));
+                    return Err(self.runtime_error(
+                        diagnostic::NO_SUPERCLASS_ATTRIBUTE,
+                        &[
+                            &self.format_val(&maybe_superclass),
+                            &self.get_str(method_id),
+                        ],
+                    ));
                 }
             }
             (firnas_bytecode::Op::SuperInvoke(method_name, arg_count), _) => {
@@ -722,6 +1106,55 @@ impl VirtualMachine {
                 self.stack
                     .push(value::Value::List(self.heap.manage_list(list_elements)));
             }
+            (firnas_bytecode::Op::BuildTuple(size), _) => {
+                let mut tuple_elements = Vec::new();
+                for _ in 0..size {
+                    tuple_elements.push(self.pop_stack())
+                }
+                tuple_elements.reverse();
+                self.stack
+                    .push(value::Value::Tuple(self.heap.manage_tuple(tuple_elements)));
+            }
+            (firnas_bytecode::Op::BuildSet(size), lineno) => {
+                let mut set_elements = BTreeMap::new();
+                for element in self.stack.split_off(self.stack.len() - size) {
+                    let key = self.hash_key_or_err(&element, lineno)?;
+                    set_elements.insert(key, element);
+                }
+                self.stack
+                    .push(value::Value::Set(self.heap.manage_set(set_elements)));
+            }
+            (firnas_bytecode::Op::BuildDict(size), lineno) => {
+                let mut entries = BTreeMap::new();
+                let stack_entries = self.stack.split_off(self.stack.len() - 2 * size);
+                for entry in stack_entries.chunks(2) {
+                    let hash_key = self.hash_key_or_err(&entry[0], lineno)?;
+                    // the last occurrence of a repeated key wins
+                    entries.insert(hash_key, (entry[0].clone(), entry[1].clone()));
+                }
+                self.stack
+                    .push(value::Value::Dict(self.heap.manage_dict(entries)));
+            }
+            (firnas_bytecode::Op::Unpack(size), lineno) => {
+                let val = self.pop_stack();
+                let elements = match &val {
+                    value::Value::Tuple(id) => self.get_tuple_elements(*id).clone(),
+                    value::Value::List(id) => self.get_list_elements(*id).clone(),
+                    _ => {
+                        return Err(self.runtime_error(
+                            diagnostic::CANNOT_UNPACK,
+                            &[&value::type_of(&val).name(self.language), &lineno.value],
+                        ))
+                    }
+                };
+                if elements.len() != size {
+                    return Err(self.runtime_error(
+                        diagnostic::UNPACK_COUNT_MISMATCH,
+                        &[&size, &elements.len(), &lineno.value],
+                    ));
+                }
+                self.stack.extend(elements);
+            }
             (firnas_bytecode::Op::Subscr, lineno) => {
                 let subscript = self.pop_stack();
                 let value_to_subscript = self.pop_stack();
@@ -735,6 +1168,25 @@ impl VirtualMachine {
                 self.setitem(lhs, subscript, rhs.clone(), lineno)?;
                 self.stack.push(rhs);
             }
+            (firnas_bytecode::Op::Slice, lineno) => {
+                let step = self.pop_stack();
+                let end = self.pop_stack();
+                let start = self.pop_stack();
+                let value_to_slice = self.pop_stack();
+                let bounds = self.slice_bounds(&start, &end, &step, lineno)?;
+                let res = self.slice(value_to_slice, bounds, lineno)?;
+                self.stack.push(res);
+            }
+            (firnas_bytecode::Op::SetSlice, lineno) => {
+                let rhs = self.pop_stack();
+                let step = self.pop_stack();
+                let end = self.pop_stack();
+                let start = self.pop_stack();
+                let lhs = self.pop_stack();
+                let bounds = self.slice_bounds(&start, &end, &step, lineno)?;
+                self.setslice(lhs, bounds, rhs.clone(), lineno)?;
+                self.stack.push(rhs);
+            }
         }
         Ok(())
     }
@@ -746,31 +1198,40 @@ impl VirtualMachine {
         rhs: value::Value,
         lineno: firnas_bytecode::Lineno,
     ) -> Result<(), VmError> {
+        if let value::Value::Tuple(_) = lhs {
+            return Err(self.runtime_error(diagnostic::TUPLE_ITEM_ASSIGNMENT, &[&lineno.value]));
+        }
+        if let value::Value::Dict(id) = lhs {
+            let key = self.hash_key_or_err(&subscript, lineno)?;
+            self.heap
+                .get_dict_entries_mut(id)
+                .insert(key, (subscript, rhs));
+            return Ok(());
+        }
         if let value::Value::List(id) = lhs {
-            if let value::Value::Number(index_float) = subscript {
+            if let Some(index) = VirtualMachine::extract_index(&subscript) {
                 let elements = self.get_list_elements_mut(id);
-                match VirtualMachine::subscript_to_inbound_index(
-                    elements.len(),
-                    index_float,
-                    lineno,
-                ) {
-                    Ok(index_int) => {
+                match VirtualMachine::subscript_to_inbound_index(elements.len(), index) {
+                    Some(index_int) => {
                         elements[index_int] = rhs;
                         Ok(())
                     }
-                    Err(err) => Err(VmError::Runtime(err)),
+                    None => {
+                        Err(self
+                            .runtime_error(diagnostic::LIST_INDEX_OUT_OF_RANGE, &[&lineno.value]))
+                    }
                 }
             } else {
-                Err(VmError::Runtime(format!(
-                    "Invalid subscript of type {:?} in subscript expression",
-                    value::type_of(&lhs)
-                )))
+                Err(self.runtime_error(
+                    diagnostic::INVALID_SUBSCRIPT,
+                    &[&value::type_of(&lhs).name(self.language)],
+                ))
             }
         } else {
-            Err(VmError::Runtime(format!(
-                "Invalid value of type {:?} in subscript expression",
-                value::type_of(&subscript)
-            )))
+            Err(self.runtime_error(
+                diagnostic::INVALID_SUBSCRIPTED_VALUE,
+                &[&value::type_of(&subscript).name(self.language)],
+            ))
         }
     }
 
@@ -780,62 +1241,248 @@ impl VirtualMachine {
         subscript: value::Value,
         lineno: firnas_bytecode::Lineno,
     ) -> Result<value::Value, VmError> {
-        if let value::Value::List(id) = value {
-            if let value::Value::Number(index_float) = subscript {
-                let elements = self.get_list_elements(id);
-                match VirtualMachine::subscript_to_inbound_index(
-                    elements.len(),
-                    index_float,
-                    lineno,
-                ) {
-                    Ok(index_int) => Ok(elements[index_int].clone()),
-                    Err(err) => Err(VmError::Runtime(err)),
+        if let value::Value::Dict(id) = value {
+            let key = self.hash_key_or_err(&subscript, lineno)?;
+            return match self.get_dict_entries(id).get(&key) {
+                Some((_, val)) => Ok(val.clone()),
+                None => Err(self.runtime_error(
+                    diagnostic::KEY_NOT_FOUND,
+                    &[&self.format_val(&subscript), &lineno.value],
+                )),
+            };
+        }
+        let elements = match value {
+            value::Value::List(id) => Some(self.get_list_elements(id)),
+            value::Value::Tuple(id) => Some(self.get_tuple_elements(id)),
+            _ => None,
+        };
+        if let Some(elements) = elements {
+            if let Some(index) = VirtualMachine::extract_index(&subscript) {
+                match VirtualMachine::subscript_to_inbound_index(elements.len(), index) {
+                    Some(index_int) => Ok(elements[index_int].clone()),
+                    None => {
+                        Err(self
+                            .runtime_error(diagnostic::LIST_INDEX_OUT_OF_RANGE, &[&lineno.value]))
+                    }
                 }
             } else {
-                Err(VmError::Runtime(format!(
-                    "Invalid subscript of type {:?} in subscript expression",
-                    value::type_of(&value)
-                )))
+                Err(self.runtime_error(
+                    diagnostic::INVALID_SUBSCRIPT,
+                    &[&value::type_of(&value).name(self.language)],
+                ))
             }
         } else {
-            Err(VmError::Runtime(format!(
-                "Invalid value of type {:?} in subscript expression",
-                value::type_of(&value)
-            )))
+            Err(self.runtime_error(
+                diagnostic::INVALID_SUBSCRIPTED_VALUE,
+                &[&value::type_of(&value).name(self.language)],
+            ))
         }
     }
 
-    fn subscript_to_inbound_index(
-        list_len: usize,
-        index_float: f64,
+    /// Strings are sliced by grapheme, so combining marks stay with their letter.
+    fn slice(
+        &mut self,
+        value: value::Value,
+        bounds: SliceBounds,
         lineno: firnas_bytecode::Lineno,
-    ) -> Result<usize, String> {
-        let index_int = index_float as i64;
+    ) -> Result<value::Value, VmError> {
+        match value {
+            value::Value::List(id) => {
+                let elements = self.get_list_elements(id);
+                let res = VirtualMachine::slice_indices(elements.len(), bounds)
+                    .into_iter()
+                    .map(|index| elements[index].clone())
+                    .collect();
+                Ok(value::Value::List(self.heap.manage_list(res)))
+            }
+            value::Value::Tuple(id) => {
+                let elements = self.get_tuple_elements(id);
+                let res = VirtualMachine::slice_indices(elements.len(), bounds)
+                    .into_iter()
+                    .map(|index| elements[index].clone())
+                    .collect();
+                Ok(value::Value::Tuple(self.heap.manage_tuple(res)))
+            }
+            value::Value::String(id) => {
+                let graphemes = self.get_str(id).graphemes(true).collect::<Vec<&str>>();
+                let res = VirtualMachine::slice_indices(graphemes.len(), bounds)
+                    .into_iter()
+                    .map(|index| graphemes[index])
+                    .collect::<String>();
+                Ok(value::Value::String(self.heap.manage_str(res)))
+            }
+            _ => Err(self.runtime_error(
+                diagnostic::INVALID_SLICED_VALUE,
+                &[&value::type_of(&value).name(self.language), &lineno.value],
+            )),
+        }
+    }
+
+    fn setslice(
+        &mut self,
+        lhs: value::Value,
+        bounds: SliceBounds,
+        rhs: value::Value,
+        lineno: firnas_bytecode::Lineno,
+    ) -> Result<(), VmError> {
+        let id = match lhs {
+            value::Value::List(id) => id,
+            _ => {
+                return Err(self.runtime_error(
+                    diagnostic::INVALID_SLICE_ASSIGNMENT_TARGET,
+                    &[&value::type_of(&lhs).name(self.language), &lineno.value],
+                ))
+            }
+        };
+        let replacement = match rhs {
+            value::Value::List(id) => self.get_list_elements(id).clone(),
+            value::Value::Tuple(id) => self.get_tuple_elements(id).clone(),
+            _ => {
+                return Err(self.runtime_error(
+                    diagnostic::INVALID_SLICE_ASSIGNMENT_VALUE,
+                    &[&value::type_of(&rhs).name(self.language), &lineno.value],
+                ))
+            }
+        };
+
+        let elements = self.get_list_elements_mut(id);
+        if bounds.step == 1 {
+            // a contiguous slice may be replaced by any number of elements
+            let indices = VirtualMachine::slice_indices(elements.len(), bounds);
+            let start = match (indices.first(), bounds.start) {
+                (Some(first), _) => *first,
+                (None, None) => 0,
+                (None, Some(start)) => {
+                    VirtualMachine::clamp_slice_bound(start, elements.len() as i64, 0) as usize
+                }
+            };
+            elements.splice(start..start + indices.len(), replacement);
+            return Ok(());
+        }
+
+        let indices = VirtualMachine::slice_indices(elements.len(), bounds);
+        if indices.len() != replacement.len() {
+            return Err(self.runtime_error(
+                diagnostic::EXTENDED_SLICE_SIZE_MISMATCH,
+                &[&replacement.len(), &indices.len(), &lineno.value],
+            ));
+        }
+        for (index, element) in indices.into_iter().zip(replacement) {
+            elements[index] = element;
+        }
+        Ok(())
+    }
+
+    fn slice_bounds(
+        &self,
+        start: &value::Value,
+        end: &value::Value,
+        step: &value::Value,
+        lineno: firnas_bytecode::Lineno,
+    ) -> Result<SliceBounds, VmError> {
+        let extract_bound = |bound: &value::Value| match bound {
+            value::Value::Nil => Ok(None),
+            _ => match VirtualMachine::extract_index(bound) {
+                Some(index) => Ok(Some(index)),
+                None => Err(self.runtime_error(
+                    diagnostic::INVALID_SLICE_INDEX,
+                    &[&value::type_of(bound).name(self.language), &lineno.value],
+                )),
+            },
+        };
+        let bounds = SliceBounds {
+            start: extract_bound(start)?,
+            end: extract_bound(end)?,
+            step: extract_bound(step)?.unwrap_or(1),
+        };
+        if bounds.step == 0 {
+            return Err(self.runtime_error(diagnostic::ZERO_SLICE_STEP, &[&lineno.value]));
+        }
+        Ok(bounds)
+    }
+
+    /*
+    The indices selected by a slice of a sequence of `len` elements. Negative bounds count
+    from the end and out of range bounds are clamped, so slicing never fails.
+     */
+    fn slice_indices(len: usize, bounds: SliceBounds) -> Vec<usize> {
+        let len = len as i64;
+        let mut indices = Vec::new();
+        let (mut index, end) = if bounds.step > 0 {
+            (
+                bounds
+                    .start
+                    .map_or(0, |start| VirtualMachine::clamp_slice_bound(start, len, 0)),
+                bounds
+                    .end
+                    .map_or(len, |end| VirtualMachine::clamp_slice_bound(end, len, 0)),
+            )
+        } else {
+            (
+                bounds.start.map_or(len - 1, |start| {
+                    VirtualMachine::clamp_slice_bound(start, len, -1)
+                }),
+                bounds
+                    .end
+                    .map_or(-1, |end| VirtualMachine::clamp_slice_bound(end, len, -1)),
+            )
+        };
+        while (bounds.step > 0 && index < end) || (bounds.step < 0 && index > end) {
+            indices.push(index as usize);
+            match index.checked_add(bounds.step) {
+                Some(next) => index = next,
+                None => break,
+            }
+        }
+        indices
+    }
+
+    // `low` is 0 for forward slices and -1 (before the first element) for backward ones
+    fn clamp_slice_bound(bound: i64, len: i64, low: i64) -> i64 {
+        let bound = if bound < 0 {
+            bound.saturating_add(len)
+        } else {
+            bound
+        };
+        bound.clamp(low, len + low)
+    }
+
+    fn extract_index(subscript: &value::Value) -> Option<i64> {
+        match subscript {
+            value::Value::Integer(index) => Some(*index),
+            value::Value::Number(index_float) => Some(*index_float as i64),
+            _ => None,
+        }
+    }
+
+    fn subscript_to_inbound_index(list_len: usize, index_int: i64) -> Option<usize> {
         if 0 <= index_int && index_int < list_len as i64 {
-            return Ok(index_int as usize);
+            return Some(index_int as usize);
         }
-        if index_int < 0 && -index_int <= list_len as i64 {
-            return Ok((list_len as i64 + index_int) as usize);
+        if index_int < 0 && index_int.unsigned_abs() <= list_len as u64 {
+            return Some((list_len as i64 + index_int) as usize);
         }
-        Err(format!(
-            "List subscript index out of range at {}",
-            lineno.value
-        ))
+        None
+    }
+
+    fn runtime_error(&self, message: Message, args: &[&dyn fmt::Display]) -> VmError {
+        VmError::Runtime(RuntimeError {
+            code: message.code,
+            what: message.render(self.language, args),
+            language: self.language,
+        })
     }
 
     fn invoke(&mut self, method_name: &str, arg_count: u8) -> Result<(), VmError> {
         let receiver_id = match self.peek_by(arg_count.into()) {
             value::Value::Instance(id) => *id,
             _ => {
-                return Err(VmError::Runtime("Only instances have methods.".to_string()));
+                return Err(self.runtime_error(diagnostic::METHOD_ON_NON_INSTANCE, &[]));
             }
         };
 
-        if let Some(field) = self
-            .get_instance(receiver_id)
-            .fields
-            .get(&String::from(method_name))
-            .cloned()
+        if let Some(field) =
+            stdlib::lookup(&self.get_instance(receiver_id).fields, method_name).cloned()
         {
             return self.call_value(field, arg_count);
         }
@@ -869,12 +1516,7 @@ impl VirtualMachine {
             .get(&String::from(method_name))
         {
             Some(method_id) => *method_id,
-            None => {
-                return Err(VmError::Runtime(format!(
-                    "Undefined property {}.",
-                    method_name
-                )))
-            }
+            None => return Err(self.runtime_error(diagnostic::UNDEFINED_PROPERTY, &[&method_name])),
         };
 
         self.call_value(value::Value::Function(method_id), arg_count)
@@ -929,11 +1571,8 @@ impl VirtualMachine {
                         "تهيئة"
                     };
 
-                    let maybe_method_id = self
-                        .get_class(class_id)
-                        .methods
-                        .get(&init_name.to_string())
-                        .copied();
+                    let methods = &self.get_class(class_id).methods;
+                    let maybe_method_id = stdlib::lookup(methods, init_name).copied();
 
                     if let Some(method_id) = maybe_method_id {
                         return self.prepare_call(method_id, arg_count);
@@ -941,10 +1580,7 @@ impl VirtualMachine {
                 }
 
                 if arg_count > 0 {
-                    return Err(VmError::Runtime(format!(
-                        "Call to class ctor expected 0 arguments, got {}.",
-                        arg_count,
-                    )));
+                    return Err(self.runtime_error(diagnostic::CLASS_CTOR_ARITY, &[&arg_count]));
                 }
 
                 self.create_instance(class_id);
@@ -954,44 +1590,80 @@ impl VirtualMachine {
                 self.call_bound_method(method_id, arg_count)?;
                 Ok(())
             }
-            _ => Err(VmError::Runtime(format!(
-                "attempted to call non-callable value of type {:?}.",
-                value::type_of(&val_to_call)
-            ))),
+            _ => Err(self.runtime_error(
+                diagnostic::NOT_CALLABLE,
+                &[&value::type_of(&val_to_call).name(self.language)],
+            )),
         }
     }
 
+    /*
+    Calls a firnas value from inside a native function and returns its result. For closures
+    `call_value` only sets up a call frame, so we step the interpreter until that frame returns.
+    Calls can nest, e.g. a comparator passed to `sort` may itself call `map`.
+    Unfortunately, this doesn't play well with our current debugger implementation, which
+    manually calls `step()`.
+     */
+    pub fn call_from_native(
+        &mut self,
+        callable: &value::Value,
+        args: &[value::Value],
+    ) -> Result<value::Value, String> {
+        let arg_count = u8::try_from(args.len())
+            .map_err(|_| format!("Too many arguments in call: {}", args.len()))?;
+        self.stack.push(callable.clone());
+        self.stack.extend(args.iter().cloned());
+
+        let frame_idx = self.frames.len();
+        let to_string = |VmError::Runtime(err)| err.what;
+        self.call_value(callable.clone(), arg_count)
+            .map_err(to_string)?;
+        while self.frames.len() > frame_idx {
+            self.step().map_err(to_string)?;
+        }
+
+        Ok(self.pop_stack())
+    }
+
     fn call_native_func(
         &mut self,
         native_func: value::NativeFunction,
         arg_count: u8,
     ) -> Result<(), VmError> {
-        if arg_count != native_func.arity {
-            return Err(VmError::Runtime(format!(
-                "Native function {} expected {} arguments but found {}.",
-                native_func.name, native_func.arity, arg_count
-            )));
+        let max_arity = native_func.arity + native_func.optional;
+        if arg_count < native_func.arity || arg_count > max_arity {
+            let expected = if native_func.optional == 0 {
+                native_func.arity.to_string()
+            } else {
+                format!("{}-{}", native_func.arity, max_arity)
+            };
+            return Err(self.runtime_error(
+                diagnostic::NATIVE_ARITY,
+                &[&native_func.name, &expected, &arg_count],
+            ));
         }
-
-        let mut args = Vec::new();
-        for _ in 0..arg_count {
-            args.push(self.pop_stack()) // pop args
+        for _ in arg_count..max_arity {
+            self.stack.push(value::Value::Nil);
         }
-        args.reverse();
-        let args = args;
-        self.pop_stack(); // native function value
+        let arg_count = max_arity;
+
+        // the args stay on the stack during the call so that they are still rooted if the
+        // native calls back into firnas code and a collection runs
+        let args_start = self.stack.len() - usize::from(arg_count);
+        let args = self.stack[args_start..].to_vec();
 
         let res = (native_func.func)(self, &args);
 
+        self.stack.truncate(args_start - 1); // args and the native function value
+
         match res {
             Ok(result) => {
                 self.stack.push(result);
                 Ok(())
             }
-            Err(err) => Err(VmError::Runtime(format!(
-                "When calling {}: {}.",
-                native_func.name, err
-            ))),
+            Err(err) => {
+                Err(self.runtime_error(diagnostic::NATIVE_CALL_FAILED, &[&native_func.name, &err]))
+            }
         }
     }
 
@@ -1021,10 +1693,7 @@ impl VirtualMachine {
         let closure = self.get_closure(closure_handle).clone();
         let func = &closure.function;
         if arg_count != func.arity {
-            return Err(VmError::Runtime(format!(
-                "Expected {} arguments but found {}.",
-                func.arity, arg_count
-            )));
+            return Err(self.runtime_error(diagnostic::ARITY, &[&func.arity, &arg_count]));
         }
 
         self.frames.push(CallFrame::default());
@@ -1040,11 +1709,14 @@ impl VirtualMachine {
         }
     }
 
-    fn is_falsey(&self, val: &value::Value) -> bool {
+    pub(crate) fn is_falsey(&self, val: &value::Value) -> bool {
         match val {
             value::Value::Nil => true,
             value::Value::Bool(b) => !*b,
             value::Value::Number(f) => *f == 0.0,
+            value::Value::Integer(i) => *i == 0,
+            value::Value::BigInt(_) => false,
+            value::Value::Rational(id) => self.heap.get_rational(*id).is_zero(),
             value::Value::Function(_) => false,
             value::Value::NativeFunction(_) => false,
             value::Value::Class(_) => false,
@@ -1052,55 +1724,235 @@ impl VirtualMachine {
             value::Value::BoundMethod(_) => false,
             value::Value::String(id) => self.get_str(*id).is_empty(),
             value::Value::List(id) => self.get_list_elements(*id).is_empty(),
+            value::Value::Tuple(id) => self.get_tuple_elements(*id).is_empty(),
+            value::Value::Set(id) => self.get_set_elements(*id).is_empty(),
+            value::Value::Dict(id) => self.get_dict_entries(*id).is_empty(),
+            value::Value::Regex(_) => false,
         }
     }
 
-    fn values_equal(&self, val1: &value::Value, val2: &value::Value) -> bool {
+    pub(crate) fn values_equal(&self, val1: &value::Value, val2: &value::Value) -> bool {
+        self.values_equal_nested(val1, val2, &mut Vec::new())
+    }
+
+    /*
+    `comparing` holds the pairs of dictionaries we are inside of. A pair met again while comparing
+    it is taken as equal, so that a dictionary that contains itself compares without recursing
+    forever: whether it differs is decided by the rest of its entries.
+     */
+    fn values_equal_nested(
+        &self,
+        val1: &value::Value,
+        val2: &value::Value,
+        comparing: &mut Vec<(gc::HeapId, gc::HeapId)>,
+    ) -> bool {
         match (val1, val2) {
-            (value::Value::Number(n1), value::Value::Number(n2)) => (n1 - n2).abs() < f64::EPSILON,
+            _ if self.is_number(val1) && self.is_number(val2) => {
+                let n1 = numeric::Numeric::from_value(&self.heap, val1).unwrap();
+                let n2 = numeric::Numeric::from_value(&self.heap, val2).unwrap();
+                n1.equals(&n2)
+            }
             (value::Value::Bool(b1), value::Value::Bool(b2)) => b1 == b2,
             (value::Value::String(s1), value::Value::String(s2)) => {
                 self.get_str(*s1) == self.get_str(*s2)
             }
             (value::Value::Nil, value::Value::Nil) => true,
+            (value::Value::Tuple(t1), value::Value::Tuple(t2)) => {
+                let elements1 = self.get_tuple_elements(*t1);
+                let elements2 = self.get_tuple_elements(*t2);
+                elements1.len() == elements2.len()
+                    && elements1
+                        .iter()
+                        .zip(elements2.iter())
+                        .all(|(e1, e2)| self.values_equal_nested(e1, e2, comparing))
+            }
+            (value::Value::Set(s1), value::Value::Set(s2)) => {
+                let elements1 = self.get_set_elements(*s1);
+                let elements2 = self.get_set_elements(*s2);
+                elements1.len() == elements2.len()
+                    && elements1.keys().all(|key| elements2.contains_key(key))
+            }
+            (value::Value::Dict(d1), value::Value::Dict(d2)) => {
+                if d1 == d2 || comparing.contains(&(*d1, *d2)) {
+                    return true;
+                }
+                let entries1 = self.get_dict_entries(*d1);
+                let entries2 = self.get_dict_entries(*d2);
+                comparing.push((*d1, *d2));
+                let equal = entries1.len() == entries2.len()
+                    && entries1
+                        .iter()
+                        .all(|(key, (_, val1))| match entries2.get(key) {
+                            Some((_, val2)) => self.values_equal_nested(val1, val2, comparing),
+                            None => false,
+                        });
+                comparing.pop();
+                equal
+            }
             (_, _) => false,
         }
     }
 
-    fn numeric_binop(
+    /// `None` for values that can't be used as keys of a hashed collection.
+    pub fn hash_key(&self, val: &value::Value) -> Option<value::HashKey> {
+        match val {
+            value::Value::Nil => Some(value::HashKey::Nil),
+            value::Value::Bool(b) => Some(value::HashKey::Bool(*b)),
+            value::Value::String(id) => Some(value::HashKey::String(self.get_str(*id).clone())),
+            value::Value::Tuple(id) => self
+                .get_tuple_elements(*id)
+                .iter()
+                .map(|element| self.hash_key(element))
+                .collect::<Option<Vec<_>>>()
+                .map(value::HashKey::Tuple),
+            _ if self.is_number(val) => {
+                let num = numeric::Numeric::from_value(&self.heap, val).unwrap();
+                match num.to_rational() {
+                    Some(rational) => Some(value::HashKey::Number(rational)),
+                    // NaN and the infinities
+                    None => Some(value::HashKey::Float(num.to_f64().to_bits())),
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn hash_key_or_err(
+        &self,
+        val: &value::Value,
+        lineno: firnas_bytecode::Lineno,
+    ) -> Result<value::HashKey, VmError> {
+        self.hash_key(val).ok_or_else(|| {
+            self.runtime_error(
+                diagnostic::UNHASHABLE,
+                &[&value::type_of(val).name(self.language), &lineno.value],
+            )
+        })
+    }
+
+    fn set_binop(
         &mut self,
-        binop: Binop,
+        binop: SetBinop,
         lineno: firnas_bytecode::Lineno,
     ) -> Result<(), VmError> {
         let val1 = self.peek_by(0).clone();
         let val2 = self.peek_by(1).clone();
 
         match (&val1, &val2) {
-            (value::Value::Number(n1), value::Value::Number(n2)) => {
+            (value::Value::Set(id1), value::Value::Set(id2)) => {
+                let right = self.get_set_elements(*id1);
+                let left = self.get_set_elements(*id2);
+                let res: BTreeMap<value::HashKey, value::Value> = match binop {
+                    SetBinop::Union => {
+                        let mut res = left.clone();
+                        for (key, element) in right {
+                            res.entry(key.clone()).or_insert_with(|| element.clone());
+                        }
+                        res
+                    }
+                    SetBinop::Intersection => left
+                        .iter()
+                        .filter(|(key, _)| right.contains_key(key))
+                        .map(|(key, element)| (key.clone(), element.clone()))
+                        .collect(),
+                    SetBinop::Difference => left
+                        .iter()
+                        .filter(|(key, _)| !right.contains_key(key))
+                        .map(|(key, element)| (key.clone(), element.clone()))
+                        .collect(),
+                };
                 self.pop_stack();
                 self.pop_stack();
                 self.stack
-                    .push(value::Value::Number(VirtualMachine::apply_numeric_binop(
-                        *n2, *n1, binop, // note the order!
-                    )));
+                    .push(value::Value::Set(self.heap.manage_set(res)));
                 Ok(())
             }
-            _ => Err(VmError::Runtime(format!(
-                "Expected numbers in {:?} expression. Found {:?} and {:?} (line={})",
-                binop,
-                value::type_of(&val1),
-                value::type_of(&val2),
-                lineno.value
-            ))),
+            _ => Err(self.runtime_error(
+                diagnostic::INVALID_SET_OPERANDS,
+                &[
+                    &binop.name(self.language),
+                    &value::type_of(&val2).name(self.language),
+                    &value::type_of(&val1).name(self.language),
+                    &lineno.value,
+                ],
+            )),
         }
     }
 
-    fn apply_numeric_binop(left: f64, right: f64, binop: Binop) -> f64 {
-        match binop {
-            Binop::Add => left + right,
-            Binop::Sub => left - right,
-            Binop::Mul => left * right,
-            Binop::Div => left / right,
+    fn is_number(&self, val: &value::Value) -> bool {
+        matches!(
+            val,
+            value::Value::Number(_)
+                | value::Value::Integer(_)
+                | value::Value::BigInt(_)
+                | value::Value::Rational(_)
+        )
+    }
+
+    /*
+    Outer `None` when the values aren't both numbers or both tuples, inner `None` when they are
+    unordered (NaN). Tuples compare lexicographically.
+     */
+    #[allow(clippy::option_option)]
+    pub(crate) fn compare_values(
+        &self,
+        left: &value::Value,
+        right: &value::Value,
+    ) -> Option<Option<std::cmp::Ordering>> {
+        if let (value::Value::Tuple(id1), value::Value::Tuple(id2)) = (left, right) {
+            let elements1 = self.get_tuple_elements(*id1);
+            let elements2 = self.get_tuple_elements(*id2);
+            for (e1, e2) in elements1.iter().zip(elements2.iter()) {
+                match self.compare_values(e1, e2)? {
+                    Some(std::cmp::Ordering::Equal) => {}
+                    ordering => return Some(ordering),
+                }
+            }
+            return Some(Some(elements1.len().cmp(&elements2.len())));
+        }
+        if let (value::Value::String(id1), value::Value::String(id2)) = (left, right) {
+            return Some(Some(
+                self.collation
+                    .compare(self.get_str(*id1), self.get_str(*id2)),
+            ));
+        }
+        let left = numeric::Numeric::from_value(&self.heap, left)?;
+        let right = numeric::Numeric::from_value(&self.heap, right)?;
+        Some(left.compare(&right))
+    }
+
+    fn numeric_binop(
+        &mut self,
+        binop: numeric::Binop,
+        lineno: firnas_bytecode::Lineno,
+    ) -> Result<(), VmError> {
+        let val1 = self.peek_by(0).clone();
+        let val2 = self.peek_by(1).clone();
+
+        match (
+            numeric::Numeric::from_value(&self.heap, &val1),
+            numeric::Numeric::from_value(&self.heap, &val2),
+        ) {
+            (Some(n1), Some(n2)) => {
+                // note the order!
+                let res = n2.apply(n1, binop).map_err(|err| {
+                    self.runtime_error(diagnostic::NUMERIC_ERROR, &[&err, &lineno.value])
+                })?;
+                self.pop_stack();
+                self.pop_stack();
+                let res = res.into_value(&mut self.heap);
+                self.stack.push(res);
+                Ok(())
+            }
+            _ => Err(self.runtime_error(
+                diagnostic::INVALID_NUMERIC_OPERANDS,
+                &[
+                    &binop.name(self.language),
+                    &value::type_of(&val1).name(self.language),
+                    &value::type_of(&val2).name(self.language),
+                    &lineno.value,
+                ],
+            )),
         }
     }
 
@@ -1112,19 +1964,31 @@ impl VirtualMachine {
     ) -> Result<(), VmError> {
         let attr_name = self.get_str(attr_id).clone();
         match maybe_instance {
+            value::Value::Instance(instance_id) if self.is_read_only(instance_id) => Err(self
+                .runtime_error(
+                    diagnostic::READ_ONLY_ATTRIBUTE,
+                    &[&attr_name, &self.format_val(&maybe_instance)],
+                )),
             value::Value::Instance(instance_id) => {
                 let instance = self.heap.get_instance_mut(instance_id);
                 instance.fields.insert(attr_name, val);
                 Ok(())
             }
-            _ => Err(VmError::Runtime(format!(
-                "can't set attribute on value of type {:?}. Need class instance. val = {:?}",
-                value::type_of(&maybe_instance),
-                self.format_val(&maybe_instance)
-            ))),
+            _ => Err(self.runtime_error(
+                diagnostic::SET_ATTRIBUTE_ON_NON_INSTANCE,
+                &[
+                    &value::type_of(&maybe_instance).name(self.language),
+                    &self.format_val(&maybe_instance),
+                ],
+            )),
         }
     }
 
+    /// Whether the instance's attributes can't be set, as for dates.
+    pub(crate) fn is_read_only(&self, instance_id: gc::HeapId) -> bool {
+        self.heap.get_instance(instance_id).class_id == self.date_class
+    }
+
     fn getattr(
         &self,
         maybe_instance: value::Value,
@@ -1134,16 +1998,18 @@ impl VirtualMachine {
         match maybe_instance {
             value::Value::Instance(instance_id) => {
                 let instance = self.heap.get_instance(instance_id);
-                match instance.fields.get(&attr_name) {
+                match stdlib::lookup(&instance.fields, &attr_name) {
                     Some(val) => Ok(Some(val.clone())),
                     None => Ok(None),
                 }
             }
-            _ => Err(VmError::Runtime(format!(
-                "can't get attribute {}  on value of type {:?}. Need class instance.",
-                attr_name,
-                value::type_of(&maybe_instance)
-            ))),
+            _ => Err(self.runtime_error(
+                diagnostic::GET_ATTRIBUTE_ON_NON_INSTANCE,
+                &[
+                    &attr_name,
+                    &value::type_of(&maybe_instance).name(self.language),
+                ],
+            )),
         }
     }
 
@@ -1200,6 +2066,14 @@ impl VirtualMachine {
         let constant = self.frame().read_constant(idx);
         match constant {
             firnas_bytecode::Constant::Number(num) => value::Value::Number(num),
+            firnas_bytecode::Constant::Integer(num) => value::Value::Integer(num),
+            firnas_bytecode::Constant::BigInteger(digits) => {
+                value::Value::BigInt(self.heap.manage_bigint(digits.parse().unwrap()))
+            }
+            firnas_bytecode::Constant::Rational(text) => value::Value::Rational(
+                self.heap
+                    .manage_rational(numeric::parse_rational(&text).unwrap()),
+            ),
             firnas_bytecode::Constant::String(s) => value::Value::String(self.heap.manage_str(s)),
             firnas_bytecode::Constant::Function(f) => {
                 value::Value::Function(self.heap.manage_closure(value::Closure {
@@ -1210,13 +2084,6 @@ impl VirtualMachine {
         }
     }
 
-    fn extract_number(val: &value::Value) -> Option<f64> {
-        match val {
-            value::Value::Number(f) => Some(*f),
-            _ => None,
-        }
-    }
-
     fn extract_bool(val: &value::Value) -> Option<bool> {
         match val {
             value::Value::Bool(b) => Some(*b),
@@ -1252,6 +2119,21 @@ impl VirtualMachine {
         self.heap.get_list_elements_mut(list_handle)
     }
 
+    fn get_tuple_elements(&self, tuple_handle: gc::HeapId) -> &Vec<value::Value> {
+        self.heap.get_tuple_elements(tuple_handle)
+    }
+
+    fn get_set_elements(&self, set_handle: gc::HeapId) -> &BTreeMap<value::HashKey, value::Value> {
+        self.heap.get_set_elements(set_handle)
+    }
+
+    fn get_dict_entries(
+        &self,
+        dict_handle: gc::HeapId,
+    ) -> &BTreeMap<value::HashKey, (value::Value, value::Value)> {
+        self.heap.get_dict_entries(dict_handle)
+    }
+
     fn get_instance(&self, instance_handle: gc::HeapId) -> &value::Instance {
         self.heap.get_instance(instance_handle)
     }
@@ -1300,10 +2182,13 @@ impl VirtualMachine {
             .flat_map(gc::Heap::extract_id)
             .collect();
 
+        let date_class = self.date_class;
+
         for val in stack_vals_to_mark
             .iter()
             .chain(frame_closure_children.iter())
             .chain(globals_to_mark.iter())
+            .chain(std::iter::once(&date_class))
         {
             self.mark_value(*val);
         }
@@ -273,7 +273,7 @@ fn test_inheriting_non_class() {
 دع ليس_بصنف = ""؛
 صنف كلا < ليس_بصنف { }
 "#,
-        &|err: &str| assert!(err.starts_with("Superclass must be a class, found String at lineno=")),
+        &|err: &str| assert!(err.starts_with("يجب أن يكون الصنف الأساس صنفا، وجد نص في السطر")),
     )
 }
 
@@ -0,0 +1,47 @@
+use crate::common::check_error_default;
+use crate::common::check_output_default;
+use crate::common::check_output_lists;
+
+#[test]
+fn test_compare_strings() {
+    check_output_default(
+        r#"
+اطبع_سطر("أحمد" < "اسم")؛
+اطبع_سطر("إبراهيم" < "أحمد")؛
+اطبع_سطر("كَبير" < "كتب")؛
+اطبع_سطر("پدر" < "تاج")؛
+"#,
+        &vec_of_strings!["صح", "صح", "صح", "صح"],
+    );
+}
+
+#[test]
+fn test_sort_strings() {
+    check_output_lists(
+        r#"
+دع ق = ["باب"، "اسم"، "أحمد"، "آدم"، "إبراهيم"]؛
+رتب(ق)؛
+اطبع_سطر(ق)؛
+استخدم_ترتيب("رموز")؛
+رتب(ق)؛
+اطبع_سطر(ق)؛
+"#,
+        &vec_of_strings![
+            "[إبراهيم، أحمد، آدم، اسم، باب]",
+            "[آدم، أحمد، إبراهيم، اسم، باب]"
+        ],
+    );
+}
+
+#[test]
+fn test_unknown_collation() {
+    check_error_default("استخدم_ترتيب(\"صيني\")؛", &|err: &str| {
+        assert!(
+            err.starts_with(
+                "عند استدعاء استخدم_ترتيب: Unknown collation 'صيني', expected 'عربي' أو 'رموز'"
+            ),
+            "{}",
+            err
+        )
+    });
+}
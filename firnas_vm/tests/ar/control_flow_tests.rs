@@ -172,7 +172,7 @@ fn test_for() {
         if n <= 1 {
             return 1;
         }
-        return n * fact(n - 1);
+        n * fact(n - 1)
     }
 
     check_output_default(
@@ -0,0 +1,56 @@
+use crate::common::check_error_default;
+use crate::common::check_output_default;
+
+#[test]
+fn test_hijri_with_arabic_names() {
+    check_output_default(
+        r#"
+دع ت = تاريخ.أنشئ(٢٠٢٦، ٣، ٤)؛
+اطبع_سطر(تاريخ.نسق_هجري(ت، "d MMMM yyyy"))؛
+اطبع_سطر(تاريخ.نسق(ت، "EEEE d MMMM yyyy"))؛
+اطبع_سطر(ت.سنة)؛
+"#,
+        &vec_of_strings!["١٥ رمضان ١٤٤٧", "الأربعاء ٤ مارس ٢٠٢٦", "٢٠٢٦"],
+    );
+}
+
+#[test]
+fn test_parse_arabic_digits() {
+    check_output_default(
+        r#"
+دع ت = تاريخ.حلل("٢٠٢٦-٠٣-٠٥T١٠:٢٠+٠٣:٠٠")؛
+اطبع_سطر(تاريخ.نسق(ت، "yyyy-MM-dd HH:mm Z"))؛
+"#,
+        &vec_of_strings!["٢٠٢٦-٠٣-٠٥ ١٠:٢٠ +٠٣:٠٠"],
+    );
+}
+
+#[test]
+fn test_date_fields_are_read_only() {
+    check_error_default(
+        "دع ت = تاريخ.أنشئ(٢٠٢٤، ٢، ٢٩)؛ ت.سنة = ١٩٩٩؛",
+        &|err: &str| {
+            assert_eq!(
+                err,
+                "لا يمكن تعيين الخاصية سنه للقيمة <تاريخ_وقت instance>، خصائصها للقراءة فقط."
+            )
+        },
+    );
+}
+
+#[test]
+fn test_native_errors_in_arabic() {
+    check_error_default(
+        "تاريخ.أنشئ(\"٢٠٢٦\"، ٣، ٤)؛",
+        &|err: &str| {
+            assert_eq!(
+                err,
+                "عند استدعاء تاريخ.أنشئ: استدعاء غير صالح: متوقع رقم، وجد نص."
+            )
+        },
+    );
+    check_error_default(
+        "تاريخ.أنشئ(٣٠٠٠٠٠٠، ٣، ٤)؛",
+        &|err: &str| assert_eq!(err, "عند استدعاء تاريخ.أنشئ: التاريخ خارج المدى."),
+    );
+}
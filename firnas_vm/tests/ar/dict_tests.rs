@@ -0,0 +1,17 @@
+use crate::common::check_output_lists;
+
+#[test]
+fn test_dict() {
+    check_output_lists(
+        r#"
+دع ق = {"أ": ١، "ب": ٢}؛
+ق["ج"] = ٣؛
+اطبع_سطر(ق["ج"])؛
+اطبع_سطر(يحتوي(ق، "أ"))؛
+اطبع_سطر(ادمج(مفاتيح(ق)، "،"))؛
+اطبع_سطر(قيم(ق)[٠])؛
+اطبع_سطر({:})؛
+"#,
+        &vec_of_strings!["٣", "صح", "أ،ب،ج", "١", "{:}"],
+    );
+}
@@ -0,0 +1,50 @@
+use crate::common::check_error_default;
+use crate::common::check_output_default;
+
+#[test]
+fn test_literals_in_every_digit_set() {
+    check_output_default(
+        r#"
+اطبع_سطر(١٢ + ۱۲ + 12)؛
+اطبع_سطر(۲٫۵ * 2)؛
+اطبع_سطر(١٬٠٠٠٬٠٠٠ + ١)؛
+"#,
+        &vec_of_strings!["٣٦", "٥", "١٠٠٠٠٠١"],
+    );
+}
+
+#[test]
+fn test_parse_every_digit_set() {
+    check_output_default(
+        r#"
+اطبع_سطر(الى_رقم("۴۲") + ١)؛
+اطبع_سطر(الى_رقم("١٬٢٣٤٫٥"))؛
+"#,
+        &vec_of_strings!["٤٣", "١٢٣٤٫٥"],
+    );
+}
+
+#[test]
+fn test_use_digits() {
+    check_output_default(
+        r#"
+استخدم_ارقام("فارسية")؛
+اطبع_سطر(١٢٫٥)؛
+اطبع_سطر(-٣)؛
+استخدم_ارقام("غربية")؛
+اطبع_سطر(١٢٫٥)؛
+اطبع_سطر(نسبي(١، ٣))؛
+استخدم_ارقام("عربية")؛
+اطبع_سطر(١٢٫٥)؛
+"#,
+        &vec_of_strings!["۱۲٫۵", "-۳", "12.5", "1/3", "١٢٫٥"],
+    );
+}
+
+#[test]
+fn test_use_unknown_digits() {
+    check_error_default(
+        "استخدم_ارقام(\"رومانية\")؛",
+        &|err: &str| assert!(err.contains("Unknown digits 'رومانية'"), "{}", err),
+    );
+}
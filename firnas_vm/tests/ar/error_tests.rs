@@ -0,0 +1,52 @@
+use crate::common::check_error_default;
+use firnas_compiler::compiler::Compiler;
+use firnas_compiler::diagnostic::Language;
+use firnas_vm::virtual_machine::VirtualMachine;
+use firnas_vm::virtual_machine::VmError;
+
+fn runtime_error(vm: &mut VirtualMachine, code: &str) -> VmError {
+    let func = Compiler::compile(String::from(code), Default::default()).unwrap();
+    vm.interpret(func).unwrap_err()
+}
+
+#[test]
+fn test_undefined_variable() {
+    check_error_default("اطبع_سطر(س)؛", &|err: &str| {
+        assert_eq!(err, "متغير غير معرف 'س' في السطر 1.")
+    });
+}
+
+#[test]
+fn test_type_names() {
+    check_error_default("اطبع_سطر(-\"نص\")؛", &|err: &str| {
+        assert_eq!(err, "معامل غير صالح للسالب. متوقع رقم، وجد نص في السطر 1")
+    });
+    check_error_default("اطبع_سطر(١ < \"نص\")؛", &|err: &str| {
+        assert!(err.ends_with("وجد نص وعدد صحيح في السطر 1"), "{}", err)
+    });
+}
+
+#[test]
+fn test_error_codes_and_prefix() {
+    let mut vm = VirtualMachine::default();
+    let err = runtime_error(&mut vm, "اطبع_سطر(س)؛");
+    let VmError::Runtime(info) = &err;
+    assert_eq!(info.code, "R006");
+    assert_eq!(
+        err.to_string(),
+        "خطأ وقت التشغيل: متغير غير معرف 'س' في السطر 1."
+    );
+}
+
+#[test]
+fn test_errors_in_english() {
+    let mut vm = VirtualMachine::default();
+    vm.language = Language::English;
+    let err = runtime_error(&mut vm, "اطبع_سطر(-\"نص\")؛");
+    let VmError::Runtime(info) = &err;
+    assert_eq!(info.code, "R001");
+    assert_eq!(
+        err.to_string(),
+        "Firnas runtime error: invalid operand to unary op negate. Expected number, found String at line 1"
+    );
+}
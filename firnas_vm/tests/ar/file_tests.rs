@@ -0,0 +1,27 @@
+use crate::common::check_output_default;
+
+#[test]
+fn test_write_and_read_file() {
+    let dir = std::env::temp_dir().join(format!("firnas_ar_file_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("ملاحظات.txt");
+
+    check_output_default(
+        &format!(
+            r#"
+دع مسار = "{}"؛
+اكتب_ملف(مسار، "سلام")؛
+ألحق_بملف(مسار، " عليكم")؛
+اطبع_سطر(اقرأ_ملف(مسار))؛
+اطبع_سطر(ادمج(محتويات_مجلد("{}")، "،"))؛
+احذف_ملف(مسار)؛
+اطبع_سطر(موجود(مسار))؛
+"#,
+            path.display(),
+            dir.display()
+        ),
+        &vec_of_strings!["سلام عليكم", "ملاحظات.txt", "خطا"],
+    );
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -62,7 +62,7 @@ fn test_functions_4() {
 #[test]
 fn test_functions_5() {
     check_error_default("رد ٤٢؛", &|err: &str| {
-        assert_eq!(err, "Cannot return from top-level code.")
+        assert_eq!(err, "لا يمكن الرد من خارج الدوال.")
     })
 }
 
@@ -122,7 +122,7 @@ fn test_functions_9() {
         if n <= 1 {
             return 1;
         }
-        return n * fact(n - 1);
+        n * fact(n - 1)
     }
 
     check_output_default(
@@ -0,0 +1,49 @@
+use crate::common::check_output_with_input;
+use crate::common::evaluate_in;
+use firnas_vm::stdlib::io::OutputMode;
+use firnas_vm::stdlib::io::ScriptedStdIO;
+use firnas_vm::virtual_machine::VirtualMachine;
+
+#[test]
+fn test_input_and_to_number() {
+    check_output_with_input(
+        r#"
+دع ع = الى_رقم(ادخل("العمر: "))؛
+اطبع_سطر(ع + ١)؛
+اطبع_سطر(الى_رقم(ادخل(عدم)))؛
+اطبع_سطر(الى_رقم("٣٫٥"))؛
+اطبع_سطر(الى_رقم("-12"))؛
+اطبع_سطر(الى_رقم("١٢س"))؛
+"#,
+        Default::default(),
+        &["٤١", "7"],
+        &vec_of_strings!["العمر: ", "٤٢", "٧", "٣٫٥", "-١٢", "عدم"],
+    );
+}
+
+#[test]
+fn test_output_modes() {
+    let line = "الاسم Ahmed والعمر 30";
+    assert_eq!(OutputMode::Plain.apply(line), line);
+    assert_eq!(
+        OutputMode::IsolateLtr.apply(line),
+        "الاسم \u{2068}Ahmed\u{2069} والعمر \u{2068}30\u{2069}"
+    );
+}
+
+#[test]
+fn test_output_mode_of_std_io() {
+    let mut std_io = ScriptedStdIO::new(&["Ahmed"]);
+    std_io.mode = OutputMode::IsolateLtr;
+    let mut vm = VirtualMachine::default();
+    vm.std_io = Box::new(std_io);
+    let output = evaluate_in(
+        &mut vm,
+        r#"دع الاسم = ادخل("Name: ")؛ اطبع_سطر("الاسم " + الاسم)؛"#,
+        Default::default(),
+    );
+    assert_eq!(
+        output,
+        Ok(vec_of_strings!["Name: ", "الاسم \u{2068}Ahmed\u{2069}"].to_vec())
+    );
+}
@@ -0,0 +1,23 @@
+use crate::common::check_output_with_input;
+
+#[test]
+fn test_parse_and_stringify() {
+    check_output_with_input(
+        r#"
+دع ق = جيسون.حلل(ادخل(""))؛
+اطبع_سطر(ق["اسم"])؛
+اطبع_سطر(جيسون.الى_نص(ق))؛
+اطبع_سطر(جيسون.الى_نص({"أ": [١]}، {"ازاحة": ٢}))؛
+"#,
+        firnas_ext::Extensions {
+            lists: true,
+            ..Default::default()
+        },
+        &[r#"{"اسم": "علي", "عمر": 30}"#],
+        &vec_of_strings![
+            "علي",
+            r#"{"اسم":"علي","عمر":30}"#,
+            "{\n  \"أ\": [\n    1\n  ]\n}"
+        ],
+    );
+}
@@ -0,0 +1,58 @@
+use crate::common::check_output_lists;
+
+#[test]
+fn test_higher_order_functions() {
+    check_output_lists(
+        r#"
+دالة ضعف(س) { رد س * ٢؛ }
+دالة كبير(س) { رد س > ٢؛ }
+دالة اجمع(أ، ب) { رد أ + ب؛ }
+دع ق = [٣، ١، ٤، ٢]؛
+اطبع_سطر(اختزل(طبق(ق، ضعف)، اجمع، ٠))؛
+اطبع_سطر(طول(رشح(ق، كبير)))؛
+اطبع_سطر(أي(ق، كبير))؛
+اطبع_سطر(كل(ق، كبير))؛
+اطبع_سطر(ابحث(ق، كبير))؛
+رتب(ق)؛
+اطبع_سطر(جيسون.الى_نص(ق))؛
+اعكس(ق)؛
+اطبع_سطر(موقع(ق، ١))؛
+"#,
+        &vec_of_strings!["٢٠", "٢", "صح", "خطا", "٣", "[1,2,3,4]", "٣"],
+    );
+}
+
+#[test]
+fn test_list_editing() {
+    check_output_lists(
+        r#"
+دع ق = مدى(١، ٤)؛
+ادفع(ق، ٩)؛
+أدرج(ق، ٠، ٠)؛
+اطبع_سطر(اسحب(ق))؛
+اطبع_سطر(احذف(ق، ٢))؛
+اطبع_سطر(يحتوي(ق، ٢))؛
+اطبع_سطر(جيسون.الى_نص(ق))؛
+اطبع_سطر(طول(زاوج(ق، مع_الفهرس(ق))))؛
+"#,
+        &vec_of_strings!["٩", "صح", "خطا", "[0,1,3]", "٣"],
+    );
+}
+
+#[test]
+fn test_print_nested_values() {
+    check_output_lists(
+        r#"
+دع ق = [١، (٢،)، {"أ": [صح، عدم]}، ١٫٥]؛
+اطبع_سطر(ق)؛
+ادفع(ق، ق)؛
+اطبع_سطر(نص(طول(ق)) + " عناصر")؛
+اطبع_سطر([ق[٤]، خطا])؛
+"#,
+        &vec_of_strings![
+            "[١، (٢،)، {أ: [صح، عدم]}، ١٫٥]",
+            "٥ عناصر",
+            "[[١، (٢،)، {أ: [صح، عدم]}، ١٫٥، [...]]، خطا]"
+        ],
+    );
+}
@@ -0,0 +1,71 @@
+use crate::common::check_error_default;
+use crate::common::check_output_default;
+
+#[test]
+fn test_math_functions() {
+    check_output_default(
+        r#"
+اطبع_سطر(جا(٠))؛
+اطبع_سطر(أس(٢، ١٠))؛
+اطبع_سطر(مطلق(-٣))؛
+اطبع_سطر(أرضية(٢٫٥))؛
+اطبع_سطر(ق_م_أ(١٢، ١٨))؛
+اطبع_سطر(أولي(٩٧))؛
+اطبع_سطر(ط > ٣)؛
+"#,
+        &vec_of_strings!["٠", "١٠٢٤", "٣", "٢", "٦", "صح", "صح"],
+    );
+}
+
+#[test]
+fn test_print_special_numbers() {
+    check_output_default(
+        r#"
+اطبع_سطر(٠ \ ٠)؛
+اطبع_سطر(-١ \ ٠)؛
+اطبع_سطر(١ \ ٠)؛
+اطبع_سطر(-٥)؛
+اطبع_سطر(-٢٫٥)؛
+اطبع_سطر(١٠٠٠٠٠٠٠٠٠٠٠٠ * ١٠٠٠٠٠٠٠٠٠٠ * ١٠٫٠)؛
+اطبع_سطر(١ \ ١٠٠٠٠٠٠٠٠)؛
+"#,
+        &vec_of_strings![
+            "ليس_رقما",
+            "-لانهاية",
+            "لانهاية",
+            "-٥",
+            "-٢٫٥",
+            "١أس٢٣",
+            "١أس-٨"
+        ],
+    );
+}
+
+#[test]
+fn test_tafqeet() {
+    check_output_default(
+        r#"
+اطبع_سطر(تفقيط(٢٠٠٠٠٠))؛
+اطبع_سطر(تفقيط(١١٠٠٠))؛
+اطبع_سطر("فقط " + تفقيط_مبلغ(١٠٣، "ريال") + " لا غير")؛
+اطبع_سطر(تفقيط_مبلغ(٢٫٠٠١، "دينار"))؛
+"#,
+        &vec_of_strings![
+            "مائتا ألف",
+            "أحد عشر ألفاً",
+            "فقط مائة وثلاثة ريالات لا غير",
+            "ديناران وفلس واحد"
+        ],
+    );
+}
+
+#[test]
+fn test_tafqeet_rational() {
+    check_error_default("تفقيط(نسبي(١، ٣))؛", &|err: &str| {
+        assert!(
+            err.starts_with("عند استدعاء تفقيط: Can't write a rational"),
+            "{}",
+            err
+        )
+    });
+}
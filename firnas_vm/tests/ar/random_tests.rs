@@ -0,0 +1,15 @@
+use crate::common::check_output_default;
+
+#[test]
+fn test_seed_makes_random_reproducible() {
+    check_output_default(
+        r#"
+بذرة(٤٢)؛
+دع أ = عدد_عشوائي(١، ١٠٠)؛
+بذرة(٤٢)؛
+اطبع_سطر(أ == عدد_عشوائي(١، ١٠٠))؛
+اطبع_سطر(عشوائي() < ١)؛
+"#,
+        &vec_of_strings!["صح", "صح"],
+    );
+}
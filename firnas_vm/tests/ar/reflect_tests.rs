@@ -0,0 +1,60 @@
+use crate::common::check_output_lists;
+
+#[test]
+fn test_reflection() {
+    check_output_lists(
+        r#"
+صنف نقطة {
+  تهيئة(س) { هذا.س = س؛ }
+  اختبر_الجمع() { اطبع_سطر("جمع")؛ }
+}
+دع ن = نقطة(١)؛
+اطبع_سطر(نوع(ن))؛
+اطبع_سطر(نوع(١))؛
+اطبع_سطر(ادمج(حقول(ن)، "،"))؛
+اطبع_سطر(ادمج(طرق(نقطة)، "،"))؛
+اطبع_سطر(يملك(ن، "س"))؛
+عين(ن، "ص"، ٢)؛
+اطبع_سطر(اجلب(ن، "ص"))؛
+اجلب(ن، "اختبر_الجمع")()؛
+اطبع_سطر(عدد_المعاملات(نقطة))؛
+اطبع_سطر(اسم(نقطة))؛
+اطبع_سطر(الأصل(نقطة))؛
+"#,
+        &vec_of_strings![
+            "كائن",
+            "صحيح",
+            "س",
+            "اختبر_الجمع،تهييه",
+            "صح",
+            "٢",
+            "جمع",
+            "١",
+            "نقطة",
+            "عدم"
+        ],
+    );
+}
+
+#[test]
+fn test_attribute_names_are_normalized() {
+    check_output_lists(
+        r#"
+صنف مكان {
+  تهيئة() {}
+}
+دع م = مكان()؛
+م.مدينة = ١؛
+اطبع_سطر(يملك(م، "مدينة"))؛
+اطبع_سطر(يملك(م، "مدينه"))؛
+اطبع_سطر(اجلب(م، "مدينة"))؛
+عين(م، "مدينة"، ٢)؛
+عين(م، "قرية"، ٣)؛
+اطبع_سطر(م.مدينه)؛
+اطبع_سطر(م.قرية)؛
+اطبع_سطر(ادمج(حقول(م)، "،"))؛
+اطبع_سطر(يملك(م، "تهيئة"))؛
+"#,
+        &vec_of_strings!["صح", "صح", "١", "٢", "٣", "قريه،مدينه", "صح"],
+    );
+}
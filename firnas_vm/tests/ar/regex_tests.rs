@@ -0,0 +1,15 @@
+use crate::common::check_output_default;
+
+#[test]
+fn test_regex_with_arabic_names() {
+    check_output_default(
+        r#"
+دع ن = نمط.ترجم("\d+")؛
+اطبع_سطر(نمط.يطابق(ن، "عدد ١٢"))؛
+اطبع_سطر(ادمج(نمط.ابحث_الكل("\w+"، "بِسْمِ اللهِ")، "|"))؛
+اطبع_سطر(نمط.استبدل("\p{Mn}"، "بِسْمِ اللهِ"، ""))؛
+اطبع_سطر(ادمج(نمط.قسم("،\s*"، "أ، ب،ج")، "|"))؛
+"#,
+        &vec_of_strings!["صح", "بِسْمِ|اللهِ", "بسم الله", "أ|ب|ج"],
+    );
+}
@@ -0,0 +1,15 @@
+use crate::common::check_output_default;
+
+#[test]
+fn test_set_add_contains() {
+    check_output_default(
+        r#"
+دع م = {١، ٢}؛
+أضف(م، ٣)؛
+اطبع_سطر(يحتوي(م، ٣))؛
+اطبع_سطر(احذف(م، ١))؛
+اطبع_سطر(يحتوي(م، ١))؛
+"#,
+        &vec_of_strings!["صح", "صح", "خطا"],
+    );
+}
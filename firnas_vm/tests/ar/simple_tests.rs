@@ -92,7 +92,7 @@ fn it_should_fail_on_read_from_own_initializer() {
     }
 }
 "#,
-        &|err: &str| assert!(err.starts_with("Cannot read local variable in its own initializer.")),
+        &|err: &str| assert!(err.starts_with("لا يمكن قراءة متغير محلي في قيمته الابتدائية.")),
     )
 }
 
@@ -123,3 +123,52 @@ fn test_print_globals() {
         &vec_of_strings!["٠", "١"],
     );
 }
+
+#[test]
+fn it_should_keep_integer_arithmetic_exact() {
+    check_output_default(
+        r#"
+اطبع_سطر(٩٠٠٧١٩٩٢٥٤٧٤٠٩٩٣ + ٢)؛
+اطبع_سطر(٦ \ ٣)؛
+اطبع_سطر(٧ \ ٢)؛
+اطبع_سطر(-٥ * ٣)؛
+"#,
+        &vec_of_strings!["٩٠٠٧١٩٩٢٥٤٧٤٠٩٩٥", "٢", "٣٫٥", "-١٥"],
+    );
+}
+
+#[test]
+fn it_should_compare_integers_and_floats() {
+    check_output_default(
+        r#"
+اطبع_سطر(١ == ١٫٠)؛
+اطبع_سطر(٢ > ١٫٥)؛
+"#,
+        &vec_of_strings!["صح", "صح"],
+    );
+}
+
+#[test]
+fn it_should_promote_integer_overflow_to_big_integer() {
+    check_output_default(
+        r#"
+اطبع_سطر(٩٢٢٣٣٧٢٠٣٦٨٥٤٧٧٥٨٠٧ + ١)؛
+اطبع_سطر(١٢٣٤٥٦٧٨٩٠١٢٣٤٥٦٧٨٩٠١٢٣٤٥٦٧٨٩٠ * ١٠)؛
+"#,
+        &vec_of_strings!["٩٢٢٣٣٧٢٠٣٦٨٥٤٧٧٥٨٠٨", "١٢٣٤٥٦٧٨٩٠١٢٣٤٥٦٧٨٩٠١٢٣٤٥٦٧٨٩٠٠"],
+    );
+}
+
+#[test]
+fn it_should_keep_rational_arithmetic_exact() {
+    check_output_default(
+        r#"
+اطبع_سطر(٠٫١ن + ٠٫٢ن == ٠٫٣ن)؛
+اطبع_سطر(١ن \ ٣)؛
+اطبع_سطر(نسبي(٢، -٤))؛
+اطبع_سطر(بسط(٠٫٧٥ن))؛
+اطبع_سطر(مقام(٠٫٧٥ن))؛
+"#,
+        &vec_of_strings!["صح", "١/٣", "-١/٢", "٣", "٤"],
+    );
+}
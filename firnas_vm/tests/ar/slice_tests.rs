@@ -0,0 +1,13 @@
+use crate::common::check_output_lists;
+
+#[test]
+fn test_string_slicing_keeps_combining_marks() {
+    check_output_lists(
+        r#"
+دع ن = "سَلامٌ"؛
+اطبع_سطر(ن[٠:١])؛
+اطبع_سطر(ن[١:])؛
+"#,
+        &vec_of_strings!["سَ", "لامٌ"],
+    );
+}
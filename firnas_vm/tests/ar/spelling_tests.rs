@@ -0,0 +1,71 @@
+use crate::common::check_output_default;
+use firnas_compiler::compiler::Compiler;
+use firnas_compiler::diagnostic::Language;
+use firnas_tokenizer::tokenizer::IdentifierSpelling;
+use firnas_tokenizer::tokenizer::ScanOptions;
+use firnas_vm::virtual_machine::VirtualMachine;
+
+#[test]
+fn test_spelling_variants_are_one_name() {
+    check_output_default(
+        r#"
+دع مدرسة = "الأمل"؛
+اطبع_سطر(مدرسه)؛
+دع م = {١}؛
+اضف(م، ٢)؛
+أضف(م، ٣)؛
+اطبع_سطر(طول(م))؛
+صنف طالب {
+  تهيئه(اسم_الطالب) { هذا.الإسم = اسم_الطالب؛ }
+}
+اطبع_سطر(طالب("علي").الاسم)؛
+اطبع_سطر(طالب)؛
+اطبع_سطر(تاريخ.أنشئ(٢٠٢٤، ٣، ١).سنة)؛
+اطبع_سطر(تاريخ.انشئ(٢٠٢٤، ٣، ١).سنه)؛
+"#,
+        &vec_of_strings!["الأمل", "٣", "علي", "<class 'طالب'>", "٢٠٢٤", "٢٠٢٤"],
+    );
+}
+
+#[test]
+fn test_tashkeel_and_tatweel_in_names() {
+    check_output_default(
+        r#"
+دَعْ عَدَدٌ = ٣؛
+اطبع_سطر(عدد + ١)؛
+دالة ضَاعِفْ(س) { رُدَّ س * ٢؛ }
+اطبع_سطر(ضـاعـف(عَدَد))؛
+اطبع_سطر(ضَاعِفْ)؛
+دع گل = "وردة"؛
+اطبع_سطر(گل)؛
+"#,
+        &vec_of_strings!["٤", "٦", "<fn 'ضَاعِفْ'>", "وردة"],
+    );
+}
+
+#[test]
+fn test_builtin_names_found_when_spellings_only_warn() {
+    let code = r#"
+دع م = {١}؛
+أضف(م، ٢)؛
+اطبع_سطر(طول(م))؛
+دع ت = تاريخ.أنشئ(٢٠٢٤، ٣، ١)؛
+اطبع_سطر(ت.سنة)؛
+اطبع_سطر(طول(حقول(ت)))؛
+"#;
+    let options = ScanOptions {
+        spelling: IdentifierSpelling::Warn,
+        ..ScanOptions::default()
+    };
+    let (func, _) = Compiler::compile_with_options(
+        String::from(code),
+        firnas_ext::Extensions::default(),
+        Language::default(),
+        options,
+    )
+    .unwrap();
+
+    let mut vm = VirtualMachine::default();
+    vm.interpret(func).unwrap();
+    assert_eq!(vm.get_output(), vec_of_strings!["٢", "٢٠٢٤", "٩"]);
+}
@@ -0,0 +1,15 @@
+use crate::common::check_output_default;
+
+#[test]
+fn test_string_functions() {
+    check_output_default(
+        r#"
+اطبع_سطر(اقتطع("مرحبا بالعالم"، ٠، ٥))؛
+اطبع_سطر(ابحث_عن_نص("سَلامٌ"، "ا"))؛
+اطبع_سطر(استبدل("سلام"، "س"، "ك"))؛
+اطبع_سطر(ادمج(قسم("أ ب ج"، " ")، "،"))؛
+اطبع_سطر(يبدأ_ب("سَلام"، "سَ"))؛
+"#,
+        &vec_of_strings!["مرحبا", "٢", "كلام", "أ،ب،ج", "صح"],
+    );
+}
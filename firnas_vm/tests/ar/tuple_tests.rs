@@ -0,0 +1,24 @@
+use crate::common::check_output_default;
+
+#[test]
+fn test_tuple_comparison() {
+    check_output_default(
+        r#"
+اطبع_سطر((١، ٢) == (١، ٢))؛
+اطبع_سطر((١، ٢) < (١، ٣))؛
+"#,
+        &vec_of_strings!["صح", "صح"],
+    );
+}
+
+#[test]
+fn test_destructuring() {
+    check_output_default(
+        r#"
+دع (أ، ب) = (١، ٢)؛
+اطبع_سطر(أ)؛
+اطبع_سطر(ب)؛
+"#,
+        &vec_of_strings!["١", "٢"],
+    );
+}
@@ -1,25 +1,41 @@
 use firnas_compiler::compiler::Compiler;
 use firnas_compiler::compiler::Error;
+use firnas_vm::stdlib::io::ScriptedStdIO;
 use firnas_vm::virtual_machine::VirtualMachine;
 use firnas_vm::virtual_machine::VmError;
 
 #[macro_export]
 macro_rules! vec_of_strings {
-    ($($x:expr),*) => (vec![$($x.to_string()),*]);
+    ($($x:expr),*) => ([$($x.to_string()),*]);
 }
 
 pub fn evaluate(code: &str, extensions: firnas_ext::Extensions) -> Result<Vec<String>, String> {
+    evaluate_with_input(code, extensions, &[])
+}
+
+pub fn evaluate_with_input(
+    code: &str,
+    extensions: firnas_ext::Extensions,
+    input: &[&str],
+) -> Result<Vec<String>, String> {
+    let mut vm = VirtualMachine::default();
+    vm.std_io = Box::new(ScriptedStdIO::new(input));
+    evaluate_in(&mut vm, code, extensions)
+}
+
+/// Runs `code` on a VM the test has already configured.
+pub fn evaluate_in(
+    vm: &mut VirtualMachine,
+    code: &str,
+    extensions: firnas_ext::Extensions,
+) -> Result<Vec<String>, String> {
     let func_or_err = Compiler::compile(String::from(code), extensions);
 
     match func_or_err {
-        Ok(func) => {
-            let mut vm = VirtualMachine::default();
-            let res = vm.interpret(func);
-            match res {
-                Ok(()) => Ok(vm.get_output()),
-                Err(VmError::Runtime(err)) => Err(err),
-            }
-        }
+        Ok(func) => match vm.interpret(func) {
+            Ok(()) => Ok(vm.get_output()),
+            Err(VmError::Runtime(err)) => Err(err.what),
+        },
         Err(Error::Lexical(err)) => Err(err.what),
         Err(Error::Parse(err)) => Err(err.what),
         Err(Error::Semantic(err)) => Err(err.what),
@@ -40,6 +56,7 @@ pub fn check_output_default(code: &str, expected_output: &[String]) {
     check_output(code, firnas_ext::Extensions::default(), expected_output);
 }
 
+#[allow(dead_code)]
 pub fn check_output_lists(code: &str, expected_output: &[String]) {
     check_output(
         code,
@@ -51,7 +68,20 @@ pub fn check_output_lists(code: &str, expected_output: &[String]) {
     );
 }
 
-pub fn check_error(code: &str, extensions: firnas_ext::Extensions, f: &dyn Fn(&str) -> ()) {
+#[allow(dead_code)]
+pub fn check_output_with_input(
+    code: &str,
+    extensions: firnas_ext::Extensions,
+    input: &[&str],
+    expected_output: &[String],
+) {
+    match evaluate_with_input(code, extensions, input) {
+        Ok(output) => assert_eq!(output, expected_output),
+        Err(err) => panic!("{}", err),
+    }
+}
+
+pub fn check_error(code: &str, extensions: firnas_ext::Extensions, f: &dyn Fn(&str)) {
     let res = evaluate(code, extensions);
 
     match res {
@@ -60,6 +90,6 @@ pub fn check_error(code: &str, extensions: firnas_ext::Extensions, f: &dyn Fn(&s
     }
 }
 
-pub fn check_error_default(code: &str, f: &dyn Fn(&str) -> ()) {
+pub fn check_error_default(code: &str, f: &dyn Fn(&str)) {
     check_error(code, firnas_ext::Extensions::default(), f);
 }
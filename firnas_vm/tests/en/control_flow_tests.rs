@@ -167,7 +167,7 @@ fn test_for() {
         if n <= 1 {
             return 1;
         }
-        return n * fact(n - 1);
+        n * fact(n - 1)
     }
 
     check_output_default(
@@ -183,3 +183,22 @@ fn test_for() {
         &vec_of_strings![format!("{}", fact(10))],
     );
 }
+
+#[test]
+fn test_loop_body_locals() {
+    check_output_default(
+        r#"
+for (var i = 0; i < 3; i = i + 1) {
+    var n = i * 2;
+    printLine(n);
+}
+var j = 0;
+while (j < 2) {
+    var m = j + 10;
+    printLine(m);
+    j = j + 1;
+}
+"#,
+        &vec_of_strings!["0", "2", "4", "10", "11"],
+    );
+}
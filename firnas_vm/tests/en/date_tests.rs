@@ -0,0 +1,209 @@
+use crate::common::check_error_default;
+use crate::common::check_output_default;
+
+#[test]
+fn test_make_and_fields() {
+    check_output_default(
+        r#"
+var d = date.make(2024, 2, 29);
+printLine(d.year);
+printLine(d.month);
+printLine(d.day);
+printLine(d.weekday);
+printLine(date.toMillis(date.make(1970, 1, 2)));
+"#,
+        &vec_of_strings!["2024", "2", "29", "4", "86400000"],
+    );
+}
+
+#[test]
+fn test_format() {
+    check_output_default(
+        r#"
+var d = date.at(date.make(2026, 3, 5), 9, 7, 3);
+printLine(date.format(d, "yyyy-MM-ddTHH:mm:ssZ"));
+printLine(date.format(d, "EEEE d MMMM yyyy, H:mm"));
+printLine(date.format(date.withOffset(d, 180), "yyyy-MM-dd HH:mm Z"));
+printLine(date.format(date.withOffset(d, -570), "dd HH:mm Z"));
+"#,
+        &vec_of_strings![
+            "2026-03-05T09:07:03+00:00",
+            "Thursday 5 March 2026, 9:07",
+            "2026-03-05 12:07 +03:00",
+            "04 23:37 -09:30"
+        ],
+    );
+}
+
+#[test]
+fn test_arithmetic() {
+    check_output_default(
+        r#"
+var d = date.make(2024, 1, 31);
+printLine(date.format(date.addDays(d, 30), "yyyy-MM-dd"));
+printLine(date.format(date.addDays(d, -31), "yyyy-MM-dd"));
+printLine(date.format(date.addMonths(d, 1), "yyyy-MM-dd"));
+printLine(date.format(date.addMonths(d, -2), "yyyy-MM-dd"));
+printLine(date.format(date.addSeconds(d, 90.5), "HH:mm:ss"));
+printLine(date.diffSeconds(date.addDays(d, 1), d));
+printLine(date.diffSeconds(d, date.addSeconds(d, 0.25)));
+"#,
+        &vec_of_strings![
+            "2024-03-01",
+            "2023-12-31",
+            "2024-02-29",
+            "2023-11-30",
+            "00:01:30",
+            "86400",
+            "-0.25"
+        ],
+    );
+}
+
+#[test]
+fn test_parse() {
+    check_output_default(
+        r#"
+var d = date.parse("2026-03-05T10:20:30+03:00");
+printLine(d.hour);
+printLine(d.offset);
+printLine(date.format(date.withOffset(d, 0), "yyyy-MM-ddTHH:mm:ssZ"));
+printLine(date.format(date.parse("1999-12-31"), "yyyy-MM-dd HH:mm"));
+printLine(date.format(date.parse("2000-01-01 23:59Z"), "HH:mm:ss"));
+"#,
+        &vec_of_strings![
+            "10",
+            "180",
+            "2026-03-05T07:20:30+00:00",
+            "1999-12-31 00:00",
+            "23:59:00"
+        ],
+    );
+}
+
+#[test]
+fn test_invalid_dates() {
+    check_error_default("date.make(2023, 2, 29);", &|err: &str| {
+        assert_eq!(err, "When calling date.make: Invalid date 2023-02-29.")
+    });
+    check_error_default(r#"date.parse("2026-13-01");"#, &|err: &str| {
+        assert_eq!(
+            err,
+            "When calling date.parse: Invalid date '2026-13-01', expected a date like 2026-03-05T10:20:30+03:00."
+        )
+    });
+    check_error_default("date.format(3, \"yyyy\");", &|err: &str| {
+        assert_eq!(
+            err,
+            "When calling date.format: Invalid call: expected date, got Integer."
+        )
+    });
+}
+
+#[test]
+fn test_dates_out_of_range() {
+    for (code, function) in [
+        ("date.make(300000000, 1, 1);", "date.make"),
+        ("date.make(275761, 1, 1);", "date.make"),
+        (
+            "date.addMonths(date.now(), 9223372036854775807);",
+            "date.addMonths",
+        ),
+        ("date.addMonths(date.now(), 4000000);", "date.addMonths"),
+        ("date.fromMillis(9223372036854775807);", "date.fromMillis"),
+        (
+            "date.addDays(date.make(2026, 1, 1), 100000000);",
+            "date.addDays",
+        ),
+        (
+            "date.fromHijri(9223372036854775807, 1, 1);",
+            "date.fromHijri",
+        ),
+    ] {
+        check_error_default(code, &|err: &str| {
+            assert_eq!(
+                err,
+                format!("When calling {}: Date out of range.", function)
+            )
+        });
+    }
+    check_output_default(
+        r#"
+printLine(date.toMillis(date.fromMillis(8640000000000000)));
+printLine(date.withOffset(date.fromMillis(-8640000000000000), -60).year);
+"#,
+        &vec_of_strings!["8640000000000000", "-271821"],
+    );
+}
+
+#[test]
+fn test_date_fields_are_read_only() {
+    check_error_default(
+        "var d = date.make(2024, 2, 29); d.year = 1999;",
+        &|err: &str| {
+            assert_eq!(
+                err,
+                "can't set attribute year of <DateTime instance>, its attributes are read-only."
+            )
+        },
+    );
+    check_error_default(
+        r#"setAttr(date.make(2024, 2, 29), "millis", 0);"#,
+        &|err: &str| {
+            assert_eq!(
+                err,
+                "When calling setAttr: Can't set attribute millis of <DateTime instance>, its attributes are read-only."
+            )
+        },
+    );
+}
+
+#[test]
+fn test_instance_of_another_date_time_class_is_not_a_date() {
+    check_error_default(
+        r#"
+class DateTime {}
+var d = DateTime();
+d.millis = 0;
+d.offset = 0;
+date.format(d, "yyyy");
+"#,
+        &|err: &str| {
+            assert_eq!(
+                err,
+                "When calling date.format: Invalid call: expected date, got Instance."
+            )
+        },
+    );
+}
+
+#[test]
+fn test_hijri() {
+    check_output_default(
+        r#"
+var d = date.make(2026, 3, 4);
+printLine(date.toHijri(d));
+printLine(date.formatHijri(d, "d MMMM yyyy"));
+printLine(date.format(date.fromHijri(1447, 9, 1), "yyyy-MM-dd"));
+printLine(date.toHijri(date.make(1970, 1, 1)));
+printLine(date.toHijri(date.fromHijri(1445, 12, 30)));
+"#,
+        &vec_of_strings![
+            "(1447, 9, 15)",
+            "15 Ramadan 1447",
+            "2026-02-18",
+            "(1389, 10, 22)",
+            "(1445, 12, 30)"
+        ],
+    );
+}
+
+#[test]
+fn test_invalid_hijri_date() {
+    check_error_default("date.fromHijri(1446, 12, 30);", &|err: &str| {
+        assert_eq!(
+            err,
+            "When calling date.fromHijri: Invalid Hijri date 1446-12-30."
+        )
+    });
+}
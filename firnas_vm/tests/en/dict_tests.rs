@@ -0,0 +1,103 @@
+use crate::common::check_error;
+use crate::common::check_output_default;
+use crate::common::check_output_lists;
+
+#[test]
+fn test_dict_literal_and_subscript() {
+    check_output_lists(
+        r#"
+var d = {"a": 1, "b": 2, "a": 3};
+printLine(d);
+printLine(d["a"]);
+d["c"] = (1, 2);
+printLine(d["c"]);
+printLine(len(d));
+"#,
+        &vec_of_strings!["{a: 3, b: 2}", "3", "(1, 2)", "3"],
+    );
+}
+
+#[test]
+fn test_empty_dict_and_empty_set() {
+    check_output_default(
+        r#"
+printLine({:});
+printLine({});
+printLine({:} == {:});
+printLine({:} == {});
+"#,
+        &vec_of_strings!["{:}", "{}", "true", "false"],
+    );
+}
+
+#[test]
+fn test_dict_equality_ignores_order() {
+    check_output_default(
+        r#"
+printLine({1: "one", 2: "two"} == {2: "two", 1: "one"});
+printLine({1: "one"} == {1: "uno"});
+"#,
+        &vec_of_strings!["true", "false"],
+    );
+}
+
+#[test]
+fn test_dict_that_contains_itself_compares() {
+    check_output_lists(
+        r#"
+var d = {:};
+d["x"] = d;
+var e = {:};
+e["x"] = e;
+var f = {:};
+f["x"] = f;
+f["y"] = 1;
+printLine(d == d);
+printLine(d == e);
+printLine(d == f);
+"#,
+        &vec_of_strings!["true", "true", "false"],
+    );
+}
+
+#[test]
+fn test_dict_natives() {
+    check_output_default(
+        r#"
+var d = {"x": 1, "y": 2};
+printLine(contains(d, "x"));
+printLine(contains(d, (1, 2)));
+printLine(remove(d, "x"));
+printLine(remove(d, "x"));
+printLine(join(keys(d), ","));
+printLine(len(values(d)));
+fun show(key) { printLine(key); }
+forEach({"b": 2, "a": 1}, show);
+"#,
+        &vec_of_strings!["true", "false", "true", "false", "y", "1", "a", "b"],
+    );
+}
+
+#[test]
+fn test_missing_key() {
+    check_error(
+        r#"
+var d = {"a": 1};
+d["b"];
+"#,
+        firnas_ext::Extensions {
+            lists: true,
+            ..Default::default()
+        },
+        &|err: &str| assert_eq!(err, "Key b not found at line 3"),
+    );
+}
+
+#[test]
+fn test_unhashable_key() {
+    check_error(
+        "var d = {{1}: 2};",
+        firnas_ext::Extensions::default(),
+        &|err: &str| assert_eq!(err, "Unhashable value of type Set at line 1"),
+    );
+}
@@ -0,0 +1,152 @@
+use crate::common::check_output_default;
+use crate::common::evaluate_in;
+use firnas_vm::stdlib::file::FileAccess;
+use firnas_vm::virtual_machine::VirtualMachine;
+use std::path::PathBuf;
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("firnas_{}_{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_write_read_append_delete() {
+    let dir = scratch_dir("file_roundtrip");
+    let path = dir.join("notes.txt");
+
+    check_output_default(
+        &format!(
+            r#"
+var path = "{}";
+printLine(exists(path));
+writeFile(path, "one");
+appendFile(path, " two");
+printLine(readFile(path));
+printLine(exists(path));
+deleteFile(path);
+printLine(exists(path));
+"#,
+            path.display()
+        ),
+        &vec_of_strings!["false", "one two", "true", "false"],
+    );
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_list_dir() {
+    let dir = scratch_dir("file_list_dir");
+    std::fs::write(dir.join("b.txt"), "").unwrap();
+    std::fs::write(dir.join("a.txt"), "").unwrap();
+
+    check_output_default(
+        &format!(r#"printLine(join(listDir("{}"), ","));"#, dir.display()),
+        &vec_of_strings!["a.txt,b.txt"],
+    );
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_missing_file_is_a_runtime_error() {
+    let dir = scratch_dir("file_missing");
+    let mut vm = VirtualMachine::default();
+    vm.file_access = FileAccess::Within(dir.clone());
+
+    let res = evaluate_in(&mut vm, r#"readFile("missing.txt");"#, Default::default());
+    let err = res.unwrap_err();
+    assert!(err.starts_with("When calling readFile: "), "{}", err);
+    assert!(err.contains("missing.txt"), "{}", err);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_file_access_denied() {
+    let mut vm = VirtualMachine::default();
+    vm.file_access = FileAccess::Denied;
+
+    assert_eq!(
+        evaluate_in(&mut vm, r#"exists("a.txt");"#, Default::default()),
+        Err(String::from(
+            "When calling exists: File access is disabled."
+        ))
+    );
+}
+
+#[test]
+fn test_file_access_within_directory() {
+    let dir = scratch_dir("file_within");
+    let mut vm = VirtualMachine::default();
+    vm.file_access = FileAccess::Within(dir.clone());
+
+    assert_eq!(
+        evaluate_in(
+            &mut vm,
+            r#"writeFile("inside.txt", "hi"); printLine(readFile("./inside.txt"));"#,
+            Default::default()
+        ),
+        Ok(vec_of_strings!["hi"].to_vec())
+    );
+    assert_eq!(
+        std::fs::read_to_string(dir.join("inside.txt")).unwrap(),
+        "hi"
+    );
+
+    for path in ["../outside.txt", "/etc/passwd"] {
+        let err = evaluate_in(
+            &mut vm,
+            &format!(r#"readFile("{}");"#, path),
+            Default::default(),
+        )
+        .unwrap_err();
+        assert!(err.contains("is outside the allowed directory"), "{}", err);
+    }
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn test_file_access_within_directory_does_not_follow_links_out() {
+    let dir = scratch_dir("file_within_links");
+    let root = dir.join("root");
+    let outside = dir.join("outside");
+    std::fs::create_dir_all(root.join("sub")).unwrap();
+    std::fs::create_dir_all(&outside).unwrap();
+    std::fs::write(outside.join("secret.txt"), "secret").unwrap();
+    std::os::unix::fs::symlink(&outside, root.join("out")).unwrap();
+    std::os::unix::fs::symlink(outside.join("new.txt"), root.join("dangling.txt")).unwrap();
+    std::os::unix::fs::symlink(root.join("sub"), root.join("inner")).unwrap();
+
+    let within = || {
+        let mut vm = VirtualMachine::default();
+        vm.file_access = FileAccess::Within(root.clone());
+        vm
+    };
+
+    for code in [
+        r#"readFile("out/secret.txt");"#,
+        r#"writeFile("out/new.txt", "x");"#,
+        r#"writeFile("dangling.txt", "x");"#,
+        r#"listDir("out");"#,
+    ] {
+        let err = evaluate_in(&mut within(), code, Default::default()).unwrap_err();
+        assert!(err.contains("is outside the allowed directory"), "{}", err);
+    }
+    assert!(!outside.join("new.txt").exists());
+
+    assert_eq!(
+        evaluate_in(
+            &mut within(),
+            r#"writeFile("inner/a.txt", "hi"); printLine(readFile("sub/a.txt"));"#,
+            Default::default()
+        ),
+        Ok(vec_of_strings!["hi"].to_vec())
+    );
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -121,7 +121,7 @@ fn test_functions_9() {
         if n <= 1 {
             return 1;
         }
-        return n * fact(n - 1);
+        n * fact(n - 1)
     }
 
     check_output_default(
@@ -273,3 +273,22 @@ a();
         &vec_of_strings!["hello world"],
     )
 }
+
+#[test]
+fn test_call_in_block_keeps_locals() {
+    check_output_default(
+        r#"
+fun zero() { return 0; }
+fun add(a, b) { return a + b; }
+{
+    var x = 7;
+    var y = add(1, 2);
+    var z = zero();
+    printLine(x);
+    printLine(y);
+    printLine(z);
+}
+"#,
+        &vec_of_strings!["7", "3", "0"],
+    );
+}
@@ -0,0 +1,72 @@
+use crate::common::check_error_default;
+use crate::common::check_output_default;
+use crate::common::check_output_with_input;
+
+#[test]
+fn test_input_reads_lines() {
+    check_output_with_input(
+        r#"
+var name = input("Name: ");
+printLine("Hello " + name);
+printLine(input(nil));
+printLine(input(""));
+"#,
+        Default::default(),
+        &["Ali", "second"],
+        &vec_of_strings!["Name: ", "Hello Ali", "second", "nil"],
+    );
+}
+
+#[test]
+fn test_read_all() {
+    check_output_with_input(
+        r#"
+printLine(input(""));
+printLine(readAll());
+printLine(readAll());
+"#,
+        Default::default(),
+        &["one", "two", "three"],
+        &vec_of_strings!["one", "two\nthree\n", ""],
+    );
+}
+
+#[test]
+fn test_to_number() {
+    check_output_with_input(
+        r#"
+var n = toNumber(input(""));
+printLine(n + 1);
+printLine(toNumber(" -2.5 "));
+printLine(toNumber("123456789012345678901234567890"));
+printLine(toNumber("12a"));
+printLine(toNumber("1.2.3"));
+printLine(toNumber(7));
+printLine(toNumber("١٢") + 1);
+printLine(toNumber("٣٫٥"));
+"#,
+        Default::default(),
+        &["41"],
+        &vec_of_strings![
+            "42",
+            "-2.5",
+            "123456789012345678901234567890",
+            "nil",
+            "nil",
+            "7",
+            "13",
+            "3.5"
+        ],
+    );
+}
+
+#[test]
+fn test_to_number_rejects_non_strings() {
+    check_error_default("toNumber(true);", &|err: &str| {
+        assert_eq!(
+            err,
+            "When calling toNumber: Invalid call: expected string, got Bool."
+        )
+    });
+    check_output_default("printLine(toNumber(\"\"));", &vec_of_strings!["nil"]);
+}
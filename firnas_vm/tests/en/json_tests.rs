@@ -0,0 +1,229 @@
+use crate::common::check_error;
+use crate::common::check_output_lists;
+use crate::common::check_output_with_input;
+
+fn lists() -> firnas_ext::Extensions {
+    firnas_ext::Extensions {
+        lists: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_parse() {
+    check_output_with_input(
+        r#"
+var d = json.parse(input(""));
+printLine(d["name"]);
+printLine(d["tags"][1]);
+printLine(d["tags"][3]);
+printLine(d["nested"]["x"]);
+printLine(len(d["nested"]["empty"]));
+"#,
+        lists(),
+        &[
+            r#" {"name": "Ali", "tags": [1, 2.5e1, true, null], "nested": {"x": "AB\n😀", "empty": {}}} "#,
+        ],
+        &vec_of_strings!["Ali", "25", "nil", "AB\n😀", "0"],
+    );
+}
+
+#[test]
+fn test_stringify() {
+    check_output_lists(
+        r#"
+printLine(json.stringify({"b": [1, 2.5, nil], "a": (true, "x"), "c": {:}}));
+printLine(json.stringify(9223372036854775807 + 1));
+printLine(json.stringify(rational(1, 4)));
+"#,
+        &vec_of_strings![
+            r#"{"a":[true,"x"],"b":[1,2.5,null],"c":{}}"#,
+            "9223372036854775808",
+            "0.25"
+        ],
+    );
+}
+
+#[test]
+fn test_stringify_with_indent() {
+    check_output_lists(
+        r#"
+printLine(json.stringify({"a": [1, []], "b": {:}}, {"indent": 2}));
+printLine(json.stringify([1], nil));
+printLine(json.stringify([1], {:}));
+"#,
+        &vec_of_strings![
+            "{\n  \"a\": [\n    1,\n    []\n  ],\n  \"b\": {}\n}",
+            "[1]",
+            "[1]"
+        ],
+    );
+}
+
+#[test]
+fn test_stringify_invalid_options() {
+    for (options, expected) in [
+        (
+            "2",
+            "Invalid call: expected a dictionary of options, got Integer",
+        ),
+        (r#"{"indnet": 2}"#, "Invalid call: unknown option 'indnet'"),
+        (
+            r#"{"indent": -1}"#,
+            "Invalid call: indent must be from 0 to 10, got -1",
+        ),
+        (
+            r#"{"indent": 100000000000000}"#,
+            "Invalid call: indent must be from 0 to 10, got 100000000000000",
+        ),
+        (
+            r#"{"indent": "  "}"#,
+            "Invalid call: expected an integer indent, got String",
+        ),
+    ] {
+        check_error(
+            &format!("json.stringify([1], {});", options),
+            lists(),
+            &|err: &str| assert_eq!(err, format!("When calling json.stringify: {}.", expected)),
+        );
+    }
+    check_error("json.stringify([1], nil, 2);", lists(), &|err: &str| {
+        assert_eq!(
+            err,
+            "Native function json.stringify expected 1-2 arguments but found 3."
+        )
+    });
+}
+
+#[test]
+fn test_stringify_sorts_keys() {
+    check_output_with_input(
+        r#"
+printLine(json.stringify(json.parse(input(""))));
+"#,
+        lists(),
+        &[r#"{"b": 1, "c": {"z": 2, "y": 3}, "a": 4}"#],
+        &vec_of_strings![r#"{"a":4,"b":1,"c":{"y":3,"z":2}}"#],
+    );
+}
+
+#[test]
+fn test_round_trip() {
+    check_output_with_input(
+        r#"
+printLine(json.stringify(json.parse(input(""))));
+"#,
+        lists(),
+        &[r#"{"quote": "say \"hi\"\t\\", "list": [[], [0.5, -3]]}"#],
+        &vec_of_strings![r#"{"list":[[],[0.5,-3]],"quote":"say \"hi\"\t\\"}"#],
+    );
+}
+
+#[test]
+fn test_parse_errors() {
+    for (text, expected) in [
+        ("[1, 2", "Invalid JSON at position 5: expected ',' or ']'"),
+        (
+            "{1: 2}",
+            "Invalid JSON at position 1: expected a string key",
+        ),
+        ("01", "Invalid JSON at position 0: invalid number"),
+        (
+            "[1] x",
+            "Invalid JSON at position 4: unexpected trailing characters",
+        ),
+        ("tru", "Invalid JSON at position 0: unexpected character"),
+    ] {
+        check_error(
+            &format!("json.parse(\"{}\");", text),
+            lists(),
+            &|err: &str| assert_eq!(err, format!("When calling json.parse: {}.", expected)),
+        );
+    }
+}
+
+#[test]
+fn test_parse_nested_too_deeply() {
+    let deep = "[".repeat(200_000);
+    check_error(
+        &format!("json.parse(\"{}\");", deep),
+        lists(),
+        &|err: &str| {
+            assert_eq!(
+                err,
+                "When calling json.parse: Invalid JSON at position 512: nested too deeply."
+            )
+        },
+    );
+
+    let nested = format!("{}{}", "[".repeat(512), "]".repeat(512));
+    check_output_lists(
+        &format!("printLine(len(json.parse(\"{}\")));", nested),
+        &vec_of_strings!["1"],
+    );
+}
+
+#[test]
+fn test_stringify_nested_too_deeply() {
+    check_error(
+        r#"
+var l = [];
+for (var i = 0; i < 1000; i = i + 1) {
+    l = [l];
+}
+json.stringify(l);
+"#,
+        lists(),
+        &|err: &str| {
+            assert_eq!(
+                err,
+                "When calling json.stringify: Can't convert a value nested too deeply to JSON."
+            )
+        },
+    );
+}
+
+#[test]
+fn test_stringify_cycle() {
+    check_error(
+        r#"
+var l = [1, 2];
+l[0] = [l];
+json.stringify(l);
+"#,
+        lists(),
+        &|err: &str| {
+            assert_eq!(
+                err,
+                "When calling json.stringify: Can't convert a value that contains itself to JSON."
+            )
+        },
+    );
+}
+
+#[test]
+fn test_stringify_shared_value_is_not_a_cycle() {
+    check_output_lists(
+        r#"
+var shared = [1];
+printLine(json.stringify([shared, shared]));
+"#,
+        &vec_of_strings!["[[1],[1]]"],
+    );
+}
+
+#[test]
+fn test_stringify_unsupported_values() {
+    check_error("json.stringify({1: 2});", lists(), &|err: &str| {
+        assert_eq!(
+            err,
+            "When calling json.stringify: JSON object keys must be strings, got Integer."
+        )
+    });
+    check_error("json.stringify(clock);", lists(), &|err: &str| {
+        assert_eq!(
+            err,
+            "When calling json.stringify: Can't convert value of type NativeFunction to JSON."
+        )
+    });
+}
@@ -1,106 +1,333 @@
-// use crate::common::check_output_lists;
-
-// #[test]
-// fn test_list_building() {
-//     check_output_lists("print([1,2,3]);", &vec_of_strings!["[1, 2, 3]"])
-// }
-
-// #[test]
-// fn test_empty_list_building() {
-//     check_output_lists("print([]);", &vec_of_strings!["[]"])
-// }
-
-// #[test]
-// fn test_list_concat() {
-//     check_output_lists(
-//         "print([1,2,3] + [4,5,6]);",
-//         &vec_of_strings!["[1, 2, 3, 4, 5, 6]"],
-//     )
-// }
-
-// #[test]
-// fn test_len() {
-//     check_output_lists(
-//         r#"
-// print(len(""));
-// print(len("cat"));
-// print(len([]));
-// print(len([1,2,3,4]));
-// "#,
-//         &vec_of_strings!["0", "3", "0", "4"],
-//     )
-// }
-
-// #[test]
-// fn test_for_each() {
-//     check_output_lists(
-//         r#"
-// fun f(arg) { print arg; }
-// forEach([1,2,3,4], f);
-// "#,
-//         &vec_of_strings!["1", "2", "3", "4"],
-//     )
-// }
-
-// #[test]
-// fn test_map() {
-//     check_output_lists(
-//         r#"
-// fun f(arg) { return arg + 1; }
-// print(map(f, [1,2,3,4]));
-// "#,
-//         &vec_of_strings!["[2, 3, 4, 5]"],
-//     )
-// }
-
-// #[test]
-// fn test_list_subscript() {
-//     check_output_lists(
-//         r#"
-// var xs = [0,1];
-// print(xs[0]);
-// print(xs[1]);
-// print(xs[-1]);
-// print(xs[-2]);
-// "#,
-//         &vec_of_strings!["0", "1", "1", "0"],
-//     )
-// }
-
-// #[test]
-// fn test_list_setitem_1() {
-//     check_output_lists(
-//         r#"
-// var xs = [0,1];
-// xs[-1] = 42;
-// print(xs);
-// "#,
-//         &vec_of_strings!["[0, 42]"],
-//     )
-// }
-
-// #[test]
-// fn test_list_setitem_2() {
-//     check_output_lists(
-//         r#"
-// var xs = [[0,1]];
-// xs[0][1] = 42;
-// print(xs);
-// "#,
-//         &vec_of_strings!["[[0, 42]]"],
-//     )
-// }
-
-// #[test]
-// fn test_list_setitem_3() {
-//     check_output_lists(
-//         r#"
-// class Foo {}
-// var foo = Foo();
-// foo.attr = [0];
-// foo.attr[0] = 1337;
-// print foo.attr;
-// "#,
-//         &vec_of_strings!["[1337]"],
-//     )
-// }
+use crate::common::check_error;
+use crate::common::check_output_lists;
+
+#[test]
+fn test_map_filter_reduce() {
+    check_output_lists(
+        r#"
+fun double(x) { return x * 2; }
+fun isBig(x) { return x > 2; }
+fun add(acc, x) { return acc + x; }
+var xs = [1, 2, 3, 4, 5];
+printLine(reduce(map(xs, double), add, 0));
+printLine(len(filter(xs, isBig)));
+printLine(reduce(filter(xs, isBig), add, 100));
+printLine(join(map("سَلام", upper), "-"));
+"#,
+        &vec_of_strings!["30", "3", "112", "سَ-ل-ا-م"],
+    );
+}
+
+#[test]
+fn test_sort_and_reverse() {
+    check_output_lists(
+        r#"
+fun byLength(a, b) { return len(a) - len(b); }
+var xs = [3, 1.5, 2, -1];
+sort(xs);
+printLine(json.stringify(xs));
+reverse(xs);
+printLine(json.stringify(xs));
+var words = ["pear", "fig", "apple"];
+sort(words);
+printLine(join(words, ","));
+sort(words, byLength);
+printLine(join(words, ","));
+sort(words, nil);
+printLine(join(words, ","));
+"#,
+        &vec_of_strings![
+            "[-1,1.5,2,3]",
+            "[3,2,1.5,-1]",
+            "apple,fig,pear",
+            "fig,pear,apple",
+            "apple,fig,pear"
+        ],
+    );
+}
+
+#[test]
+fn test_zip_and_enumerate() {
+    check_output_lists(
+        r#"
+fun first(pair) { printLine(pair[0]); }
+var pairs = zip(["a", "b", "c"], (1, 2));
+printLine(len(pairs));
+printLine(pairs[1] == ("b", 2));
+forEach(enumerate(["x", "y"]), first);
+"#,
+        &vec_of_strings!["2", "true", "0", "1"],
+    );
+}
+
+#[test]
+fn test_any_all_find() {
+    check_output_lists(
+        r#"
+fun big(x) { return x > 10; }
+printLine(any([1, 20, 3], big));
+printLine(all([1, 20, 3], big));
+printLine(all([], big));
+printLine(find([1, 20, 30], big));
+printLine(find([1, 2], big));
+fun vowel(c) { return c == "i" or c == "a"; }
+printLine(find("firnas", vowel));
+"#,
+        &vec_of_strings!["true", "false", "true", "20", "nil", "i"],
+    );
+}
+
+#[test]
+fn test_index_of_and_contains() {
+    check_output_lists(
+        r#"
+var xs = [1, "two", (3, 4)];
+printLine(indexOf(xs, "two"));
+printLine(indexOf(xs, (3, 4)));
+printLine(indexOf(xs, 5));
+printLine(contains(xs, 1.0));
+printLine(contains((1, 2), 3));
+printLine(contains("firnas", "rna"));
+printLine(indexOf("firnas", "na"));
+printLine(contains("سَلام", "س"));
+"#,
+        &vec_of_strings!["1", "2", "-1", "true", "false", "true", "3", "false"],
+    );
+}
+
+#[test]
+fn test_push_pop_insert_remove() {
+    check_output_lists(
+        r#"
+var xs = [];
+push(xs, 1);
+push(xs, 2);
+insert(xs, 0, 0);
+insert(xs, -1, 5);
+printLine(json.stringify(xs));
+printLine(pop(xs));
+printLine(remove(xs, 5));
+printLine(remove(xs, 5));
+printLine(json.stringify(xs));
+"#,
+        &vec_of_strings!["[0,1,5,2]", "2", "true", "false", "[0,1]"],
+    );
+}
+
+#[test]
+fn test_range() {
+    check_output_lists(
+        r#"
+fun add(a, b) { return a + b; }
+printLine(json.stringify(range(2, 5)));
+printLine(reduce(range(0, 5), add, 0));
+printLine(len(range(3, 1)));
+"#,
+        &vec_of_strings!["[2,3,4]", "10", "0"],
+    );
+}
+
+#[test]
+fn test_range_too_long() {
+    check_error(
+        "range(0, 9000000000000000000);",
+        firnas_ext::Extensions::default(),
+        &|err: &str| {
+            assert_eq!(
+                err,
+                "When calling range: Range from 0 to 9000000000000000000 is too long, it can have at most 100000000 elements."
+            )
+        },
+    );
+}
+
+#[test]
+fn test_nested_callbacks() {
+    check_output_lists(
+        r#"
+fun add(a, b) { return a + b; }
+fun sum(xs) { return reduce(xs, add, 0); }
+fun bySumDescending(a, b) { return sum(b) - sum(a); }
+var rows = [[1, 2], [3, 4, 5]];
+printLine(json.stringify(map(rows, sum)));
+sort(rows, bySumDescending);
+printLine(json.stringify(rows));
+"#,
+        &vec_of_strings!["[3,12]", "[[3,4,5],[1,2]]"],
+    );
+}
+
+#[test]
+fn test_callback_error() {
+    check_error(
+        "fun f(x) { return x + nil; } forEach([1], f);",
+        firnas_ext::Extensions {
+            lists: true,
+            ..Default::default()
+        },
+        &|err: &str| assert!(err.starts_with("When calling forEach: invalid operands")),
+    );
+}
+
+#[test]
+fn test_pop_empty_is_error() {
+    check_error(
+        "pop([]);",
+        firnas_ext::Extensions {
+            lists: true,
+            ..Default::default()
+        },
+        &|err: &str| assert!(err.starts_with("When calling pop: Can't pop from an empty list")),
+    );
+}
+
+#[test]
+fn test_list_building() {
+    check_output_lists("printLine([1,2,3]);", &vec_of_strings!["[1, 2, 3]"])
+}
+
+#[test]
+fn test_empty_list_building() {
+    check_output_lists("printLine([]);", &vec_of_strings!["[]"])
+}
+
+#[test]
+fn test_list_concat() {
+    check_output_lists(
+        "printLine([1,2,3] + [4,5,6]);",
+        &vec_of_strings!["[1, 2, 3, 4, 5, 6]"],
+    )
+}
+
+#[test]
+fn test_len() {
+    check_output_lists(
+        r#"
+printLine(len(""));
+printLine(len("cat"));
+printLine(len([]));
+printLine(len([1,2,3,4]));
+"#,
+        &vec_of_strings!["0", "3", "0", "4"],
+    )
+}
+
+#[test]
+fn test_for_each() {
+    check_output_lists(
+        r#"
+fun f(arg) { printLine(arg); }
+forEach([1,2,3,4], f);
+"#,
+        &vec_of_strings!["1", "2", "3", "4"],
+    )
+}
+
+#[test]
+fn test_map() {
+    check_output_lists(
+        r#"
+fun f(arg) { return arg + 1; }
+printLine(map([1,2,3,4], f));
+"#,
+        &vec_of_strings!["[2, 3, 4, 5]"],
+    )
+}
+
+#[test]
+fn test_list_subscript() {
+    check_output_lists(
+        r#"
+var xs = [0,1];
+printLine(xs[0]);
+printLine(xs[1]);
+printLine(xs[-1]);
+printLine(xs[-2]);
+"#,
+        &vec_of_strings!["0", "1", "1", "0"],
+    )
+}
+
+#[test]
+fn test_list_setitem_1() {
+    check_output_lists(
+        r#"
+var xs = [0,1];
+xs[-1] = 42;
+printLine(xs);
+"#,
+        &vec_of_strings!["[0, 42]"],
+    )
+}
+
+#[test]
+fn test_list_setitem_2() {
+    check_output_lists(
+        r#"
+var xs = [[0,1]];
+xs[0][1] = 42;
+printLine(xs);
+"#,
+        &vec_of_strings!["[[0, 42]]"],
+    )
+}
+
+#[test]
+fn test_list_setitem_3() {
+    check_output_lists(
+        r#"
+class Foo {}
+var foo = Foo();
+foo.attr = [0];
+foo.attr[0] = 1337;
+printLine(foo.attr);
+"#,
+        &vec_of_strings!["[1337]"],
+    )
+}
+
+#[test]
+fn test_print_nested_values() {
+    check_output_lists(
+        r#"
+printLine([1, (2,), {3}, {"a": [true, nil]}, 1.5]);
+printLine("total: " + str([1, 2]));
+printLine(str(3) + " items");
+"#,
+        &vec_of_strings![
+            "[1, (2,), {3}, {a: [true, nil]}, 1.5]",
+            "total: [1, 2]",
+            "3 items"
+        ],
+    );
+}
+
+#[test]
+fn test_print_cycles() {
+    check_output_lists(
+        r#"
+var xs = [1];
+push(xs, xs);
+printLine(xs);
+var d = {"self": nil};
+d["self"] = d;
+d["list"] = [d];
+printLine(d);
+var shared = [0];
+printLine([shared, shared]);
+"#,
+        &vec_of_strings!["[1, [...]]", "{list: [{...}], self: {...}}", "[[0], [0]]"],
+    );
+}
+
+#[test]
+fn test_most_negative_index() {
+    check_error(
+        "var xs = [1, 2]; var i = -9223372036854775807 - 1; xs[i];",
+        firnas_ext::Extensions {
+            lists: true,
+            ..Default::default()
+        },
+        &|err: &str| assert_eq!(err, "List subscript index out of range at 1"),
+    );
+}
@@ -0,0 +1,179 @@
+use crate::common::check_error_default;
+use crate::common::check_output_default;
+
+#[test]
+fn test_trig_and_logs() {
+    check_output_default(
+        r#"
+printLine(sin(0));
+printLine(cos(0));
+printLine(round(tan(PI / 4) * 1000));
+printLine(asin(1) == PI / 2);
+printLine(atan2(1, 1) == PI / 4);
+printLine(log(E));
+printLine(log10(1000));
+printLine(log2(8));
+printLine(hypot(3, 4));
+"#,
+        &vec_of_strings!["0", "1", "1000", "true", "true", "1", "3", "3", "5"],
+    );
+}
+
+#[test]
+fn test_pow() {
+    check_output_default(
+        r#"
+printLine(pow(2, 10));
+printLine(pow(2, 100));
+printLine(pow(2r / 3, 2));
+printLine(pow(2r, -2));
+printLine(pow(4, 0.5));
+printLine(pow(2, -1));
+"#,
+        &vec_of_strings![
+            "1024",
+            "1267650600228229401496703205376",
+            "4/9",
+            "1/4",
+            "2",
+            "0.5"
+        ],
+    );
+}
+
+#[test]
+fn test_exact_power_too_big() {
+    check_output_default(
+        r#"
+printLine(pow(1, 2000000000));
+printLine(pow(-1, 2000000001));
+printLine(len(str(pow(7, 100000))));
+printLine(pow(3.0, 2000000000));
+"#,
+        &vec_of_strings!["1", "-1", "84510", "inf"],
+    );
+    for code in ["pow(3, 2000000000);", "pow(2r / 3, -2000000000);"] {
+        check_error_default(code, &|err: &str| {
+            assert_eq!(
+                err,
+                "When calling pow: The exact power is too big, use a float base for an approximate one."
+            )
+        });
+    }
+}
+
+#[test]
+fn test_rounding() {
+    check_output_default(
+        r#"
+printLine(abs(-3));
+printLine(abs(-1r / 2));
+printLine(abs(-2.5));
+printLine(floor(-2.5));
+printLine(ceil(-2.5));
+printLine(round(2.5));
+printLine(round(-7r / 2));
+printLine(trunc(-2.7));
+printLine(floor(pow(10.0, 20)));
+"#,
+        &vec_of_strings![
+            "3",
+            "1/2",
+            "2.5",
+            "-3",
+            "-2",
+            "3",
+            "-4",
+            "-2",
+            "100000000000000000000"
+        ],
+    );
+}
+
+#[test]
+fn test_min_max() {
+    check_output_default(
+        r#"
+printLine(min(3, 2.5));
+printLine(max(1r / 3, 0.3));
+printLine(max(7, 7.0));
+"#,
+        &vec_of_strings!["2.5", "1/3", "7"],
+    );
+}
+
+#[test]
+fn test_constants() {
+    check_output_default(
+        r#"
+printLine(PI);
+printLine(E);
+printLine(INF > pow(10.0, 308));
+printLine(NAN == NAN);
+"#,
+        &vec_of_strings!["3.141592653589793", "2.718281828459045", "true", "false"],
+    );
+}
+
+#[test]
+fn test_integer_helpers() {
+    check_output_default(
+        r#"
+printLine(gcd(12, 18));
+printLine(lcm(4, 6));
+printLine(gcd(-12, 18));
+printLine(isPrime(2));
+printLine(isPrime(1));
+printLine(isPrime(97));
+printLine(isPrime(561));
+printLine(isPrime(18446744073709551557));
+"#,
+        &vec_of_strings!["6", "12", "6", "true", "false", "true", "false", "true"],
+    );
+}
+
+#[test]
+fn test_integer_helper_type_error() {
+    check_error_default("gcd(1.5, 2);", &|err: &str| {
+        assert!(err.starts_with("When calling gcd: Invalid call: expected integer"))
+    });
+}
+
+#[test]
+fn test_rounding_infinity_is_error() {
+    check_error_default("floor(INF);", &|err: &str| {
+        assert!(err.starts_with("When calling floor: Can't round NaN or infinity"))
+    });
+}
+
+#[test]
+fn test_to_arabic_words() {
+    check_output_default(
+        r#"
+printLine(toArabicWords(1250));
+printLine(toArabicWords(-3.05));
+printLine(toArabicWords(0.1 + 0.2));
+printLine(amountToArabicWords(1250.75, "riyal"));
+printLine(amountToArabicWords(3.25, "dinar"));
+"#,
+        &vec_of_strings![
+            "ألف ومائتان وخمسون",
+            "سالب ثلاثة فاصلة صفر خمسة",
+            "صفر فاصلة ثلاثة",
+            "ألف ومائتان وخمسون ريالاً وخمس وسبعون هللةً",
+            "ثلاثة دنانير ومائتان وخمسون فلساً"
+        ],
+    );
+}
+
+#[test]
+fn test_to_arabic_words_errors() {
+    check_error_default("toArabicWords(1000000000000000);", &|err: &str| {
+        assert!(err.starts_with(
+            "When calling toArabicWords: Can't write numbers from a quadrillion on in words"
+        ))
+    });
+    check_error_default("amountToArabicWords(1, \"euro\");", &|err: &str| {
+        assert!(err.contains("Unknown currency 'euro'"), "{}", err)
+    });
+}
@@ -1,5 +1,18 @@
 pub mod class_tests;
 pub mod control_flow_tests;
+pub mod date_tests;
+pub mod dict_tests;
+pub mod file_tests;
 pub mod function_tests;
+pub mod io_tests;
+pub mod json_tests;
 pub mod list_tests;
+pub mod math_tests;
+pub mod random_tests;
+pub mod reflect_tests;
+pub mod regex_tests;
+pub mod set_tests;
 pub mod simple_tests;
+pub mod slice_tests;
+pub mod string_tests;
+pub mod tuple_tests;
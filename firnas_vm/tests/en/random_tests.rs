@@ -0,0 +1,67 @@
+use crate::common::check_error_default;
+use crate::common::check_output_default;
+use crate::common::check_output_lists;
+
+#[test]
+fn test_seed_makes_random_reproducible() {
+    check_output_default(
+        r#"
+seed(42);
+var a = random();
+var b = randint(1, 1000000);
+seed(42);
+printLine(a == random());
+printLine(b == randint(1, 1000000));
+printLine(a >= 0 and a < 1);
+"#,
+        &vec_of_strings!["true", "true", "true"],
+    );
+}
+
+#[test]
+fn test_randint_stays_in_range() {
+    check_output_default(
+        r#"
+seed(7);
+var inRange = true;
+var sawLow = false;
+var sawHigh = false;
+for (var i = 0; i < 200; i = i + 1) {
+    var n = randint(-2, 2);
+    if (n < -2 or n > 2) { inRange = false; }
+    if (n == -2) { sawLow = true; }
+    if (n == 2) { sawHigh = true; }
+}
+printLine(inRange);
+printLine(sawLow and sawHigh);
+printLine(randint(5, 5));
+"#,
+        &vec_of_strings!["true", "true", "5"],
+    );
+}
+
+#[test]
+fn test_choice_and_shuffle() {
+    check_output_lists(
+        r#"
+seed(1);
+var xs = [1, 2, 3, 4, 5];
+shuffle(xs);
+var total = 0;
+fun add(x) { total = total + x; }
+forEach(xs, add);
+printLine(len(xs));
+printLine(total);
+var c = choice((10, 20, 30));
+printLine(c == 10 or c == 20 or c == 30);
+"#,
+        &vec_of_strings!["5", "15", "true"],
+    );
+}
+
+#[test]
+fn test_randint_empty_range_is_error() {
+    check_error_default("randint(3, 1);", &|err: &str| {
+        assert!(err.starts_with("When calling randint: Empty range from 3 to 1"))
+    });
+}
@@ -0,0 +1,122 @@
+use crate::common::check_error_default;
+use crate::common::check_output_default;
+use crate::common::check_output_lists;
+use crate::common::evaluate_in;
+use firnas_compiler::diagnostic::Language;
+use firnas_vm::virtual_machine::VirtualMachine;
+
+#[test]
+fn test_type() {
+    check_output_lists(
+        r#"
+class Foo {}
+printLine(type(1));
+printLine(type(1.5));
+printLine(type("s"));
+printLine(type(nil));
+printLine(type([]));
+printLine(type({:}));
+printLine(type(Foo));
+printLine(type(Foo()));
+printLine(type(printLine));
+"#,
+        &vec_of_strings![
+            "Integer",
+            "Number",
+            "String",
+            "Nil",
+            "List",
+            "Dict",
+            "Class",
+            "Instance",
+            "NativeFunction"
+        ],
+    );
+}
+
+#[test]
+fn test_type_names_ignore_error_language() {
+    let mut vm = VirtualMachine::default();
+    vm.language = Language::Arabic;
+    let output = evaluate_in(
+        &mut vm,
+        "printLine(type(1)); printLine(type(printLine));",
+        firnas_ext::Extensions::default(),
+    );
+    assert_eq!(
+        output,
+        Ok(vec_of_strings!["Integer", "NativeFunction"].to_vec())
+    );
+}
+
+#[test]
+fn test_discover_test_methods() {
+    check_output_lists(
+        r#"
+class Base {
+  testInherited() { printLine("inherited"); }
+}
+class Suite < Base {
+  setUp() {}
+  testOne() { printLine("one"); }
+  testTwo() { printLine("two"); }
+}
+var suite = Suite();
+fun isTest(name) { return startsWith(name, "test"); }
+fun run(name) { getAttr(suite, name)(); }
+forEach(filter(methods(Suite), isTest), run);
+printLine(name(superclass(Suite)));
+printLine(superclass(Base));
+"#,
+        &vec_of_strings!["inherited", "one", "two", "Base", "nil"],
+    );
+}
+
+#[test]
+fn test_fields_and_attrs() {
+    check_output_lists(
+        r#"
+class Point {
+  init(x, y) { this.y = y; this.x = x; }
+  norm() { return this.x + this.y; }
+}
+var p = Point(1, 2);
+printLine(join(fields(p), ","));
+printLine(hasAttr(p, "x"));
+printLine(hasAttr(p, "norm"));
+printLine(hasAttr(p, "z"));
+setAttr(p, "z", 3);
+printLine(getAttr(p, "z") + getAttr(p, "norm")());
+"#,
+        &vec_of_strings!["x,y", "true", "true", "false", "6"],
+    );
+}
+
+#[test]
+fn test_arity_and_name() {
+    check_output_default(
+        r#"
+fun add(a, b) { return a + b; }
+class Point {
+  init(x, y) {}
+  move(dx) {}
+}
+printLine(arity(add));
+printLine(name(add));
+printLine(arity(Point));
+printLine(name(Point));
+printLine(arity(Point(1, 2).move));
+printLine(name(Point(1, 2).move));
+printLine(arity(substring));
+printLine(name(substring));
+"#,
+        &vec_of_strings!["2", "add", "2", "Point", "1", "move", "3", "substring"],
+    );
+}
+
+#[test]
+fn test_get_attr_missing_is_error() {
+    check_error_default("class Foo {} getAttr(Foo(), \"bar\");", &|err: &str| {
+        assert!(err.starts_with("When calling getAttr: ") && err.contains("has no attribute bar"))
+    });
+}
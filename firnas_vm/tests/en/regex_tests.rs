@@ -0,0 +1,82 @@
+use crate::common::check_error_default;
+use crate::common::check_output_default;
+
+#[test]
+fn test_compile_and_test() {
+    check_output_default(
+        r#"
+var digits = regex.compile("^\d+$");
+printLine(digits);
+printLine(regex.test(digits, "12345"));
+printLine(regex.test(digits, "12a45"));
+printLine(regex.test("a.c", "xxabcxx"));
+"#,
+        &vec_of_strings!["<regex '^\\d+$'>", "true", "false", "true"],
+    );
+}
+
+#[test]
+fn test_find_and_find_all() {
+    check_output_default(
+        r#"
+printLine(regex.find("\d+", "abc 12 def 345"));
+printLine(regex.find("\d+", "abc"));
+printLine(join(regex.findAll("\d+", "abc 12 def 345"), ","));
+printLine(len(regex.findAll("\d+", "abc")));
+"#,
+        &vec_of_strings!["12", "nil", "12,345", "0"],
+    );
+}
+
+#[test]
+fn test_captures() {
+    check_output_default(
+        r#"
+var groups = regex.captures("(\w+)@(\w+)(\.com)?", "mail ali@example now");
+printLine(len(groups));
+printLine(join(regex.captures("(\w+)@(\w+)", "ali@example"), " "));
+printLine(regex.captures("x", "abc"));
+"#,
+        &vec_of_strings!["4", "ali@example ali example", "nil"],
+    );
+}
+
+#[test]
+fn test_replace_and_split() {
+    check_output_default(
+        r#"
+printLine(regex.replace("(\w+)@(\w+)", "ali@example", "$2 at ${1}"));
+printLine(regex.replace("\s+", "a  b   c", " "));
+printLine(join(regex.split("\s*,\s*", "a , b,c"), "|"));
+"#,
+        &vec_of_strings!["example at ali", "a b c", "a|b|c"],
+    );
+}
+
+#[test]
+fn test_arabic_text_with_diacritics() {
+    check_output_default(
+        r#"
+printLine(join(regex.findAll("\w+", "السَّلامُ عَلَيْكُم"), "|"));
+printLine(regex.replace("\p{Mn}", "السَّلامُ عَلَيْكُم", ""));
+printLine(regex.test("^\p{Arabic}+$", "كتاب"));
+"#,
+        &vec_of_strings!["السَّلامُ|عَلَيْكُم", "السلام عليكم", "true"],
+    );
+}
+
+#[test]
+fn test_invalid_pattern() {
+    check_error_default(r#"regex.compile("(ab");"#, &|err: &str| {
+        assert_eq!(
+            err,
+            "When calling regex.compile: Invalid regular expression '(ab': error: unclosed group."
+        )
+    });
+    check_error_default("regex.test(1, \"a\");", &|err: &str| {
+        assert_eq!(
+            err,
+            "When calling regex.test: Invalid call: expected regex or string, got Integer."
+        )
+    });
+}
@@ -0,0 +1,90 @@
+use crate::common::check_error_default;
+use crate::common::check_output_default;
+
+#[test]
+fn test_set_building() {
+    check_output_default(
+        r#"
+printLine({3, 1, 2, 1});
+printLine({});
+printLine(len({1, 1.0, 1r, "1"}));
+"#,
+        &vec_of_strings!["{1, 2, 3}", "{}", "2"],
+    );
+}
+
+#[test]
+fn test_set_add_remove_contains() {
+    check_output_default(
+        r#"
+var s = {"a"};
+add(s, "b");
+add(s, (1, 2));
+printLine(contains(s, "b"));
+printLine(contains(s, (1, 2)));
+printLine(remove(s, "a"));
+printLine(remove(s, "a"));
+printLine(contains(s, "a"));
+printLine(len(s));
+"#,
+        &vec_of_strings!["true", "true", "true", "false", "false", "2"],
+    );
+}
+
+#[test]
+fn test_float_and_rational_elements_agree_with_equality() {
+    check_output_default(
+        r#"
+printLine(0.1 == 0.1r);
+printLine(contains({0.1}, 0.1r));
+printLine(0.5 == 1r / 2);
+printLine(contains({0.5}, 1r / 2));
+var d = {0.25: "float", 1r / 4: "rational", 0.1r: "tenth"};
+printLine(len(d));
+printLine(contains(d, 0.1));
+"#,
+        &vec_of_strings!["false", "false", "true", "true", "2", "false"],
+    );
+}
+
+#[test]
+fn test_set_operators() {
+    check_output_default(
+        r#"
+var a = {1, 2, 3};
+var b = {2, 3, 4};
+printLine(a | b);
+printLine(a & b);
+printLine(a - b);
+printLine(a | b == {4, 3, 2, 1});
+"#,
+        &vec_of_strings!["{1, 2, 3, 4}", "{2, 3}", "{1}", "true"],
+    );
+}
+
+#[test]
+fn test_set_for_each() {
+    check_output_default(
+        r#"
+var total = 0;
+fun f(x) { total = total + x; }
+forEach({1, 2, 3, 3}, f);
+printLine(total);
+"#,
+        &vec_of_strings!["6"],
+    );
+}
+
+#[test]
+fn test_unhashable_set_element_is_error() {
+    check_error_default("var s = {{1}};", &|err: &str| {
+        assert!(err.starts_with("Unhashable value of type Set"))
+    });
+}
+
+#[test]
+fn test_set_operator_on_non_sets_is_error() {
+    check_error_default("printLine(1 | 2);", &|err: &str| {
+        assert!(err.starts_with("Expected sets in Union expression"))
+    });
+}
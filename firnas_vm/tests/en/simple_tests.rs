@@ -111,3 +111,81 @@ printLine(y);
         &vec_of_strings!["0", "1"],
     );
 }
+
+#[test]
+fn it_should_keep_integer_arithmetic_exact() {
+    check_output_default(
+        r#"
+printLine(9007199254740993 + 2);
+printLine(6 / 3);
+printLine(7 / 2);
+printLine(2 * 1.5);
+"#,
+        &vec_of_strings!["9007199254740995", "2", "3.5", "3"],
+    );
+}
+
+#[test]
+fn it_should_promote_integer_overflow_to_big_integer() {
+    check_output_default(
+        r#"
+printLine(9223372036854775807 + 1);
+printLine(-9223372036854775807 - 2);
+printLine(123456789012345678901234567890 * 10);
+printLine(123456789012345678901234567890 - 123456789012345678901234567889);
+"#,
+        &vec_of_strings![
+            "9223372036854775808",
+            "-9223372036854775809",
+            "1234567890123456789012345678900",
+            "1"
+        ],
+    );
+}
+
+#[test]
+fn it_should_compare_integers_and_floats() {
+    check_output_default(
+        r#"
+printLine(1 == 1.0);
+printLine(2 > 1.5);
+printLine(2 < 1.5);
+"#,
+        &vec_of_strings!["true", "true", "false"],
+    );
+}
+
+#[test]
+fn it_should_keep_rational_arithmetic_exact() {
+    check_output_default(
+        r#"
+printLine(0.1r + 0.2r == 0.3r);
+printLine(1r / 3);
+printLine(1r / 3 * 3);
+printLine(rational(2, -4));
+printLine(1r / 3 < 0.34);
+"#,
+        &vec_of_strings!["true", "1/3", "1", "-1/2", "true"],
+    );
+}
+
+#[test]
+fn it_should_convert_between_number_kinds() {
+    check_output_default(
+        r#"
+printLine(int(7.9));
+printLine(int(-7r / 2));
+printLine(float(1r / 4));
+printLine(numerator(0.75r));
+printLine(denominator(0.75r));
+"#,
+        &vec_of_strings!["7", "-3", "0.25", "3", "4"],
+    );
+}
+
+#[test]
+fn it_should_fail_on_rational_division_by_zero() {
+    check_error_default("printLine(1r / 0);", &|err: &str| {
+        assert!(err.starts_with("Division of a rational by zero"))
+    })
+}
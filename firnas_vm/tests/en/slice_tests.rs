@@ -0,0 +1,123 @@
+use crate::common::check_error;
+use crate::common::check_output_lists;
+
+fn lists() -> firnas_ext::Extensions {
+    firnas_ext::Extensions {
+        lists: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_tuple_slicing() {
+    check_output_lists(
+        r#"
+var t = (0, 1, 2, 3, 4, 5);
+printLine(t[1:3]);
+printLine(t[:2]);
+printLine(t[4:]);
+printLine(t[:]);
+printLine(t[-2:]);
+printLine(t[::2]);
+printLine(t[::-1]);
+printLine(t[4:1:-2]);
+printLine(t[10:20]);
+"#,
+        &vec_of_strings![
+            "(1, 2)",
+            "(0, 1)",
+            "(4, 5)",
+            "(0, 1, 2, 3, 4, 5)",
+            "(4, 5)",
+            "(0, 2, 4)",
+            "(5, 4, 3, 2, 1, 0)",
+            "(4, 2)",
+            "()"
+        ],
+    );
+}
+
+#[test]
+fn test_list_slicing() {
+    check_output_lists(
+        r#"
+var xs = [0, 1, 2, 3, 4];
+var ys = xs[1:-1];
+printLine(len(ys));
+printLine(ys[0]);
+printLine(ys[-1]);
+ys[0] = 10;
+printLine(xs[1]);
+"#,
+        &vec_of_strings!["3", "1", "3", "1"],
+    );
+}
+
+#[test]
+fn test_string_slicing() {
+    check_output_lists(
+        r#"
+var s = "hello";
+printLine(s[1:4]);
+printLine(s[::-1]);
+printLine(s[-3:]);
+"#,
+        &vec_of_strings!["ell", "olleh", "llo"],
+    );
+}
+
+#[test]
+fn test_string_slicing_keeps_combining_marks() {
+    check_output_lists(
+        r#"
+var s = "سَلامٌ";
+printLine(s[0:1]);
+printLine(s[-1:]);
+"#,
+        &vec_of_strings!["سَ", "مٌ"],
+    );
+}
+
+#[test]
+fn test_slice_assignment() {
+    check_output_lists(
+        r#"
+var xs = [0, 1, 2, 3, 4];
+xs[1:3] = (10, 20, 30);
+printLine(len(xs));
+printLine(xs[3]);
+xs[:2] = [];
+printLine(xs[0]);
+xs[::2] = [7, 8];
+printLine(xs[0]);
+printLine(xs[1]);
+printLine(xs[2]);
+"#,
+        &vec_of_strings!["6", "30", "20", "7", "30", "8"],
+    );
+}
+
+#[test]
+fn test_extended_slice_assignment_size_mismatch_is_error() {
+    check_error(
+        "var xs = [0, 1, 2, 3]; xs[::2] = [1];",
+        lists(),
+        &|err: &str| {
+            assert!(err.starts_with("Can't assign 1 values to an extended slice of size 2"))
+        },
+    );
+}
+
+#[test]
+fn test_tuple_slice_assignment_is_error() {
+    check_error("var t = (0, 1, 2); t[0:1] = [5];", lists(), &|err: &str| {
+        assert!(err.starts_with("Can't assign to a slice of value of type Tuple"))
+    });
+}
+
+#[test]
+fn test_zero_slice_step_is_error() {
+    check_error("var t = (0, 1); t[::0];", lists(), &|err: &str| {
+        assert!(err.starts_with("Slice step can't be zero"))
+    });
+}
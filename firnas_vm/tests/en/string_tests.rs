@@ -0,0 +1,129 @@
+use crate::common::check_error_default;
+use crate::common::check_output_default;
+
+#[test]
+fn test_len_counts_graphemes() {
+    check_output_default(
+        r#"
+printLine(len("cat"));
+printLine(len("سلام"));
+printLine(len("سَلامٌ"));
+printLine(len(""));
+"#,
+        &vec_of_strings!["3", "4", "4", "0"],
+    );
+}
+
+#[test]
+fn test_substring_and_find() {
+    check_output_default(
+        r#"
+printLine(substring("hello world", 6, 11));
+printLine(substring("hello", -3, 100));
+printLine(substring("سَلامٌ", 1, 3));
+printLine(findSubstring("hello", "llo"));
+printLine(findSubstring("سَلامٌ", "ا"));
+printLine(findSubstring("hello", "z"));
+printLine(findSubstring("سَلام", "س"));
+printLine(findSubstring("سَلام", "لا"));
+"#,
+        &vec_of_strings!["world", "llo", "لا", "2", "2", "-1", "-1", "1"],
+    );
+}
+
+#[test]
+fn test_replace_split_join() {
+    check_output_default(
+        r#"
+printLine(replace("a-b-c", "-", "+"));
+var parts = split("a,b,c", ",");
+printLine(len(parts));
+printLine(join(parts, " "));
+printLine(join(split("سلام", ""), "."));
+"#,
+        &vec_of_strings!["a+b+c", "3", "a b c", "س.ل.ا.م"],
+    );
+}
+
+#[test]
+fn test_trim_upper_lower() {
+    check_output_default(
+        r#"
+printLine(trim("  hi  "));
+printLine(upper("Hi"));
+printLine(lower("Hi"));
+"#,
+        &vec_of_strings!["hi", "HI", "hi"],
+    );
+}
+
+#[test]
+fn test_starts_and_ends_with() {
+    check_output_default(
+        r#"
+printLine(startsWith("hello", "he"));
+printLine(endsWith("hello", "lo"));
+printLine(endsWith("سلامٌ", "م"));
+printLine(endsWith("سلامٌ", "مٌ"));
+"#,
+        &vec_of_strings!["true", "true", "false", "true"],
+    );
+}
+
+#[test]
+fn test_character_iteration() {
+    check_output_default(
+        r#"
+fun f(c) { printLine(c); }
+forEach("سَلا", f);
+printLine(len(chars("hello")));
+"#,
+        &vec_of_strings!["سَ", "ل", "ا", "5"],
+    );
+}
+
+#[test]
+fn test_string_function_type_error() {
+    check_error_default("upper(1);", &|err: &str| {
+        assert!(err.starts_with("When calling upper: Invalid call: expected string"))
+    });
+}
+
+#[test]
+fn test_compare_strings() {
+    check_output_default(
+        r#"
+printLine("apple" < "banana");
+printLine("پدر" < "تاج");
+useCollation("codepoint");
+printLine("پدر" < "تاج");
+"#,
+        &vec_of_strings!["true", "true", "false"],
+    );
+}
+
+#[test]
+fn test_compare_string_with_number() {
+    check_error_default("\"a\" < 1;", &|err: &str| {
+        assert!(err.starts_with(
+            "invalid operands in Less expression. Expected numbers, strings or tuples"
+        ))
+    });
+}
+
+#[test]
+fn test_concatenation_needs_explicit_conversion() {
+    check_output_default(
+        r#"
+printLine("total: " + str(3));
+printLine(str(1.5) + str(nil) + str(true) + str("!"));
+"#,
+        &vec_of_strings!["total: 3", "1.5niltrue!"],
+    );
+    check_error_default("\"total: \" + 3;", &|err: &str| {
+        assert_eq!(
+            err,
+            "invalid operands of type Integer and String in add expression: both operands must be number or string (line=1)"
+        )
+    });
+}
@@ -0,0 +1,114 @@
+use crate::common::check_error;
+use crate::common::check_error_default;
+use crate::common::check_output_default;
+use crate::common::check_output_lists;
+
+#[test]
+fn test_tuple_building() {
+    check_output_default(
+        r#"
+printLine((1, "two", 3.5));
+printLine((1,));
+printLine(());
+printLine((1));
+"#,
+        &vec_of_strings!["(1, two, 3.5)", "(1,)", "()", "1"],
+    );
+}
+
+#[test]
+fn test_tuple_subscript() {
+    check_output_lists(
+        r#"
+var t = (1, 2, 3);
+printLine(t[0]);
+printLine(t[-1]);
+printLine(len(t));
+"#,
+        &vec_of_strings!["1", "3", "3"],
+    );
+}
+
+#[test]
+fn test_tuple_setitem_is_error() {
+    check_error(
+        r#"
+var t = (1, 2, 3);
+t[0] = 5;
+"#,
+        firnas_ext::Extensions {
+            lists: true,
+            ..Default::default()
+        },
+        &|err: &str| assert!(err.starts_with("Tuples are immutable")),
+    );
+}
+
+#[test]
+fn test_tuple_equality() {
+    check_output_default(
+        r#"
+printLine((1, (2, "a")) == (1.0, (2, "a")));
+printLine((1, 2) == (1, 2, 3));
+printLine((1, 2) == (2, 1));
+"#,
+        &vec_of_strings!["true", "false", "false"],
+    );
+}
+
+#[test]
+fn test_tuple_comparison() {
+    check_output_default(
+        r#"
+printLine((1, 2) < (1, 3));
+printLine((2, 0) > (1, 9));
+printLine((1, 2) < (1, 2, 0));
+printLine((1, 2) <= (1, 2));
+"#,
+        &vec_of_strings!["true", "true", "true", "true"],
+    );
+}
+
+#[test]
+fn test_tuple_concat() {
+    check_output_default("printLine((1, 2) + (3,));", &vec_of_strings!["(1, 2, 3)"]);
+}
+
+#[test]
+fn test_global_destructuring() {
+    check_output_default(
+        r#"
+var (a, b, c) = (1, 2, 3);
+printLine(a);
+printLine(b);
+printLine(c);
+"#,
+        &vec_of_strings!["1", "2", "3"],
+    );
+}
+
+#[test]
+fn test_local_destructuring() {
+    check_output_default(
+        r#"
+fun divmod(a, b) {
+    return (a / b, a - b * (a / b));
+}
+{
+    var x = 0;
+    var (q, r) = divmod(10, 5);
+    printLine(q);
+    printLine(r);
+    printLine(x);
+}
+"#,
+        &vec_of_strings!["2", "0", "0"],
+    );
+}
+
+#[test]
+fn test_destructuring_length_mismatch_is_error() {
+    check_error_default("var (a, b) = (1, 2, 3);", &|err: &str| {
+        assert!(err.starts_with("Expected 2 values to unpack, found 3"))
+    });
+}